@@ -22,7 +22,12 @@ pub enum Commands {
         file: PathBuf,
     },
     /// Type check
-    Check { path: PathBuf },
+    Check {
+        path: PathBuf,
+        /// Print the inferred type of every expression
+        #[arg(long)]
+        types: bool,
+    },
     ///  Symbol table
     Symbols { path: PathBuf },
 
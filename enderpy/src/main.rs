@@ -17,7 +17,7 @@ fn main() -> Result<()> {
     match &cli.command {
         Commands::Tokenize {} => tokenize(),
         Commands::Parse { file } => parse(file),
-        Commands::Check { path } => check(path),
+        Commands::Check { path, types } => check(path, *types),
         Commands::Watch => watch(),
         Commands::Symbols { path } => symbols(path),
     }
@@ -112,7 +112,7 @@ fn parse(file: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-fn check(path: &Path) -> Result<()> {
+fn check(path: &Path, dump_types: bool) -> Result<()> {
     if path.is_dir() {
         bail!("Path must be a file");
     }
@@ -129,8 +129,16 @@ fn check(path: &Path) -> Result<()> {
     let id = build_manager.paths.get(path).unwrap();
     let file = build_manager.files.get(&id).unwrap();
     let checker = build_manager.type_check(path, &file);
-    print!("{}", checker.dump_types());
+    if dump_types {
+        print!("{}", checker.dump_types());
+    }
+    for diagnostic in checker.diagnostics.iter() {
+        println!("{}:{}", path.display(), diagnostic);
+    }
 
+    if checker.has_errors() {
+        bail!("Found {} diagnostics", checker.diagnostics.len());
+    }
     Ok(())
 }
 
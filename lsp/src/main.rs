@@ -1,6 +1,8 @@
 use std::path::PathBuf;
 
-use enderpy_python_type_checker::{build::BuildManager, find_project_root, settings::Settings};
+use enderpy_python_type_checker::{
+    build::BuildManager, diagnostic, find_project_root, settings::Settings,
+};
 use env_logger::Builder;
 use log::LevelFilter;
 use tower_lsp::{jsonrpc::Result, lsp_types::*, Client, LanguageServer, LspService, Server};
@@ -12,12 +14,51 @@ struct Backend {
 }
 
 impl<'a> Backend {
-    fn build(&self, path: PathBuf) {
+    fn build(&self, path: PathBuf) -> Vec<Diagnostic> {
         let root = find_project_root(&path);
         self.manager.build_one(root, &path);
         let file_id = self.manager.paths.get(&path).unwrap();
         let file = self.manager.files.get(&file_id).unwrap();
-        self.manager.type_check(&path, &file);
+        let checker = self.manager.type_check(&path, &file);
+        checker.diagnostics.iter().map(to_lsp_diagnostic).collect()
+    }
+
+    async fn check(&self, uri: Url) {
+        let Ok(path) = uri.to_file_path() else {
+            return;
+        };
+        let diagnostics = self.build(path);
+        self.client
+            .publish_diagnostics(uri, diagnostics, None)
+            .await;
+    }
+}
+
+fn to_lsp_diagnostic(d: &diagnostic::Diagnostic) -> Diagnostic {
+    // Enderpy lines are 1-based, LSP lines are 0-based.
+    let to_lsp_position = |p: &diagnostic::Position| Position {
+        line: p.line.saturating_sub(1),
+        character: p.character,
+    };
+    let severity = match d.severity {
+        diagnostic::Severity::Error => DiagnosticSeverity::ERROR,
+        diagnostic::Severity::Warning => DiagnosticSeverity::WARNING,
+        diagnostic::Severity::Information => DiagnosticSeverity::INFORMATION,
+    };
+    let mut message = d.body.clone();
+    if let Some(suggestion) = &d.suggestion {
+        message.push_str(&format!("\n{suggestion}"));
+    }
+    Diagnostic {
+        range: Range {
+            start: to_lsp_position(&d.range.start),
+            end: to_lsp_position(&d.range.end),
+        },
+        severity: Some(severity),
+        code: Some(NumberOrString::String(d.code.to_string())),
+        source: Some("enderpy".to_string()),
+        message,
+        ..Diagnostic::default()
     }
 }
 
@@ -53,33 +94,21 @@ impl LanguageServer for Backend {
         self.client
             .log_message(MessageType::INFO, "file saved!")
             .await;
-        let uri = params.text_document.uri;
-        let path = uri.to_file_path();
-        if let Ok(path) = path {
-            self.build(path);
-        }
+        self.check(params.text_document.uri).await;
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
         self.client
             .log_message(MessageType::INFO, "file opened!")
             .await;
-        let uri = params.text_document.uri;
-        let path = uri.to_file_path();
-        if let Ok(path) = path {
-            self.build(path);
-        }
+        self.check(params.text_document.uri).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         self.client
             .log_message(MessageType::INFO, "file changed!")
             .await;
-        let uri = params.text_document.uri;
-        let path = uri.to_file_path();
-        if let Ok(path) = path {
-            self.build(path);
-        }
+        self.check(params.text_document.uri).await;
     }
 
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
@@ -1,5 +1,5 @@
 use is_macro::Is;
-use std::borrow::Cow;
+use num_bigint::BigInt;
 use std::fmt::{self};
 use std::sync::Arc;
 
@@ -91,6 +91,7 @@ pub enum Statement {
     ClassDef(Arc<ClassDef>),
     MatchStmt(Box<Match>),
     TypeAlias(Box<TypeAlias>),
+    Invalid(Box<Invalid>),
 }
 
 impl GetNode for Statement {
@@ -124,6 +125,7 @@ impl GetNode for Statement {
             Statement::ClassDef(s) => s.node,
             Statement::MatchStmt(s) => s.node,
             Statement::TypeAlias(s) => s.node,
+            Statement::Invalid(s) => s.node,
         }
     }
 }
@@ -357,113 +359,19 @@ pub struct Constant {
     pub value: ConstantValue,
 }
 
-impl Constant {
-    pub fn get_value<'a>(&self, source: &'a str) -> Cow<'a, str> {
-        match &self.value {
-            ConstantValue::Str(quote_type) => match quote_type {
-                QuoteType::Single => Cow::Borrowed(
-                    &source[(self.node.start + 1) as usize..(self.node.end - 1) as usize],
-                ),
-                QuoteType::Triple => Cow::Borrowed(
-                    &source[(self.node.start + 3) as usize..(self.node.end - 3) as usize],
-                ),
-                QuoteType::Concat => {
-                    let input = &source[(self.node.start) as usize..(self.node.end) as usize];
-                    let mut result = String::new();
-                    let mut chars = input.chars().peekable();
-
-                    while let Some(c) = chars.next() {
-                        let quote_type = match c {
-                            '\'' => {
-                                if chars.peek() == Some(&'\'') && chars.nth(1) == Some('\'') {
-                                    // Triple single quote
-                                    "'''"
-                                } else {
-                                    // Single quote
-                                    "'"
-                                }
-                            }
-                            '"' => {
-                                if chars.peek() == Some(&'"') && chars.nth(1) == Some('"') {
-                                    // Triple double quote
-                                    "\"\"\""
-                                } else {
-                                    // Double quote
-                                    "\""
-                                }
-                            }
-                            _ => continue, // Ignore any non-quote characters
-                        };
-
-                        // Extract content between quotes
-                        let mut content = String::new();
-                        let mut quote_ending = quote_type.chars().peekable();
-
-                        for next_char in chars.by_ref() {
-                            // Check for quote ending
-                            if Some(&next_char) == quote_ending.peek() {
-                                quote_ending.next();
-                                if quote_ending.peek().is_none() {
-                                    break; // End of the string literal
-                                }
-                            } else {
-                                content.push(next_char);
-                                quote_ending = quote_type.chars().peekable(); // Reset the ending check
-                            }
-                        }
-
-                        // Concatenate the cleaned-up string
-                        result.push_str(&content);
-                    }
-
-                    Cow::Owned(result)
-                }
-            },
-            ConstantValue::Bool(b) => {
-                if *b {
-                    Cow::Borrowed("true")
-                } else {
-                    Cow::Borrowed("false")
-                }
-            }
-            _ => todo!("Call the parser and get the value"),
-        }
-    }
-}
-
 #[derive(Clone, PartialEq, Debug)]
 pub enum ConstantValue {
     None,
     Ellipsis,
     Bool(bool),
-    // If the string start with triple quotes or single
-    // true => triple
-    // false => single
-    Str(QuoteType),
-    // Str,
-    Bytes,
+    // Decoded value of the string with escape sequences processed.
+    // Implicitly concatenated strings are stored as one value.
+    Str(String),
+    Bytes(Vec<u8>),
     Tuple,
-    // Numbers are string because we don't care about the value rn.
-    Int,
-    Float,
-    Complex,
-}
-
-#[derive(Clone, PartialEq, Debug)]
-pub enum QuoteType {
-    Single,
-    Triple,
-    // When this string was created because two strings were concatenated
-    Concat,
-}
-
-impl From<&str> for QuoteType {
-    fn from(value: &str) -> Self {
-        if value.starts_with("\"\"\"") || value.starts_with("'''") {
-            return Self::Triple;
-        }
-        Self::Single
-    }
+    Int(BigInt),
+    Float(f64),
+    Complex { real: f64, imaginary: f64 },
 }
 
 #[derive(Debug, Clone)]
@@ -521,6 +429,19 @@ pub enum UnaryOperator {
     USub,
 }
 
+impl std::fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let op_str = match self {
+            UnaryOperator::Not => "not",
+            UnaryOperator::Invert => "~",
+            UnaryOperator::UAdd => "+",
+            UnaryOperator::USub => "-",
+        };
+
+        write!(f, "{}", op_str)
+    }
+}
+
 // https://docs.python.org/3/library/ast.html#ast.BinOp
 #[derive(Debug, Clone)]
 pub struct BinOp {
@@ -714,6 +635,25 @@ pub enum ComparisonOperator {
     NotIn,
 }
 
+impl std::fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let op_str = match self {
+            ComparisonOperator::Eq => "==",
+            ComparisonOperator::NotEq => "!=",
+            ComparisonOperator::Lt => "<",
+            ComparisonOperator::LtE => "<=",
+            ComparisonOperator::Gt => ">",
+            ComparisonOperator::GtE => ">=",
+            ComparisonOperator::Is => "is",
+            ComparisonOperator::IsNot => "is not",
+            ComparisonOperator::In => "in",
+            ComparisonOperator::NotIn => "not in",
+        };
+
+        write!(f, "{}", op_str)
+    }
+}
+
 // https://docs.python.org/3/library/ast.html#ast.Lambda
 #[derive(Debug, Clone)]
 pub struct Lambda {
@@ -1112,6 +1052,13 @@ pub struct TypeAlias {
     pub value: Expression,
 }
 
+// A statement with a syntax error. The parser skips to the end of the line
+// and continues from the next statement.
+#[derive(Debug, Clone)]
+pub struct Invalid {
+    pub node: Node,
+}
+
 impl Module {
     pub fn new(node: Node, body: Vec<Statement>) -> Self {
         Self { node, body }
@@ -3,7 +3,7 @@ use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug, Clone)]
 pub enum ParsingError {
-    #[error("Invalid syntax")]
+    #[error("Invalid syntax: {msg}")]
     #[diagnostic(code(parser::invalid_syntax))]
     InvalidSyntax {
         msg: String,
@@ -18,6 +18,8 @@ pub enum ParsingError {
 pub enum LexError {
     #[error("String not terminated")]
     StringNotTerminated,
+    #[error("F-string not terminated")]
+    FStringNotTerminated,
     #[error("Invalid char {0} in binary literal. Must be 0 or 1")]
     InvalidDigitInBinaryLiteral(char),
     #[error("Invalid char {0} in octal literal. Must be between 0 and 7")]
@@ -15,21 +15,13 @@ enum StringQuotation {
 }
 
 impl StringQuotation {
-    pub fn new(quote: char, count: u8) -> Self {
-        if quote == '\'' {
-            match count {
-                1 => return Self::Single,
-                3 => return Self::TripleSingle,
-                _ => panic!("Invalid quotation string: {}", quote),
-            }
-        } else if quote == '"' {
-            match count {
-                1 => return Self::Double,
-                3 => return Self::TripleDouble,
-                _ => panic!("Invalid quotation string: {}", quote),
-            }
+    pub fn new(quote: char, triple: bool) -> Self {
+        match (quote, triple) {
+            ('\'', false) => Self::Single,
+            ('\'', true) => Self::TripleSingle,
+            (_, false) => Self::Double,
+            (_, true) => Self::TripleDouble,
         }
-        panic!("Invalid quotation string: {}", quote);
     }
 }
 
@@ -112,11 +104,15 @@ impl<'a> Lexer<'a> {
         let kind = match self.next_kind() {
             Ok(kind) => kind,
             Err(e) => {
-                println!(
-                    "position {:?}",
+                tracing::debug!(
+                    "invalid token {e} at {:?}",
                     get_row_col_position(start, self.current, &self.line_starts)
                 );
-                panic!("Invalid token {e}");
+                // Make sure the lexer always makes progress
+                if self.current == start {
+                    self.next();
+                }
+                Kind::Error
             }
         };
 
@@ -124,7 +120,8 @@ impl<'a> Lexer<'a> {
             kind,
             Kind::RightParen | Kind::RightBrace | Kind::RightBracket
         ) {
-            self.nesting -= 1;
+            // Unbalanced closing brackets are reported by the parser
+            self.nesting = self.nesting.saturating_sub(1);
         } else if matches!(kind, Kind::LeftParen | Kind::LeftBrace | Kind::LeftBracket) {
             self.nesting += 1;
         }
@@ -176,14 +173,14 @@ impl<'a> Lexer<'a> {
         if let Some(mode) = self.tokenization_mode_stack.last() {
             match mode {
                 TokenizationMode::Fstring((fstring_nesting, fstrin_ending)) => {
-                    return Ok(self.next_fstring_token(*fstrin_ending, *fstring_nesting))
+                    return self.next_fstring_token(*fstrin_ending, *fstring_nesting)
                 }
                 TokenizationMode::FstringFormatSpecifier => {
                     let mut read_chars = 0;
-                    while self.peek() != Some('}')
-                        && self.peek() != Some('{')
-                        && self.peek() != Some('\n')
-                    {
+                    while let Some(c) = self.peek() {
+                        if matches!(c, '}' | '{' | '\n') {
+                            break;
+                        }
                         self.next();
                         read_chars += 1;
                     }
@@ -434,7 +431,11 @@ impl<'a> Lexer<'a> {
     }
 
     // https://peps.python.org/pep-0701/#how-to-produce-these-new-tokens
-    fn next_fstring_token(&mut self, str_finisher: StringQuotation, _fstring_nesting: u8) -> Kind {
+    fn next_fstring_token(
+        &mut self,
+        str_finisher: StringQuotation,
+        _fstring_nesting: u8,
+    ) -> Result<Kind, LexError> {
         let mut read_chars = false;
         let mut last_read_char: Option<char> = None;
         loop {
@@ -452,14 +453,14 @@ impl<'a> Lexer<'a> {
                         self.tokenization_mode_stack
                             .push(TokenizationMode::PythonWithinFstring(self.nesting + 1));
                     }
-                    return Kind::FStringMiddle;
+                    return Ok(Kind::FStringMiddle);
                 } else {
                     if !self.peek_mode {
                         self.tokenization_mode_stack
                             .push(TokenizationMode::PythonWithinFstring(self.nesting + 1));
                     }
                     self.next();
-                    return Kind::LeftBracket;
+                    return Ok(Kind::LeftBracket);
                 }
             }
             if last_read_char != Some('\\')
@@ -467,44 +468,54 @@ impl<'a> Lexer<'a> {
                 && read_chars
                 && str_finisher == StringQuotation::Single
             {
-                return Kind::FStringMiddle;
+                return Ok(Kind::FStringMiddle);
             }
             if last_read_char != Some('\\')
                 && self.peek() == Some('"')
                 && read_chars
                 && str_finisher == StringQuotation::Double
             {
-                return Kind::FStringMiddle;
+                return Ok(Kind::FStringMiddle);
             }
 
+            // Single quoted f-strings end at the end of the line
+            if peeked_char == Some('\n')
+                && last_read_char != Some('\\')
+                && matches!(
+                    str_finisher,
+                    StringQuotation::Single | StringQuotation::Double
+                )
+            {
+                return Err(self.leave_unterminated_fstring());
+            }
             let Some(curr) = self.next() else {
-                panic!("eof while parsing fstring")
+                return Err(self.leave_unterminated_fstring());
             };
             read_chars = true;
 
             match str_finisher {
                 StringQuotation::Single => {
                     if curr != '\\' && self.peek() == Some('\'') {
-                        return Kind::FStringMiddle;
+                        return Ok(Kind::FStringMiddle);
                     }
                     if last_read_char != Some('\\') && curr == '\'' {
                         if !self.peek_mode {
                             let last = self.tokenization_mode_stack.pop();
                             assert!(matches!(last, Some(TokenizationMode::Fstring(_))))
                         }
-                        return Kind::FStringEnd;
+                        return Ok(Kind::FStringEnd);
                     }
                 }
                 StringQuotation::Double => {
                     if self.peek() == Some('"') {
-                        return Kind::FStringMiddle;
+                        return Ok(Kind::FStringMiddle);
                     }
                     if curr == '"' {
                         if !self.peek_mode {
                             let last = self.tokenization_mode_stack.pop();
                             assert!(matches!(last, Some(TokenizationMode::Fstring(_))))
                         }
-                        return Kind::FStringEnd;
+                        return Ok(Kind::FStringEnd);
                     }
                 }
                 StringQuotation::TripleSingle => {
@@ -512,7 +523,7 @@ impl<'a> Lexer<'a> {
                         && self.peek() == self.double_peek()
                         && self.peek() == self.triple_peek()
                     {
-                        return Kind::FStringMiddle;
+                        return Ok(Kind::FStringMiddle);
                     }
 
                     if curr == '\''
@@ -524,7 +535,7 @@ impl<'a> Lexer<'a> {
                             assert!(matches!(last, Some(TokenizationMode::Fstring(_))))
                         }
                         self.double_next();
-                        return Kind::FStringEnd;
+                        return Ok(Kind::FStringEnd);
                     }
                 }
                 StringQuotation::TripleDouble => {
@@ -532,7 +543,7 @@ impl<'a> Lexer<'a> {
                         && self.peek() == self.double_peek()
                         && self.peek() == self.triple_peek()
                     {
-                        return Kind::FStringMiddle;
+                        return Ok(Kind::FStringMiddle);
                     }
                     if curr == '"' && self.peek() == Some(curr) && self.peek() == self.double_peek()
                     {
@@ -541,7 +552,7 @@ impl<'a> Lexer<'a> {
                             assert!(matches!(last, Some(TokenizationMode::Fstring(_))))
                         }
                         self.double_next();
-                        return Kind::FStringEnd;
+                        return Ok(Kind::FStringEnd);
                     }
                 }
             }
@@ -549,6 +560,14 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Leaves the f-string that is not terminated so that lexing continues after it.
+    fn leave_unterminated_fstring(&mut self) -> LexError {
+        if !self.peek_mode {
+            self.tokenization_mode_stack.pop();
+        }
+        LexError::FStringNotTerminated
+    }
+
     fn match_id_keyword(&mut self, id_start: char) -> Result<Kind, LexError> {
         if let Some(str_kind) = self.match_str(id_start)? {
             return Ok(str_kind);
@@ -596,13 +615,10 @@ impl<'a> Lexer<'a> {
                 Some('f') | Some('F') => match self.double_peek() {
                     Some(str_start @ '"') | Some(str_start @ '\'') => {
                         self.double_next();
-                        let fstring_start = self.f_string_quote_count(str_start);
+                        let quotation = self.f_string_quotation(str_start);
                         if !self.peek_mode {
                             self.tokenization_mode_stack
-                                .push(TokenizationMode::Fstring((
-                                    self.nesting,
-                                    StringQuotation::new(str_start, fstring_start),
-                                )));
+                                .push(TokenizationMode::Fstring((self.nesting, quotation)));
                         }
                         return Ok(Some(Kind::RawFStringStart));
                     }
@@ -635,13 +651,10 @@ impl<'a> Lexer<'a> {
                 Some('r') | Some('R') => match self.double_peek() {
                     Some(str_start @ '"') | Some(str_start @ '\'') => {
                         self.double_next();
-                        let fstring_start = self.f_string_quote_count(str_start);
+                        let quotation = self.f_string_quotation(str_start);
                         if !self.peek_mode {
                             self.tokenization_mode_stack
-                                .push(TokenizationMode::Fstring((
-                                    self.nesting,
-                                    StringQuotation::new(str_start, fstring_start),
-                                )));
+                                .push(TokenizationMode::Fstring((self.nesting, quotation)));
                         }
                         return Ok(Some(Kind::RawFStringStart));
                     }
@@ -649,13 +662,10 @@ impl<'a> Lexer<'a> {
                 },
                 Some(str_start @ '"') | Some(str_start @ '\'') => {
                     self.next();
-                    let fstring_start = self.f_string_quote_count(str_start);
+                    let quotation = self.f_string_quotation(str_start);
                     if !self.peek_mode {
                         self.tokenization_mode_stack
-                            .push(TokenizationMode::Fstring((
-                                self.nesting,
-                                StringQuotation::new(str_start, fstring_start),
-                            )));
+                            .push(TokenizationMode::Fstring((self.nesting, quotation)));
                     }
                     return Ok(Some(Kind::FStringStart));
                 }
@@ -769,7 +779,12 @@ impl<'a> Lexer<'a> {
                 }
             }
         } else {
-            while let Some(c) = self.next() {
+            while let Some(c) = self.peek() {
+                // Single quoted strings cannot span multiple lines
+                if c == '\n' && last_read_char != '\\' {
+                    break;
+                }
+                self.next();
                 // Two consecutive backslashes cancel out
                 if c == str_start && last_read_char != '\\' {
                     string_terminated = true;
@@ -1044,13 +1059,12 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    fn f_string_quote_count(&mut self, str_start: char) -> u8 {
-        let mut count = 1;
-        if self.peek() == Some(str_start) && self.double_peek() == Some(str_start) {
-            count = 3;
+    fn f_string_quotation(&mut self, str_start: char) -> StringQuotation {
+        let triple = self.peek() == Some(str_start) && self.double_peek() == Some(str_start);
+        if triple {
             self.double_next();
         }
-        count
+        StringQuotation::new(str_start, triple)
     }
 }
 
@@ -1416,13 +1430,28 @@ def",
     }
 
     #[test]
-    #[should_panic]
     fn test_unterminated_string_double_quotes() {
-        snapshot_test_lexer(
-            "unterminated-string",
-            &["\"hello", "'hello", "'''hello''", "'''hello'"],
-        )
-        .unwrap();
+        for source in [
+            "\"hello",
+            "'hello",
+            "'''hello''",
+            "'''hello'",
+            "'hello\nworld'",
+        ] {
+            let mut lexer = Lexer::new(source);
+            let tokens = lexer.lex();
+            assert_eq!(tokens[0].kind, Kind::Error);
+        }
+    }
+
+    #[test]
+    fn test_unterminated_fstring() {
+        for source in ["f\"hello", "f'{a}hello", "f'''hello''", "rf'hello"] {
+            let mut lexer = Lexer::new(source);
+            let tokens = lexer.lex();
+            assert!(tokens.iter().any(|token| token.kind == Kind::Error));
+            assert_eq!(tokens.last().map(|token| token.kind), Some(Kind::Eof));
+        }
     }
 
     #[test]
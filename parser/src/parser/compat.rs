@@ -88,6 +88,7 @@ impl AsPythonCompat for Statement {
             Statement::AsyncWithStatement(w) => w.as_python_compat(parser),
             Statement::AsyncFunctionDef(f) => f.as_python_compat(parser),
             Statement::TypeAlias(t) => t.as_python_compat(parser),
+            Statement::Invalid(i) => i.as_python_compat(parser),
         }
     }
 }
@@ -159,6 +160,12 @@ impl AsPythonCompat for Pass {
     }
 }
 
+impl AsPythonCompat for Invalid {
+    fn as_python_compat(&self, parser: &Parser) -> Value {
+        json_python_compat_node!("Invalid", self, parser, {})
+    }
+}
+
 impl AsPythonCompat for Delete {
     fn as_python_compat(&self, parser: &Parser) -> Value {
         json_python_compat_node!("Delete", self, parser, {
@@ -284,11 +291,37 @@ impl AsPythonCompat for Name {
 impl AsPythonCompat for Constant {
     fn as_python_compat(&self, parser: &Parser) -> Value {
         json_python_compat_node!("Constant", self, parser, {
-            "value": json!(self.get_value(parser.source)),
+            "value": self.value.as_python_compat(parser),
         })
     }
 }
 
+impl AsPythonCompat for ConstantValue {
+    fn as_python_compat(&self, _parser: &Parser) -> Value {
+        match self {
+            ConstantValue::None => json!(null),
+            ConstantValue::Ellipsis => json!("..."),
+            ConstantValue::Bool(v) => json!(v),
+            ConstantValue::Str(v) => json!(v),
+            ConstantValue::Bytes(v) => match std::str::from_utf8(v) {
+                Ok(v) => json!(v),
+                Err(_) => json!(v.iter().map(|b| format!("{b:02x}")).collect::<String>()),
+            },
+            ConstantValue::Tuple => json!([]),
+            // Parse the number from string so big integers don't overflow
+            ConstantValue::Int(v) => serde_json::from_str(&v.to_string()).unwrap_or(Value::Null),
+            ConstantValue::Float(v) => json!(v),
+            ConstantValue::Complex { real, imaginary } => {
+                if *real == 0.0 {
+                    json!(format!("{imaginary}j"))
+                } else {
+                    json!(format!("({real}+{imaginary}j)"))
+                }
+            }
+        }
+    }
+}
+
 impl AsPythonCompat for List {
     fn as_python_compat(&self, parser: &Parser) -> Value {
         json_python_compat_node!("List", self, parser, {
@@ -0,0 +1,200 @@
+// Decoding of literal tokens into their values.
+// https://docs.python.org/3/reference/lexical_analysis.html#literals
+use num_bigint::BigInt;
+use num_traits::Num;
+
+pub fn parse_int(text: &str) -> Option<BigInt> {
+    let text = text.replace('_', "");
+    let (digits, radix) = match text.get(..2) {
+        Some("0x" | "0X") => (&text[2..], 16),
+        Some("0o" | "0O") => (&text[2..], 8),
+        Some("0b" | "0B") => (&text[2..], 2),
+        _ => (text.as_str(), 10),
+    };
+    BigInt::from_str_radix(digits, radix).ok()
+}
+
+pub fn parse_float(text: &str) -> Option<f64> {
+    text.replace('_', "").parse().ok()
+}
+
+// Imaginary literals are in the form of `<float or int>j`
+pub fn parse_imaginary(text: &str) -> Option<f64> {
+    let text = text.strip_suffix(['j', 'J'])?;
+    parse_float(text)
+}
+
+/// Splits a string literal token into its prefix and the text between the quotes.
+fn split_string_token(text: &str) -> (&str, &str) {
+    let quote_start = text.find(['\'', '"']).unwrap_or(text.len());
+    let (prefix, quoted) = text.split_at(quote_start);
+    let quote_len = if quoted.starts_with("\"\"\"") || quoted.starts_with("'''") {
+        3
+    } else {
+        1
+    };
+    let content = quoted
+        .get(quote_len..quoted.len().saturating_sub(quote_len))
+        .unwrap_or_default();
+    (prefix, content)
+}
+
+fn is_raw(prefix: &str) -> bool {
+    prefix.contains(['r', 'R'])
+}
+
+/// Returns the value of a string literal token, e.g. `'a\n'` or `r"a\n"`.
+pub fn decode_string(text: &str) -> String {
+    let (prefix, content) = split_string_token(text);
+    if is_raw(prefix) {
+        return content.to_string();
+    }
+    unescape(content, false)
+        .into_iter()
+        .map(|c| match c {
+            Unescaped::Char(c) => c,
+            Unescaped::Byte(b) => b as char,
+        })
+        .collect()
+}
+
+/// Returns the value of a bytes literal token, e.g. `b'a\x00'` or `rb"a\x00"`.
+pub fn decode_bytes(text: &str) -> Vec<u8> {
+    let (prefix, content) = split_string_token(text);
+    if is_raw(prefix) {
+        return content.as_bytes().to_vec();
+    }
+    let mut bytes = vec![];
+    for c in unescape(content, true) {
+        match c {
+            Unescaped::Char(c) => {
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+            Unescaped::Byte(b) => bytes.push(b),
+        }
+    }
+    bytes
+}
+
+/// Returns the value of the text between replacement fields in an f-string.
+pub fn decode_fstring_middle(text: &str, raw: bool) -> String {
+    let text = text.replace("{{", "{").replace("}}", "}");
+    if raw {
+        return text;
+    }
+    unescape(&text, false)
+        .into_iter()
+        .map(|c| match c {
+            Unescaped::Char(c) => c,
+            Unescaped::Byte(b) => b as char,
+        })
+        .collect()
+}
+
+enum Unescaped {
+    Char(char),
+    // \x and octal escapes are bytes in bytes literals
+    Byte(u8),
+}
+
+// https://docs.python.org/3/reference/lexical_analysis.html#escape-sequences
+// Unrecognized or malformed escape sequences are left in the string unchanged.
+fn unescape(content: &str, is_bytes: bool) -> Vec<Unescaped> {
+    let mut result = Vec::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(Unescaped::Char(c));
+            continue;
+        }
+        let Some(escaped) = chars.next() else {
+            result.push(Unescaped::Char('\\'));
+            break;
+        };
+        let simple = match escaped {
+            // Line continuation
+            '\n' => continue,
+            '\r' => {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                continue;
+            }
+            '\\' => Some('\\'),
+            '\'' => Some('\''),
+            '"' => Some('"'),
+            'a' => Some('\x07'),
+            'b' => Some('\x08'),
+            'f' => Some('\x0c'),
+            'n' => Some('\n'),
+            'r' => Some('\r'),
+            't' => Some('\t'),
+            'v' => Some('\x0b'),
+            _ => None,
+        };
+        if let Some(simple) = simple {
+            result.push(Unescaped::Char(simple));
+            continue;
+        }
+
+        match escaped {
+            '0'..='7' => {
+                let mut value = escaped.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                if is_bytes {
+                    result.push(Unescaped::Byte(value as u8));
+                } else if let Some(c) = char::from_u32(value) {
+                    result.push(Unescaped::Char(c));
+                }
+            }
+            'x' | 'u' | 'U' if escaped == 'x' || !is_bytes => {
+                let len = match escaped {
+                    'x' => 2,
+                    'u' => 4,
+                    _ => 8,
+                };
+                let hex: String = chars.clone().take(len).collect();
+                let value = if hex.len() == len {
+                    u32::from_str_radix(&hex, 16).ok()
+                } else {
+                    None
+                };
+                match value {
+                    Some(value) if is_bytes => {
+                        result.push(Unescaped::Byte(value as u8));
+                        chars.nth(len - 1);
+                    }
+                    Some(value) => match char::from_u32(value) {
+                        Some(c) => {
+                            result.push(Unescaped::Char(c));
+                            chars.nth(len - 1);
+                        }
+                        None => {
+                            result.push(Unescaped::Char('\\'));
+                            result.push(Unescaped::Char(escaped));
+                        }
+                    },
+                    None => {
+                        result.push(Unescaped::Char('\\'));
+                        result.push(Unescaped::Char(escaped));
+                    }
+                }
+            }
+            // \N{name} requires the unicode name database so it's left as is
+            _ => {
+                result.push(Unescaped::Char('\\'));
+                result.push(Unescaped::Char(escaped));
+            }
+        }
+    }
+    result
+}
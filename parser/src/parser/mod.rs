@@ -1,5 +1,6 @@
 pub mod compat;
-use crate::ast::{self, QuoteType};
+use crate::ast;
+mod literal;
 pub mod parser;
 use crate::token::{Kind, Token};
 use ast::{Node, UnaryOperator};
@@ -27,35 +28,14 @@ pub fn is_at_compound_statement(token: &Token) -> bool {
     }
 }
 
-// TODO: performance
-pub fn extract_string_inside(val: String) -> String {
-    let delimiters = vec!["\"\"\"", "\"", "'''", "'"];
-    let mut result = String::new();
-    let is_raw = val.starts_with('r') || val.starts_with('R');
-    // drop first char if raw and put in val
-    let val = if is_raw {
-        val.chars().skip(1).collect::<String>()
-    } else {
-        val
-    };
-
-    for delimiter in delimiters {
-        if let Some(val) = val.strip_prefix(delimiter) {
-            let message = format!("String must be enclosed with {}", delimiter);
-            result = val.strip_suffix(delimiter).expect(&message).to_string();
-            break;
-        }
-    }
-    // add r back if raw ternary
-    if is_raw {
-        result = format!("r\"{}\"", result);
-    }
-
-    result
-}
-
 pub fn concat_string_exprs(lhs: Expression, rhs: Expression) -> Result<Expression, ParsingError> {
-    use crate::parser::ast::{Constant, ConstantValue};
+    use crate::parser::ast::{Constant, ConstantValue, GetNode};
+    let concat_node = Node::new(lhs.get_node().start, rhs.get_node().end);
+    let concat_error = |msg: &str| ParsingError::InvalidSyntax {
+        msg: msg.to_string(),
+        advice: String::new(),
+        span: (concat_node.start as usize, concat_node.len() as usize),
+    };
     match (lhs, rhs) {
         (Expression::Constant(lhs), Expression::Constant(rhs)) => {
             let node = Node {
@@ -63,25 +43,26 @@ pub fn concat_string_exprs(lhs: Expression, rhs: Expression) -> Result<Expressio
                 end: rhs.node.end,
             };
             let concatnated_string = match (lhs.value, rhs.value) {
-                (ConstantValue::Str(_), ConstantValue::Str(_)) => {
+                (ConstantValue::Str(lhs), ConstantValue::Str(rhs)) => {
                     Expression::Constant(Box::new(Constant {
                         node,
-                        value: ConstantValue::Str(QuoteType::Concat),
+                        value: ConstantValue::Str(lhs + &rhs),
                     }))
                 }
-                (ConstantValue::Bytes, ConstantValue::Bytes) => {
+                (ConstantValue::Bytes(mut lhs), ConstantValue::Bytes(rhs)) => {
+                    lhs.extend(rhs);
                     Expression::Constant(Box::new(Constant {
                         node,
-                        value: ConstantValue::Bytes,
+                        value: ConstantValue::Bytes(lhs),
                     }))
                 }
-                (ConstantValue::Bytes, _) => {
-                    panic!("Cannot concat bytes and string");
+                (ConstantValue::Bytes(_), _) => {
+                    return Err(concat_error("Cannot concat bytes and string"));
                 }
-                (_, ConstantValue::Bytes) => {
-                    panic!("Can only concat bytes with other bytes");
+                (_, ConstantValue::Bytes(_)) => {
+                    return Err(concat_error("Can only concat bytes with other bytes"));
                 }
-                _ => panic!("Cannot concat string"),
+                _ => return Err(concat_error("Cannot concat string")),
             };
             Ok(concatnated_string)
         }
@@ -108,10 +89,10 @@ pub fn concat_string_exprs(lhs: Expression, rhs: Expression) -> Result<Expressio
                         value: ConstantValue::Str(s),
                     })));
                 }
-                ConstantValue::Bytes => {
-                    panic!("Cannot concat string and bytes");
+                ConstantValue::Bytes(_) => {
+                    return Err(concat_error("Cannot concat string and bytes"));
                 }
-                _ => panic!("Cannot concat string"),
+                _ => return Err(concat_error("Cannot concat string")),
             }
             Ok(Expression::JoinedStr(Box::new(JoinedStr {
                 node: Node {
@@ -123,17 +104,17 @@ pub fn concat_string_exprs(lhs: Expression, rhs: Expression) -> Result<Expressio
         }
         (Expression::Constant(const_lhs), Expression::JoinedStr(fstring_rhs)) => {
             let const_expr = match const_lhs.value {
-                ConstantValue::Str(_) => Expression::Constant(Box::new(Constant {
+                ConstantValue::Str(s) => Expression::Constant(Box::new(Constant {
                     node: Node {
                         start: const_lhs.node.start,
                         end: fstring_rhs.node.end,
                     },
-                    value: ConstantValue::Str(QuoteType::Concat),
+                    value: ConstantValue::Str(s),
                 })),
-                ConstantValue::Bytes => {
-                    panic!("Cannot concat string and bytes");
+                ConstantValue::Bytes(_) => {
+                    return Err(concat_error("Cannot concat string and bytes"));
                 }
-                _ => panic!("Cannot concat string"),
+                _ => return Err(concat_error("Cannot concat string")),
             };
             let mut values = vec![const_expr];
             values.extend(fstring_rhs.values);
@@ -145,7 +126,7 @@ pub fn concat_string_exprs(lhs: Expression, rhs: Expression) -> Result<Expressio
                 values,
             })))
         }
-        _ => panic!("Cannot concat string"),
+        _ => Err(concat_error("Cannot concat string")),
     }
 }
 
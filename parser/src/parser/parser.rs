@@ -1,5 +1,3 @@
-use core::panic;
-
 /// Some functions in this file have misleading names.
 /// For example star expressions are defined slightly differently in python grammar and references.
 /// So there might be duplicates of both. Try to migrate the wrong names to how they are called in:
@@ -17,7 +15,7 @@ use crate::{
     get_row_col_position,
     intern::{Interner, StrId},
     lexer::Lexer,
-    parser::{ast::*, extract_string_inside},
+    parser::{ast::*, literal},
     token::{Kind, Token},
 };
 static INTERNER: OnceLock<RwLock<Interner>> = OnceLock::new();
@@ -48,6 +46,8 @@ pub struct Parser<'a> {
     // This is incremented when we see an opening bracket and decremented when we
     // see a closing bracket.
     nested_expression_list: u32,
+    // Syntax errors that the parser recovered from.
+    errors: Vec<ParsingError>,
 }
 
 #[allow(unused)]
@@ -56,10 +56,12 @@ impl<'a> Parser<'a> {
         let mut lexer = Lexer::new(source);
         let cur_token = lexer.next_token();
 
-        let mut nested_expression_list = 0;
+        let mut nested_expression_list: u32 = 0;
         match cur_token.kind {
             Kind::LeftParen | Kind::LeftBrace | Kind::LeftBracket => nested_expression_list += 1,
-            Kind::RightParen | Kind::RightBrace | Kind::RightBracket => nested_expression_list -= 1,
+            Kind::RightParen | Kind::RightBrace | Kind::RightBracket => {
+                nested_expression_list = nested_expression_list.saturating_sub(1)
+            }
             _ => {}
         }
         let identifiers_offset =
@@ -78,16 +80,35 @@ impl<'a> Parser<'a> {
             prev_nonwhitespace_token_end: prev_token_end,
             nested_expression_list,
             identifiers_start_offset: identifiers_offset,
+            errors: vec![],
         }
     }
 
+    /// Parses the source and fails on the first syntax error.
     pub fn parse(&mut self) -> Result<Module, ParsingError> {
+        let (module, mut errors) = self.parse_with_errors();
+        if errors.is_empty() {
+            Ok(module)
+        } else {
+            Err(errors.swap_remove(0))
+        }
+    }
+
+    /// Parses the source and recovers from syntax errors.
+    /// Statements that cannot be parsed are replaced with `Statement::Invalid`
+    /// and the errors are returned next to the partial module.
+    pub fn parse_with_errors(&mut self) -> (Module, Vec<ParsingError>) {
         let node = self.start_node();
         let mut body = vec![];
         while self.cur_kind() != Kind::Eof {
             if self.consume_whitespace_and_comments() {
                 continue;
             }
+            // Only reachable after recovering from an unexpected indent
+            if self.eat(Kind::Dedent) {
+                continue;
+            }
+            let stmt_node = self.start_node();
             let stmt = if is_at_compound_statement(self.cur_token()) {
                 self.parse_compound_statement()
             } else {
@@ -95,7 +116,7 @@ impl<'a> Parser<'a> {
             };
             match stmt {
                 Ok(stmt) => body.push(stmt),
-                Err(err) => return Err(err),
+                Err(err) => body.push(self.recover_statement(stmt_node, err)),
             }
         }
 
@@ -103,7 +124,63 @@ impl<'a> Parser<'a> {
         // Remove the EOF offset
         node.end.saturating_sub(1);
 
-        Ok(Module::new(node, body))
+        (Module::new(node, body), std::mem::take(&mut self.errors))
+    }
+
+    /// Records the error and skips the rest of the logical line so parsing
+    /// can continue from the next statement.
+    fn recover_statement(&mut self, node: Node, err: ParsingError) -> Statement {
+        self.errors.push(err);
+        loop {
+            match self.cur_kind() {
+                Kind::Eof | Kind::Dedent => break,
+                Kind::NewLine => {
+                    self.bump_any();
+                    if !self.at(Kind::Indent) {
+                        break;
+                    }
+                }
+                // Skip the whole block when it is indented unexpectedly or
+                // belongs to a compound statement with an invalid header
+                Kind::Indent => {
+                    let mut depth = 0;
+                    loop {
+                        match self.cur_kind() {
+                            Kind::Indent => depth += 1,
+                            Kind::Dedent => depth -= 1,
+                            Kind::Eof => break,
+                            _ => {}
+                        }
+                        self.bump_any();
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    break;
+                }
+                _ => self.bump_any(),
+            }
+        }
+        Statement::Invalid(Box::new(Invalid {
+            node: self.finish_node_chomped(node),
+        }))
+    }
+
+    fn syntax_error(&self, msg: impl Into<String>, node: Node) -> ParsingError {
+        ParsingError::InvalidSyntax {
+            msg: msg.into(),
+            advice: String::new(),
+            span: (node.start as usize, node.len() as usize),
+        }
+    }
+
+    fn unexpected_token(&self) -> ParsingError {
+        let token = self.cur_token();
+        let msg = match token.kind {
+            Kind::Error => format!("Invalid token \"{}\"", token.as_str(self.source)),
+            kind => format!("Unexpected token \"{kind}\""),
+        };
+        self.syntax_error(msg, Node::new(token.start, token.end))
     }
 
     fn start_node(&self) -> Node {
@@ -130,7 +207,10 @@ impl<'a> Parser<'a> {
     fn peek_token(&mut self) -> Result<Token, ParsingError> {
         let token = self.lexer.peek_token();
         if matches!(token.kind, Kind::Error) {
-            panic!("Next token is Error {token:?}");
+            return Err(self.syntax_error(
+                format!("Invalid token \"{}\"", token.as_str(self.source)),
+                Node::new(token.start, token.end),
+            ));
         }
         Ok(token)
     }
@@ -186,7 +266,7 @@ impl<'a> Parser<'a> {
                 self.nested_expression_list += 1
             }
             Kind::RightParen | Kind::RightBrace | Kind::RightBracket => {
-                self.nested_expression_list -= 1
+                self.nested_expression_list = self.nested_expression_list.saturating_sub(1)
             }
             _ => {}
         }
@@ -213,8 +293,10 @@ impl<'a> Parser<'a> {
     pub fn expect(&mut self, kind: Kind) -> Result<(), ParsingError> {
         if !self.at(kind) {
             let found = &self.cur_token;
-            let line = &self.get_offset_line_number(found.start);
-            panic!("Expected {:?} but found {:?} line: {line:}", kind, found);
+            return Err(self.syntax_error(
+                format!("Expected \"{}\" but found \"{}\"", kind, found.kind),
+                Node::new(found.start, found.end),
+            ));
         }
         self.bump_any();
         Ok(())
@@ -223,12 +305,16 @@ impl<'a> Parser<'a> {
     /// Expect any of `Kinds` or return error
     pub fn expect_any(&mut self, kind: Vec<Kind>) -> Result<(), ParsingError> {
         if !kind.contains(&self.cur_token.kind) {
-            let mut expected = String::new();
-            for kind in kind {
-                expected.push_str(&format!("{:?}, ", kind));
-            }
-            let found = self.cur_token.kind;
-            panic!("Expected one of {:?} but found {:?}", expected, found);
+            let expected = kind
+                .iter()
+                .map(|k| format!("\"{k}\""))
+                .collect::<Vec<String>>()
+                .join(", ");
+            let found = &self.cur_token;
+            return Err(self.syntax_error(
+                format!("Expected one of {} but found \"{}\"", expected, found.kind),
+                Node::new(found.start, found.end),
+            ));
         }
         self.bump_any();
         Ok(())
@@ -260,14 +346,17 @@ impl<'a> Parser<'a> {
             Kind::Nonlocal => self.parse_nonlocal_statement(),
             other => {
                 if self.cur_kind() == Kind::Indent {
-                    panic!("Unexpected token {:?}", self.cur_token());
+                    return Err(self.syntax_error(
+                        "Unexpected indentation",
+                        Node::new(self.cur_token().start, self.cur_token().end),
+                    ));
                 } else {
                     self.parse_assignment_or_expression_statement()
                 }
             }
         }?;
 
-        self.err_if_statement_not_ending_in_new_line_or_semicolon(stmt.get_node(), &stmt);
+        self.err_if_statement_not_ending_in_new_line_or_semicolon(stmt.get_node(), &stmt)?;
 
         Ok(stmt)
     }
@@ -297,7 +386,7 @@ impl<'a> Parser<'a> {
                 } else if matches!(self.peek_kind(), Ok(Kind::With)) {
                     self.parse_with_statement()
                 } else {
-                    panic!("");
+                    Err(self.unexpected_token())
                 }
             }
             other => {
@@ -319,15 +408,19 @@ impl<'a> Parser<'a> {
         &mut self,
         node: Node,
         stmt: &Statement,
-    ) {
+    ) -> Result<(), ParsingError> {
         while self.eat(Kind::WhiteSpace) || self.eat(Kind::Comment) {}
 
         if !matches!(
             self.cur_kind(),
             Kind::NewLine | Kind::NL | Kind::SemiColon | Kind::Eof
         ) {
-            panic!("Statement does not end in new line or semicolon {:?}", stmt);
+            return Err(self.syntax_error(
+                "Statement does not end in new line or semicolon",
+                Node::new(self.cur_token().start, self.cur_token().end),
+            ));
         }
+        Ok(())
     }
 
     fn parse_if_statement(&mut self) -> Result<Statement, ParsingError> {
@@ -414,7 +507,7 @@ impl<'a> Parser<'a> {
         let iter_list = self.parse_starred_list(Kind::Colon)?;
         let iter = match iter_list.len() {
             0 => {
-                panic!("Unexpected token {:?}", self.cur_token());
+                return Err(self.unexpected_token());
             }
             1 => iter_list.into_iter().next().unwrap(),
             _ => Expression::Tuple(Box::new(Tuple {
@@ -879,10 +972,7 @@ impl<'a> Parser<'a> {
                 self.parse_literal_pattern()
 
                 },
-            _ => {
-                let node = self.start_node();
-                panic!("Unexpected token {:?}", self.cur_kind());
-            },
+            _ => Err(self.unexpected_token()),
         }
     }
 
@@ -1037,7 +1127,10 @@ impl<'a> Parser<'a> {
                 kwd_patterns.push(self.parse_pattern()?);
             } else {
                 if seen_keyword_pattern {
-                    panic!("Positional arguments cannot come after keyword arguments.");
+                    return Err(self.syntax_error(
+                        "Positional patterns cannot come after keyword patterns",
+                        self.finish_node(node),
+                    ));
                 }
                 patterns.push(self.parse_pattern()?);
             }
@@ -1065,7 +1158,7 @@ impl<'a> Parser<'a> {
             self.expect(Kind::RightParen)?;
             Ok(MatchPattern::MatchSequence(pattern))
         } else {
-            panic!("Unexpected token {:?}", self.cur_token());
+            Err(self.unexpected_token())
         }
     }
 
@@ -1127,8 +1220,11 @@ impl<'a> Parser<'a> {
                 if self.eat(Kind::Comment) || self.consume_whitespace_and_newline() {
                     continue;
                 }
-                let stmt = self.parse_statement()?;
-                stmts.extend(stmt);
+                let stmt_node = self.start_node();
+                match self.parse_statement() {
+                    Ok(stmt) => stmts.extend(stmt),
+                    Err(err) => stmts.push(self.recover_statement(stmt_node, err)),
+                }
             }
             Ok(stmts)
         } else {
@@ -1446,7 +1542,7 @@ impl<'a> Parser<'a> {
             self.bump_any();
             aliases.push(self.parse_alias("*".to_string(), node));
         } else {
-            panic!("Unexpected token {:?}", self.cur_token());
+            return Err(self.unexpected_token());
         }
         Ok(Statement::ImportFrom(Box::new(ImportFrom {
             node: self.finish_node(import_node),
@@ -1481,9 +1577,7 @@ impl<'a> Parser<'a> {
                 Kind::Ellipsis => {
                     level += 3;
                 }
-                _ => {
-                    panic!("lol");
-                }
+                _ => unreachable!(),
             }
             self.bump_any();
         }
@@ -1503,7 +1597,7 @@ impl<'a> Parser<'a> {
     // https://docs.python.org/3/library/ast.html#ast.Expr
     fn parse_expressions(&mut self) -> Result<Expression, ParsingError> {
         let node = self.start_node();
-        let expr = self.parse_expression()?;
+        let expr = self.parse_star_expression()?;
 
         let mut exprs = vec![];
         if self.at(Kind::Comma) {
@@ -1514,7 +1608,7 @@ impl<'a> Parser<'a> {
                 if self.at(Kind::Eof) || !self.cur_kind().is_star_expression() {
                     break;
                 }
-                exprs.push(self.parse_expression()?);
+                exprs.push(self.parse_star_expression()?);
             }
         } else {
             return Ok(expr);
@@ -1589,7 +1683,6 @@ impl<'a> Parser<'a> {
         let started_with_star = self.at(Kind::Mul);
         let first_elm = self.parse_star_named_expression()?;
         if !started_with_star
-            && self.at(Kind::For)
             && (self.at(Kind::For)
                 || self.at(Kind::Async) && matches!(self.peek_kind(), Ok(Kind::For)))
         {
@@ -1661,12 +1754,12 @@ impl<'a> Parser<'a> {
 
     // https://docs.python.org/3/reference/expressions.html#displays-for-lists-sets-and-dictionaries
     fn parse_comp_for(&mut self) -> Result<Vec<Comprehension>, ParsingError> {
-        // if current token is async
         let node = self.start_node();
-        let is_async = self.eat(Kind::Async);
 
         let mut generators = vec![];
         loop {
+            // every clause can be an `async for`
+            let is_async = self.eat(Kind::Async);
             self.expect(Kind::For)?;
             let target = self.parse_target_list()?;
             self.expect(Kind::In)?;
@@ -1690,7 +1783,9 @@ impl<'a> Parser<'a> {
                 ifs,
                 is_async,
             });
-            if !matches!(self.cur_kind(), Kind::For) {
+            if !(self.at(Kind::For)
+                || self.at(Kind::Async) && matches!(self.peek_kind(), Ok(Kind::For)))
+            {
                 break;
             }
         }
@@ -1778,11 +1873,15 @@ impl<'a> Parser<'a> {
                     }))
                 }
             }
-            Kind::Mul => Expression::Starred(Box::new(Starred {
-                node: self.finish_node(node),
-                value: self.parse_target()?,
-            })),
-            _ => panic!("invalid target"),
+            Kind::Mul => {
+                self.bump(Kind::Mul);
+                let value = self.parse_target()?;
+                Expression::Starred(Box::new(Starred {
+                    node: self.finish_node(node),
+                    value,
+                }))
+            }
+            _ => return Err(self.unexpected_token()),
         };
         targets.push(target);
         while self.eat(Kind::Comma) {
@@ -1843,7 +1942,6 @@ impl<'a> Parser<'a> {
     // https://docs.python.org/3/reference/expressions.html#set-displays
     fn parse_set(&mut self, node: Node, first_elm: Expression) -> Result<Expression, ParsingError> {
         if !matches!(first_elm, Expression::Starred(_))
-            && self.at(Kind::For)
             && (self.at(Kind::For)
                 || self.at(Kind::Async) && matches!(self.peek_kind(), Ok(Kind::For)))
         {
@@ -1876,7 +1974,10 @@ impl<'a> Parser<'a> {
     ) -> Result<Expression, ParsingError> {
         if self.at(Kind::For) || self.at(Kind::Async) && matches!(self.peek_kind(), Ok(Kind::For)) {
             let Some(key) = first_key else {
-                panic!("cannot use ** in dict comprehension");
+                return Err(self.syntax_error(
+                    "Dict unpacking cannot be used in dict comprehension",
+                    self.finish_node(node),
+                ));
             };
 
             // make sure the first key is some
@@ -1987,8 +2088,7 @@ impl<'a> Parser<'a> {
             let expr = self.parse_or_expr()?;
             node = self.finish_node(node);
             if !is_iterable(&expr) {
-                // self.unepxted_token(node, starred_value_kind);
-                panic!();
+                return Err(self.syntax_error("Expected an iterable after *", node));
             }
             return Ok(Expression::Starred(Box::new(Starred {
                 node: self.finish_node(node),
@@ -2236,8 +2336,10 @@ impl<'a> Parser<'a> {
         } else if self.cur_kind().is_atom() {
             self.parse_atom()?
         } else {
-            panic!("not a primary {:?}", self.cur_token());
+            return Err(self.unexpected_token());
         };
+        // A generator expression that is the only argument spans the parentheses of the call
+        let arguments_node = self.start_node();
 
         let mut primary = if self.at(Kind::Dot) {
             self.parse_attribute_ref(node, atom_or_primary)
@@ -2281,8 +2383,10 @@ impl<'a> Parser<'a> {
                     keyword_args.push(kwarg);
                 } else {
                     if seen_keyword {
-                        let node_end = self.finish_node(node);
-                        panic!("Positional arguments cannot come after keyword arguments.");
+                        return Err(self.syntax_error(
+                            "Positional argument cannot appear after keyword arguments",
+                            Node::new(self.cur_token().start, self.cur_token().end),
+                        ));
                     }
                     let arg = self.parse_named_expression()?;
                     positional_args.push(arg);
@@ -2295,18 +2399,21 @@ impl<'a> Parser<'a> {
             self.bump(Kind::Comma);
             if self.at(Kind::Async) || self.at(Kind::For) {
                 let comprehension = self.parse_comp_for()?;
+                self.expect(Kind::RightParen)?;
                 let arg = Expression::Generator(Box::new(Generator {
-                    node: self.finish_node(node),
+                    node: self.finish_node(arguments_node),
                     element: positional_args
                         .into_iter()
                         .next()
                         .expect("generator passed to function does not have an element"),
                     generators: comprehension,
                 }));
-                self.expect(Kind::RightParen)?;
 
                 Ok(Expression::Call(Box::new(Call {
-                    node,
+                    node: Node {
+                        start: atom_or_primary.get_node().start,
+                        end: self.finish_node(call_node).end,
+                    },
                     func: atom_or_primary,
                     args: vec![arg],
                     keywords: vec![],
@@ -2377,8 +2484,10 @@ impl<'a> Parser<'a> {
                 keyword_args.push(kwarg);
             } else {
                 if seen_keyword {
-                    let node_end = self.finish_node(node);
-                    panic!("Positional arguments cannot come after keyword arguments.");
+                    return Err(self.syntax_error(
+                        "Positional argument cannot appear after keyword arguments",
+                        Node::new(self.cur_token().start, self.cur_token().end),
+                    ));
                 }
                 let arg = self.parse_named_expression()?;
                 positional_args.push(arg);
@@ -2426,6 +2535,65 @@ impl<'a> Parser<'a> {
         expr
     }
 
+    // https://docs.python.org/3/reference/lexical_analysis.html#literals
+    fn parse_literal(&mut self) -> Result<Expression, ParsingError> {
+        let start = self.start_node();
+        let text = self.cur_token().as_str(self.source);
+        let value = match self.cur_kind() {
+            Kind::None => ConstantValue::None,
+            Kind::True => ConstantValue::Bool(true),
+            Kind::False => ConstantValue::Bool(false),
+            Kind::Ellipsis => ConstantValue::Ellipsis,
+            Kind::Integer | Kind::Binary | Kind::Octal | Kind::Hexadecimal => {
+                match literal::parse_int(text) {
+                    Some(value) => ConstantValue::Int(value),
+                    None => return Err(self.invalid_literal("integer")),
+                }
+            }
+            Kind::PointFloat | Kind::ExponentFloat => match literal::parse_float(text) {
+                Some(value) => ConstantValue::Float(value),
+                None => return Err(self.invalid_literal("float")),
+            },
+            Kind::ImaginaryInteger | Kind::ImaginaryPointFloat | Kind::ImaginaryExponentFloat => {
+                match literal::parse_imaginary(text) {
+                    Some(imaginary) => ConstantValue::Complex {
+                        real: 0.0,
+                        imaginary,
+                    },
+                    None => return Err(self.invalid_literal("imaginary")),
+                }
+            }
+            Kind::StringLiteral => ConstantValue::Str(literal::decode_string(text)),
+            Kind::Bytes | Kind::RawBytes => ConstantValue::Bytes(literal::decode_bytes(text)),
+            Kind::FStringStart | Kind::RawFStringStart => {
+                let raw = self.at(Kind::RawFStringStart);
+                self.bump_any();
+                let fstring = self.parse_fstring(raw)?;
+                return Ok(Expression::JoinedStr(Box::new(JoinedStr {
+                    node: self.finish_node(start),
+                    values: fstring,
+                })));
+            }
+            _ => return Err(self.unexpected_token()),
+        };
+        self.bump_any();
+        Ok(Expression::Constant(Box::new(Constant {
+            node: self.finish_node(start),
+            value,
+        })))
+    }
+
+    fn invalid_literal(&self, literal_kind: &str) -> ParsingError {
+        let token = self.cur_token();
+        self.syntax_error(
+            format!(
+                "Invalid {literal_kind} literal \"{}\"",
+                token.as_str(self.source)
+            ),
+            Node::new(token.start, token.end),
+        )
+    }
+
     // https://docs.python.org/3/reference/expressions.html#atoms
     fn parse_atom(&mut self) -> Result<Expression, ParsingError> {
         let node = self.start_node();
@@ -2457,147 +2625,7 @@ impl<'a> Parser<'a> {
                         parenthesized: false,
                     }))
                 }
-                Kind::Integer => {
-                    let val = self.cur_token.to_string(self.source);
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Int,
-                    }))
-                }
-                Kind::None => {
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::None,
-                    }))
-                }
-                Kind::True => {
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Bool(true),
-                    }))
-                }
-                Kind::False => {
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Bool(false),
-                    }))
-                }
-                Kind::ImaginaryInteger => {
-                    let val = self.cur_token.to_string(self.source);
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Complex,
-                    }))
-                }
-                Kind::Bytes => {
-                    let val = self.cur_token.to_string(self.source);
-                    let bytes_val = extract_string_inside(
-                        val.strip_prefix('b')
-                            .expect("bytes literal must start with b")
-                            .to_string(),
-                    )
-                    .into_bytes();
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Bytes,
-                    }))
-                }
-                Kind::StringLiteral => {
-                    let val = self.cur_token.as_str(self.source);
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Str(QuoteType::from(val)),
-                    }))
-                }
-
-                Kind::RawBytes => {
-                    let val = self.cur_token.to_string(self.source);
-                    // rb or br appear in the beginning of raw bytes
-                    let bytes_val =
-                        extract_string_inside(val.chars().skip(2).collect::<String>()).into_bytes();
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Bytes,
-                    }))
-                }
-                Kind::FStringStart => {
-                    self.bump_any();
-                    let fstring = self.parse_fstring(start)?;
-                    Expression::JoinedStr(Box::new(JoinedStr {
-                        node: self.finish_node(start),
-                        values: fstring,
-                    }))
-                }
-                Kind::PointFloat => {
-                    let val = self.cur_token.to_string(self.source);
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Float,
-                    }))
-                }
-                Kind::ExponentFloat => {
-                    let val = self.cur_token.to_string(self.source);
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Float,
-                    }))
-                }
-                Kind::ImaginaryPointFloat => {
-                    let val = self.cur_token.to_string(self.source);
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Complex,
-                    }))
-                }
-                Kind::ImaginaryExponentFloat => {
-                    let val = self.cur_token.to_string(self.source);
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Complex,
-                    }))
-                }
-                Kind::Ellipsis => {
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Ellipsis,
-                    }))
-                }
-                // TODO: is there something for octal and Hexadecimal?
-                Kind::Octal => {
-                    let val = self.cur_token.to_string(self.source);
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Int,
-                    }))
-                }
-                Kind::Hexadecimal => {
-                    let val = self.cur_token.to_string(self.source);
-                    self.bump_any();
-                    Expression::Constant(Box::new(Constant {
-                        node: self.finish_node(start),
-                        value: ConstantValue::Int,
-                    }))
-                }
-                _ => {
-                    let line = self.get_offset_line_number(start.start);
-                    let kind = self.cur_kind();
-                    let value = self.cur_token().to_string(self.source);
-                    panic!("token {kind:?} {value:?} {start:?} is not atom");
-                }
+                _ => self.parse_literal()?,
             };
 
             // If the current token is a string, we need to check if there are more strings
@@ -2606,162 +2634,7 @@ impl<'a> Parser<'a> {
             if atom_is_string {
                 loop {
                     if self.cur_kind().is_string() {
-                        // TODO: duplicate code
-                        let start = self.start_node();
-                        let next_str = match self.cur_kind() {
-                            Kind::Type | Kind::Match | Kind::Identifier => {
-                                let val = self.cur_token().to_string(self.source);
-                                self.bump_any();
-                                Expression::Name(Box::new(Name {
-                                    node: self.finish_node(start),
-                                    id: val,
-                                    parenthesized: false,
-                                }))
-                            }
-                            Kind::Integer => {
-                                let val = self.cur_token().to_string(self.source);
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Int,
-                                }))
-                            }
-                            Kind::None => {
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::None,
-                                }))
-                            }
-                            Kind::True => {
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Bool(true),
-                                }))
-                            }
-                            Kind::False => {
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Bool(true),
-                                }))
-                            }
-                            Kind::ImaginaryInteger => {
-                                let val = self.cur_token().to_string(self.source);
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Complex,
-                                }))
-                            }
-                            Kind::Bytes => {
-                                let val = &self.cur_token().to_string(self.source);
-                                let bytes_val = extract_string_inside(
-                                    val.strip_prefix('b')
-                                        .expect("bytes literal must start with b")
-                                        .to_string(),
-                                )
-                                .into_bytes();
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Bytes,
-                                }))
-                            }
-                            Kind::StringLiteral => {
-                                let val = self.cur_token().as_str(self.source);
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Str(QuoteType::from(val)),
-                                }))
-                            }
-
-                            Kind::RawBytes => {
-                                let val = &self.cur_token().to_string(self.source);
-                                // rb or br appear in the beginning of raw bytes
-                                let bytes_val =
-                                    extract_string_inside(val.chars().skip(2).collect::<String>())
-                                        .into_bytes();
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Bytes,
-                                }))
-                            }
-                            Kind::FStringStart => {
-                                self.bump_any();
-                                let fstring = self.parse_fstring(start)?;
-                                Expression::JoinedStr(Box::new(JoinedStr {
-                                    node: self.finish_node(start),
-                                    values: fstring,
-                                }))
-                            }
-                            Kind::PointFloat => {
-                                let val = self.cur_token().to_string(self.source);
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Float,
-                                }))
-                            }
-                            Kind::ExponentFloat => {
-                                let val = self.cur_token().to_string(self.source);
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Float,
-                                }))
-                            }
-                            Kind::ImaginaryPointFloat => {
-                                let val = self.cur_token().to_string(self.source);
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Complex,
-                                }))
-                            }
-                            Kind::ImaginaryExponentFloat => {
-                                let val = self.cur_token().to_string(self.source);
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Complex,
-                                }))
-                            }
-                            Kind::Ellipsis => {
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Ellipsis,
-                                }))
-                            }
-                            // TODO: is there something for octal and Hexadecimal?
-                            Kind::Octal => {
-                                let val = self.cur_token().to_string(self.source);
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Int,
-                                }))
-                            }
-                            Kind::Hexadecimal => {
-                                let val = self.cur_token().to_string(self.source);
-                                self.bump_any();
-                                Expression::Constant(Box::new(Constant {
-                                    node: self.finish_node(start),
-                                    value: ConstantValue::Int,
-                                }))
-                            }
-                            _ => {
-                                let line = self.get_offset_line_number(start.start);
-                                let kind = self.cur_kind();
-                                let value = self.cur_token().to_string(self.source);
-                                panic!("token {kind:?} {value:?} {start:?} is not atom");
-                            }
-                        };
-
+                        let next_str = self.parse_literal()?;
                         expr = concat_string_exprs(expr, next_str)?;
                     } else if self.eat(Kind::WhiteSpace) {
                         continue;
@@ -2847,6 +2720,15 @@ impl<'a> Parser<'a> {
         })))
     }
 
+    // https://docs.python.org/3/reference/expressions.html#grammar-token-python-grammar-starred_expression
+    fn parse_star_expression(&mut self) -> Result<Expression, ParsingError> {
+        if self.at(Kind::Mul) {
+            self.parse_starred_expression_real()
+        } else {
+            self.parse_expression()
+        }
+    }
+
     fn parse_starred_expression_real(&mut self) -> Result<Expression, ParsingError> {
         let node = self.start_node();
         self.expect(Kind::Mul);
@@ -2979,8 +2861,8 @@ impl<'a> Parser<'a> {
                                 return Ok(Expression::Slice(Box::new(Slice {
                                     node: self.finish_node(node),
                                     lower: Some(n),
-                                    upper: None,
-                                    step: upper_or_step,
+                                    upper: upper_or_step,
+                                    step: None,
                                 })));
                                 // [expr:expr:expr] end
                             } else {
@@ -3026,12 +2908,12 @@ impl<'a> Parser<'a> {
                         })));
                     // [::expr
                     } else {
-                        let upper = Some(self.parse_expression()?);
+                        let step = Some(self.parse_expression()?);
                         return Ok(Expression::Slice(Box::new(Slice {
                             node: self.finish_node(node),
                             lower: None,
-                            upper,
-                            step: None,
+                            upper: None,
+                            step,
                         })));
                     }
                 // [:expr
@@ -3090,15 +2972,9 @@ impl<'a> Parser<'a> {
                     self.bump_any();
                     ComparisonOperator::NotIn
                 }
-                _ => {
-                    // return Err(self.unepxted_token(node, self.cur_kind()).err().unwrap());
-                    panic!();
-                }
+                _ => return Err(self.unexpected_token()),
             },
-            _ => {
-                // return Err(self.unepxted_token(node, self.cur_kind()).err().unwrap());
-                panic!();
-            }
+            _ => return Err(self.unexpected_token()),
         };
         self.bump_any();
         Ok(op)
@@ -3114,13 +2990,7 @@ impl<'a> Parser<'a> {
             Kind::Mod => Ok(BinaryOperator::Mod),
             Kind::Pow => Ok(BinaryOperator::Pow),
             Kind::MatrixMul => Ok(BinaryOperator::MatMult),
-            _ => {
-                // Err(self
-                // .unepxted_token(self.start_node(), self.cur_kind())
-                // .err()
-                // .unwrap()),
-                panic!();
-            }
+            _ => Err(self.unexpected_token()),
         };
         self.bump_any();
         op
@@ -3159,7 +3029,9 @@ impl<'a> Parser<'a> {
                 if seen_vararg {
                     kwonlyargs.push(param);
                 } else if seen_kwarg {
-                    panic!("positional argument follows keyword argument");
+                    return Err(
+                        self.syntax_error("Parameter cannot follow \"**\" parameter", param.node)
+                    );
                 } else {
                     args.push(param);
                 }
@@ -3171,7 +3043,10 @@ impl<'a> Parser<'a> {
                     must_have_default = true;
                     defaults.push(default_value);
                 } else if must_have_default {
-                    panic!("non-default argument follows default argument");
+                    return Err(self.syntax_error(
+                        "Non-default argument follows default argument",
+                        self.finish_node(node),
+                    ));
                 }
             // If a parameter has a default value, all following parameters up
             // until the “*” must also have a default value — this
@@ -3189,7 +3064,10 @@ impl<'a> Parser<'a> {
                 let (param, default) = self.parse_parameter(is_lambda)?;
                 // default is not allowed for vararg
                 if default.is_some() {
-                    panic!("var-positional argument cannot have default value");
+                    return Err(self.syntax_error(
+                        "Var-positional argument cannot have default value",
+                        self.finish_node(param.node),
+                    ));
                 }
                 vararg = Some(param);
             } else if self.eat(Kind::Pow) {
@@ -3197,7 +3075,10 @@ impl<'a> Parser<'a> {
                 let (param, default) = self.parse_parameter(is_lambda)?;
                 // default is not allowed for kwarg
                 if default.is_some() {
-                    panic!("var-keyword argument cannot have default value");
+                    return Err(self.syntax_error(
+                        "Var-keyword argument cannot have default value",
+                        self.finish_node(param.node),
+                    ));
                 }
                 kwarg = Some(param);
             } else if self.eat(Kind::Comma) {
@@ -3255,10 +3136,10 @@ impl<'a> Parser<'a> {
     }
 
     // the FStringStart token is consumed by the caller
-    fn parse_fstring(&mut self, node: Node) -> Result<Vec<Expression>, ParsingError> {
+    fn parse_fstring(&mut self, raw: bool) -> Result<Vec<Expression>, ParsingError> {
         let mut expressions = vec![];
         while self.cur_kind() != Kind::FStringEnd {
-            expressions.push(self.parse_fstring_middle()?)
+            expressions.push(self.parse_fstring_middle(raw)?)
         }
         self.bump(Kind::FStringEnd);
         Ok(expressions)
@@ -3314,16 +3195,14 @@ impl<'a> Parser<'a> {
                         name,
                     }));
                 }
-                _ => {
-                    panic!("Unexpected token {:?}", self.cur_token());
-                }
+                _ => return Err(self.unexpected_token()),
             }
             if !self.at(Kind::RightBrace) {
                 self.expect(Kind::Comma)?;
             }
         }
         if type_params.is_empty() {
-            panic!("Unexpected token {:?}", self.cur_token());
+            return Err(self.unexpected_token());
         }
         Ok(type_params)
     }
@@ -3350,7 +3229,7 @@ impl<'a> Parser<'a> {
         })))
     }
 
-    fn parse_fstring_replacement_field(&mut self) -> Result<Expression, ParsingError> {
+    fn parse_fstring_replacement_field(&mut self, raw: bool) -> Result<Expression, ParsingError> {
         let node = self.start_node();
         self.bump(Kind::LeftBracket);
 
@@ -3369,7 +3248,7 @@ impl<'a> Parser<'a> {
                 "s" => 115,
                 "r" => 114,
                 "a" => 97,
-                _ => panic!("should not happen"),
+                _ => return Err(self.unexpected_token()),
             };
             self.bump_any();
         }
@@ -3377,7 +3256,7 @@ impl<'a> Parser<'a> {
         let format_spec = if self.eat(Kind::Colon) {
             let mut specs = vec![];
             while matches!(self.cur_kind(), Kind::FStringMiddle | Kind::LeftBracket) {
-                specs.push(self.parse_fstring_middle()?);
+                specs.push(self.parse_fstring_middle(raw)?);
             }
             Some(Expression::JoinedStr(Box::new(JoinedStr {
                 node: self.finish_node(format_spec_node),
@@ -3396,7 +3275,7 @@ impl<'a> Parser<'a> {
         })))
     }
 
-    fn parse_fstring_middle(&mut self) -> Result<Expression, ParsingError> {
+    fn parse_fstring_middle(&mut self, raw: bool) -> Result<Expression, ParsingError> {
         let node = self.start_node();
         match self.cur_kind() {
             Kind::FStringMiddle => {
@@ -3404,13 +3283,11 @@ impl<'a> Parser<'a> {
                 self.bump(Kind::FStringMiddle);
                 Ok(Expression::Constant(Box::new(Constant {
                     node: self.finish_node(node),
-                    value: ConstantValue::Str(QuoteType::from(val)),
+                    value: ConstantValue::Str(literal::decode_fstring_middle(val, raw)),
                 })))
             }
-            Kind::LeftBracket => self.parse_fstring_replacement_field(),
-            _ => {
-                panic!("kind is {}", self.cur_token().kind);
-            }
+            Kind::LeftBracket => self.parse_fstring_replacement_field(raw),
+            _ => Err(self.unexpected_token()),
         }
     }
 
@@ -3461,6 +3338,9 @@ mod tests {
             "a ^= 1",
             "a |= 1",
             // annotated assignment
+            // star targets
+            "a, *b = c",
+            "*a, b = c",
         ] {
             let mut parser = Parser::new(test_case);
             let program = parser.parse().expect("parsing failed");
@@ -3961,6 +3841,36 @@ except *Exception as e:
         }
     }
 
+    #[test]
+    fn test_error_recovery() {
+        for test_case in &[
+            "a = \nb = 1",
+            "a.\nb = 1",
+            "foo(a=1, 2)\nb = 1",
+            "def f(a=1, b):\n    pass\nb = 1",
+            "def f():\n    a = 1 +\n    return a\nb = 1",
+            "a = 1\n    b = 2\n    c = 3\nd = 4",
+            "a = 1 2\nb = 1",
+            "a = $\nb = 1",
+            "a = 'abc\nb = 1",
+            "a = f'abc\nb = 1",
+            "a = f'{b:",
+            ")\nb = 1",
+        ] {
+            let mut parser = Parser::new(test_case);
+            let (program, errors) = parser.parse_with_errors();
+            assert!(!errors.is_empty());
+
+            insta::with_settings!({
+                    description => test_case.to_string(), // the template source code
+                        snapshot_path => "../../test_data/output/",
+                    omit_expression => true // do not include the default expression
+                }, {
+                    assert_debug_snapshot!((program, errors));
+            });
+        }
+    }
+
     #[test]
     fn test_constant_value_get_source() {
         for source in &[
@@ -3981,9 +3891,57 @@ except *Exception as e:
                 .expect_expression_statement()
                 .expect_constant();
 
-            let value = constant_value.get_value(source);
-            dbg!(&value);
-            assert!(value.eq("hello"));
+            assert_eq!(
+                constant_value.value,
+                ConstantValue::Str("hello".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_constant_values() {
+        use num_bigint::BigInt;
+
+        for (source, expected) in [
+            ("1_000", ConstantValue::Int(BigInt::from(1000))),
+            ("0x_ff", ConstantValue::Int(BigInt::from(255))),
+            ("0o17", ConstantValue::Int(BigInt::from(15))),
+            ("0b101", ConstantValue::Int(BigInt::from(5))),
+            (
+                "123456789012345678901234567890",
+                ConstantValue::Int("123456789012345678901234567890".parse().unwrap()),
+            ),
+            ("1.5e3", ConstantValue::Float(1500.0)),
+            (
+                "2.5j",
+                ConstantValue::Complex {
+                    real: 0.0,
+                    imaginary: 2.5,
+                },
+            ),
+            (
+                r"'a\tb\x41é\101\q'",
+                ConstantValue::Str("a\tbAéA\\q".to_string()),
+            ),
+            (r"r'a\tb'", ConstantValue::Str(r"a\tb".to_string())),
+            ("'a\\\nb'", ConstantValue::Str("ab".to_string())),
+            (r"b'a\x00\xff'", ConstantValue::Bytes(vec![b'a', 0, 255])),
+            (r"rb'a\x00'", ConstantValue::Bytes(br"a\x00".to_vec())),
+            ("b'a' b'b'", ConstantValue::Bytes(b"ab".to_vec())),
+            (r"'a' r'\n'", ConstantValue::Str(r"a\n".to_string())),
+        ] {
+            let mut parser = Parser::new(source);
+            let module = parser.parse().unwrap();
+
+            let constant_value = module
+                .body
+                .first()
+                .unwrap()
+                .clone()
+                .expect_expression_statement()
+                .expect_constant();
+
+            assert_eq!(constant_value.value, expected, "source: {source}");
         }
     }
 
@@ -20,3 +20,6 @@ async for a in b:
 
 for a in [1, 2, 3]:
     pass
+
+for a, *b in c:
+    pass
@@ -30,3 +30,7 @@ month_names = ['Januari', 'Februari', 'Maart',      # These are the
 [a for a in b for c in d]
 
 [a for a in b if c for d in e]
+
+[a async for a in b]
+
+[a for a in b async for c in a]
@@ -40,6 +40,7 @@ pub trait TraversalVisitor {
             Statement::AsyncWithStatement(w) => self.visit_async_with(w),
             Statement::AsyncFunctionDef(f) => self.visit_async_function_def(f),
             Statement::TypeAlias(t) => self.visit_type_alias(t),
+            Statement::Invalid(i) => self.visit_invalid(i),
         }
     }
 
@@ -373,6 +374,8 @@ pub trait TraversalVisitor {
     fn visit_type_alias(&mut self, _t: &TypeAlias) {
         todo!()
     }
+
+    fn visit_invalid(&mut self, _i: &Invalid) {}
 }
 
 #[allow(dead_code)]
@@ -408,6 +411,7 @@ pub trait TraversalVisitorGeneric<T> {
             Statement::AsyncWithStatement(w) => self.visit_async_with(w),
             Statement::AsyncFunctionDef(f) => self.visit_async_function_def(f),
             Statement::TypeAlias(a) => self.visit_type_alias(a),
+            Statement::Invalid(i) => self.visit_invalid(i),
         }
     }
     fn visit_expr(&self, e: &Expression) -> T {
@@ -634,4 +638,8 @@ pub trait TraversalVisitorGeneric<T> {
     fn visit_type_alias(&self, _a: &TypeAlias) -> T {
         todo!()
     }
+
+    fn visit_invalid(&self, _i: &Invalid) -> T {
+        todo!()
+    }
 }
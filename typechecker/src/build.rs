@@ -11,6 +11,7 @@ use log::debug;
 
 use crate::{
     checker::TypeChecker,
+    diagnostic::Diagnostic,
     file::{EnderpyFile, ImportKinds},
     ruff_python_import_resolver::{
         self as ruff_python_resolver, execution_environment, import_result::ImportResult,
@@ -99,12 +100,28 @@ impl<'a> BuildManager {
         let span = span!(Level::TRACE, "type check", path = %path.display());
         let _guard = span.enter();
         let mut checker = TypeChecker::new(*id, self);
+        for error in file.parse_errors.iter() {
+            checker.add_syntax_error(error);
+        }
         for stmt in file.tree.body.iter() {
             checker.type_check(stmt);
         }
+        checker.check_module_body(&file.tree.body);
+        checker
+            .diagnostics
+            .sort_by_key(|d| (d.range.start.line, d.range.start.character));
         checker
     }
 
+    /// The diagnostics of a file, which are empty when the file was not built.
+    pub fn get_diagnostics(&self, path: &Path) -> Vec<Diagnostic> {
+        let Some(file) = self.paths.get(path).and_then(|id| self.files.get(&id)) else {
+            return vec![];
+        };
+        let checker = self.type_check(path, &file);
+        checker.diagnostics
+    }
+
     pub fn get_symbol_table_by_path(&'a self, path: &Path) -> Arc<SymbolTable> {
         let module_id = self.paths.get(path).expect("incorrect ID");
         return self.get_symbol_table_by_id(&module_id);
@@ -344,8 +361,19 @@ mod tests {
         test_symbols_variables,
         "test_data/inputs/symbol_table/variables.py"
     );
+    symbol_table_test!(
+        test_symbols_comprehensions,
+        "test_data/inputs/symbol_table/comprehensions.py"
+    );
     symbol_table_test!(
         test_symbols_import_star,
         "test_data/inputs/import_star_test/a.py"
     );
+
+    #[test]
+    fn test_diagnostics_of_unknown_path() {
+        let manager = BuildManager::new(Settings::test_settings());
+        let path = Path::new("test_data/inputs/not_built.py");
+        assert!(manager.get_diagnostics(path).is_empty());
+    }
 }
@@ -0,0 +1,1106 @@
+//! Control flow graph of a function or module body.
+//!
+//! The graph is made of basic blocks. Each block holds flow nodes that are executed in
+//! order and ends with edges to the blocks that can be executed next. Edges that are
+//! only taken when a condition is true or false carry that condition.
+//!
+//! Nested functions, classes and lambdas have their own graphs. Their bodies are not
+//! part of the graph of the enclosing scope.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use enderpy_python_parser::ast::*;
+use enderpy_python_parser::parser::parser::intern_lookup;
+
+pub type BlockId = usize;
+
+/// The first block of every graph.
+pub const ENTRY: BlockId = 0;
+/// The block reached by return statements, unhandled exceptions and calls that never
+/// return.
+pub const EXIT: BlockId = 1;
+
+#[derive(Debug, Clone, Copy)]
+pub enum FlowNode<'a> {
+    /// A simple statement, e.g. an assignment or a function definition.
+    Statement(&'a Statement),
+    /// An expression evaluated by a compound statement, e.g. the test of an if.
+    Expression(&'a Expression),
+    /// The target of a for loop or the variable of a with item.
+    Assignment(&'a Expression),
+    /// A pattern of a match case.
+    Pattern(&'a MatchPattern),
+    /// The name of an exception handler is bound to the exception.
+    Bind(&'a str),
+    /// The name of an exception handler is deleted when the handler completes.
+    Delete(&'a str),
+}
+
+impl FlowNode<'_> {
+    /// The source range of the node. Nodes of exception handler names have no range.
+    pub fn range(&self) -> Option<Node> {
+        match self {
+            FlowNode::Statement(s) => Some(s.get_node()),
+            FlowNode::Expression(e) | FlowNode::Assignment(e) => Some(e.get_node()),
+            FlowNode::Pattern(_) | FlowNode::Bind(_) | FlowNode::Delete(_) => None,
+        }
+    }
+
+    /// How the node changes the binding of a name, if it does.
+    pub fn binding_of(&self, name: &str) -> Option<Binding> {
+        let mut effects = vec![];
+        node_effects(self, &mut effects);
+        effects.iter().rev().find_map(|effect| match effect {
+            Effect::Store(n) if *n == name => Some(Binding::Assigned),
+            Effect::Delete(n) if *n == name => Some(Binding::Deleted),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binding {
+    Assigned,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Condition<'a> {
+    pub test: &'a Expression,
+    /// Truthiness of the test when the edge is taken.
+    pub value: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Edge<'a> {
+    pub target: BlockId,
+    pub condition: Option<Condition<'a>>,
+}
+
+#[derive(Debug, Default)]
+pub struct BasicBlock<'a> {
+    pub nodes: Vec<FlowNode<'a>>,
+    pub successors: Vec<Edge<'a>>,
+}
+
+#[derive(Debug)]
+pub struct ControlFlowGraph<'a> {
+    pub blocks: Vec<BasicBlock<'a>>,
+    /// The block reached when execution falls off the end of the body.
+    pub end: BlockId,
+    /// Blocks in which statements start, by the start offset of the statement.
+    /// Statements of finally clauses start in more than one block.
+    statement_blocks: HashMap<u32, Vec<BlockId>>,
+    reachable: Vec<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundness {
+    Unbound,
+    PossiblyUnbound,
+}
+
+/// A use of a local variable that is not assigned on every path leading to it.
+#[derive(Debug, Clone, Copy)]
+pub struct UnboundName<'a> {
+    pub name: &'a Name,
+    pub boundness: Boundness,
+}
+
+impl<'a> ControlFlowGraph<'a> {
+    /// Builds the graph of a body. `is_no_return_call` tells whether an expression
+    /// statement calls a function that never returns.
+    pub fn build(body: &'a [Statement], is_no_return_call: &dyn Fn(&Expression) -> bool) -> Self {
+        let mut builder = Builder {
+            blocks: vec![BasicBlock::default(), BasicBlock::default()],
+            current: ENTRY,
+            statement_blocks: HashMap::new(),
+            loops: vec![],
+            raise_targets: vec![EXIT],
+            return_targets: vec![EXIT],
+            is_no_return_call,
+        };
+        builder.statements(body);
+        let end = builder.new_block();
+        builder.add_edge(builder.current, end, None);
+
+        let mut graph = ControlFlowGraph {
+            blocks: builder.blocks,
+            end,
+            statement_blocks: builder.statement_blocks,
+            reachable: vec![],
+        };
+        graph.reachable = graph.find_reachable_blocks();
+        graph
+    }
+
+    fn find_reachable_blocks(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut queue = VecDeque::from([ENTRY]);
+        reachable[ENTRY] = true;
+        while let Some(block) = queue.pop_front() {
+            for edge in &self.blocks[block].successors {
+                if !reachable[edge.target] {
+                    reachable[edge.target] = true;
+                    queue.push_back(edge.target);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Checks whether any path from the entry executes the statement.
+    /// Statements that are not part of this graph are assumed reachable.
+    pub fn is_reachable(&self, stmt: &Statement) -> bool {
+        match self.statement_blocks.get(&stmt.get_node().start) {
+            Some(blocks) => blocks.iter().any(|b| self.reachable[*b]),
+            None => true,
+        }
+    }
+
+    /// Checks whether execution can fall off the end of the body.
+    pub fn is_end_reachable(&self) -> bool {
+        self.reachable[self.end]
+    }
+
+    /// Finds the nodes of reachable blocks whose source range contains the position.
+    /// Statements of finally clauses are found once for every copy.
+    pub fn find_nodes_at(&self, position: u32) -> Vec<(BlockId, usize)> {
+        let mut found = vec![];
+        for (id, block) in self.blocks.iter().enumerate() {
+            if !self.reachable[id] {
+                continue;
+            }
+            for (index, node) in block.nodes.iter().enumerate() {
+                if node
+                    .range()
+                    .is_some_and(|r| r.start <= position && position < r.end)
+                {
+                    found.push((id, index));
+                }
+            }
+        }
+        found
+    }
+
+    /// The blocks from which one of the given blocks can be reached, including them.
+    pub fn ancestors(&self, blocks: &[BlockId]) -> HashSet<BlockId> {
+        let predecessors = self.predecessors();
+        let mut ancestors: HashSet<BlockId> = blocks.iter().copied().collect();
+        let mut queue: VecDeque<BlockId> = blocks.iter().copied().collect();
+        while let Some(block) = queue.pop_front() {
+            for pred in &predecessors[block] {
+                if self.reachable[*pred] && ancestors.insert(*pred) {
+                    queue.push_back(*pred);
+                }
+            }
+        }
+        ancestors
+    }
+
+    /// Names that are assigned in the body and are not declared global or nonlocal.
+    pub fn local_names(&self) -> HashSet<&'a str> {
+        let mut locals = HashSet::new();
+        let mut non_locals = HashSet::new();
+        for node in self.blocks.iter().flat_map(|b| b.nodes.iter()) {
+            let mut effects = vec![];
+            node_effects(node, &mut effects);
+            for effect in effects {
+                if let Effect::Store(name) = effect {
+                    locals.insert(name);
+                }
+            }
+            if let FlowNode::Statement(Statement::Global(g)) = node {
+                non_locals.extend(g.names.iter().map(|n| n.as_str()));
+            }
+            if let FlowNode::Statement(Statement::Nonlocal(n)) = node {
+                non_locals.extend(n.names.iter().map(|n| n.as_str()));
+            }
+        }
+        for name in non_locals {
+            locals.remove(name);
+        }
+        locals
+    }
+
+    /// The loads of names that are not assigned in the body. These names resolve in the
+    /// enclosing scopes or in builtins.
+    pub fn free_names(&self) -> Vec<&'a Name> {
+        let locals = self.local_names();
+        let mut free = vec![];
+        for (block, reachable) in self.blocks.iter().zip(&self.reachable) {
+            if !reachable {
+                continue;
+            }
+            let mut effects = vec![];
+            for node in &block.nodes {
+                node_effects(node, &mut effects);
+            }
+            for effect in effects {
+                if let Effect::Load(name) = effect {
+                    if !locals.contains(name.id.as_str()) {
+                        free.push(name);
+                    }
+                }
+            }
+        }
+        // Finally clauses are in the graph twice
+        free.sort_by_key(|name| name.node.start);
+        free.dedup_by_key(|name| name.node.start);
+        free
+    }
+
+    /// The edges that lead to each block, with the blocks they start from.
+    pub fn incoming_edges(&self) -> Vec<Vec<(BlockId, Edge<'a>)>> {
+        let mut incoming = vec![vec![]; self.blocks.len()];
+        for (id, block) in self.blocks.iter().enumerate() {
+            for edge in &block.successors {
+                incoming[edge.target].push((id, *edge));
+            }
+        }
+        incoming
+    }
+
+    pub fn predecessors(&self) -> Vec<Vec<BlockId>> {
+        let mut predecessors = vec![vec![]; self.blocks.len()];
+        for (id, block) in self.blocks.iter().enumerate() {
+            for edge in &block.successors {
+                predecessors[edge.target].push(id);
+            }
+        }
+        predecessors
+    }
+
+    /// Finds the uses of local variables that are not assigned on every path leading
+    /// to them. Only names that are assigned in the body are local, other names come
+    /// from enclosing scopes. `bound_at_entry` are names that have a value when the
+    /// body starts, e.g. parameters.
+    pub fn unbound_names(&self, bound_at_entry: &[&str]) -> Vec<UnboundName<'a>> {
+        let locals = self.local_names();
+        let block_effects: Vec<Vec<Effect>> = self
+            .blocks
+            .iter()
+            .map(|block| {
+                let mut effects = vec![];
+                for node in &block.nodes {
+                    node_effects(node, &mut effects);
+                }
+                effects
+            })
+            .collect();
+        if locals.is_empty() {
+            return vec![];
+        }
+
+        // Forward dataflow over the names that are assigned on all paths (`definite`)
+        // and on some path (`maybe`). Blocks that are not visited yet have all names
+        // in `definite`, so the sets only shrink and the loop ends on cyclic graphs.
+        let predecessors = self.predecessors();
+        let entry_state: HashSet<&str> = bound_at_entry.iter().copied().collect();
+        let all_names: HashSet<&str> = locals.union(&entry_state).copied().collect();
+        let mut definite: Vec<HashSet<&str>> = vec![all_names; self.blocks.len()];
+        let mut maybe: Vec<HashSet<&str>> = vec![HashSet::new(); self.blocks.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in 0..self.blocks.len() {
+                if !self.reachable[block] {
+                    continue;
+                }
+                let (mut block_definite, mut block_maybe) =
+                    self.block_input(block, &predecessors, &definite, &maybe, &entry_state);
+                apply_effects(&block_effects[block], &mut block_definite, &mut block_maybe);
+                if definite[block] != block_definite {
+                    definite[block] = block_definite;
+                    changed = true;
+                }
+                if maybe[block] != block_maybe {
+                    maybe[block] = block_maybe;
+                    changed = true;
+                }
+            }
+        }
+
+        // Finally clauses are in the graph twice, so a load can be seen more than once.
+        // The state of every load is (bound on some path, bound on all paths).
+        let mut loads: HashMap<u32, (&Name, bool, bool)> = HashMap::new();
+        for (block, effects) in block_effects.iter().enumerate() {
+            if !self.reachable[block] {
+                continue;
+            }
+            let (mut block_definite, mut block_maybe) =
+                self.block_input(block, &predecessors, &definite, &maybe, &entry_state);
+            for effect in effects {
+                match effect {
+                    Effect::Load(name) if locals.contains(name.id.as_str()) => {
+                        let load = loads.entry(name.node.start).or_insert((name, false, true));
+                        load.1 |= block_maybe.contains(name.id.as_str());
+                        load.2 &= block_definite.contains(name.id.as_str());
+                    }
+                    _ => apply_effects(
+                        std::slice::from_ref(effect),
+                        &mut block_definite,
+                        &mut block_maybe,
+                    ),
+                }
+            }
+        }
+        let mut unbound: Vec<UnboundName> = loads
+            .into_values()
+            .filter_map(|(name, bound_on_some_path, bound_on_all_paths)| {
+                let boundness = if !bound_on_some_path {
+                    Boundness::Unbound
+                } else if !bound_on_all_paths {
+                    Boundness::PossiblyUnbound
+                } else {
+                    return None;
+                };
+                Some(UnboundName { name, boundness })
+            })
+            .collect();
+        unbound.sort_by_key(|u| u.name.node.start);
+        unbound
+    }
+
+    /// Merges the output states of the reachable predecessors of a block.
+    fn block_input<'s>(
+        &self,
+        block: BlockId,
+        predecessors: &[Vec<BlockId>],
+        definite: &[HashSet<&'s str>],
+        maybe: &[HashSet<&'s str>],
+        entry_state: &HashSet<&'s str>,
+    ) -> (HashSet<&'s str>, HashSet<&'s str>) {
+        if block == ENTRY {
+            return (entry_state.clone(), entry_state.clone());
+        }
+        let mut block_definite: Option<HashSet<&str>> = None;
+        let mut block_maybe = HashSet::new();
+        for pred in &predecessors[block] {
+            if !self.reachable[*pred] {
+                continue;
+            }
+            block_definite = Some(match block_definite {
+                Some(d) => d.intersection(&definite[*pred]).copied().collect(),
+                None => definite[*pred].clone(),
+            });
+            block_maybe.extend(maybe[*pred].iter().copied());
+        }
+        (block_definite.unwrap_or_default(), block_maybe)
+    }
+}
+
+struct Loop {
+    /// Target of continue statements.
+    head: BlockId,
+    /// Target of break statements.
+    after: BlockId,
+}
+
+struct Builder<'a, 'f> {
+    blocks: Vec<BasicBlock<'a>>,
+    current: BlockId,
+    statement_blocks: HashMap<u32, Vec<BlockId>>,
+    loops: Vec<Loop>,
+    /// Where exceptions raised by the statement being built go.
+    raise_targets: Vec<BlockId>,
+    /// Where return statements go. Returns in a try with a finally clause run the
+    /// finally clause first.
+    return_targets: Vec<BlockId>,
+    is_no_return_call: &'f dyn Fn(&Expression) -> bool,
+}
+
+impl<'a, 'f> Builder<'a, 'f> {
+    fn new_block(&mut self) -> BlockId {
+        self.blocks.push(BasicBlock::default());
+        self.blocks.len() - 1
+    }
+
+    /// Adds an edge unless its condition can never hold, e.g. the body of `if False`.
+    fn add_edge(&mut self, from: BlockId, to: BlockId, condition: Option<Condition<'a>>) {
+        if let Some(condition) = &condition {
+            if is_constant_bool(condition.test, !condition.value) {
+                return;
+            }
+        }
+        self.blocks[from].successors.push(Edge {
+            target: to,
+            condition,
+        });
+    }
+
+    fn push(&mut self, node: FlowNode<'a>) {
+        self.blocks[self.current].nodes.push(node);
+    }
+
+    /// Ends the current block with a jump. Statements that follow the jump go to a
+    /// new block without predecessors.
+    fn jump(&mut self, target: BlockId) {
+        self.add_edge(self.current, target, None);
+        self.current = self.new_block();
+    }
+
+    fn raise_target(&self) -> BlockId {
+        *self.raise_targets.last().unwrap()
+    }
+
+    fn statements(&mut self, body: &'a [Statement]) {
+        for stmt in body {
+            self.statement(stmt);
+        }
+    }
+
+    fn statement(&mut self, stmt: &'a Statement) {
+        self.statement_blocks
+            .entry(stmt.get_node().start)
+            .or_default()
+            .push(self.current);
+        match stmt {
+            Statement::ReturnStmt(_) => {
+                self.push(FlowNode::Statement(stmt));
+                let target = *self.return_targets.last().unwrap();
+                self.jump(target);
+            }
+            Statement::Raise(_) => {
+                self.push(FlowNode::Statement(stmt));
+                self.jump(self.raise_target());
+            }
+            // The rest of the body only runs when the test is true
+            Statement::Assert(a) => {
+                self.push(FlowNode::Statement(stmt));
+                let before = self.current;
+                let after = self.new_block();
+                self.add_edge(
+                    before,
+                    after,
+                    Some(Condition {
+                        test: &a.test,
+                        value: true,
+                    }),
+                );
+                self.add_edge(
+                    before,
+                    self.raise_target(),
+                    Some(Condition {
+                        test: &a.test,
+                        value: false,
+                    }),
+                );
+                self.current = after;
+            }
+            Statement::ExpressionStatement(e) if (self.is_no_return_call)(e) => {
+                self.push(FlowNode::Statement(stmt));
+                self.jump(self.raise_target());
+            }
+            Statement::BreakStmt(_) => {
+                self.push(FlowNode::Statement(stmt));
+                if let Some(target) = self.loops.last().map(|l| l.after) {
+                    self.jump(target);
+                }
+            }
+            Statement::ContinueStmt(_) => {
+                self.push(FlowNode::Statement(stmt));
+                if let Some(target) = self.loops.last().map(|l| l.head) {
+                    self.jump(target);
+                }
+            }
+            Statement::IfStatement(i) => self.if_statement(i),
+            Statement::WhileStatement(w) => self.while_statement(w),
+            Statement::ForStatement(f) => {
+                self.for_statement(&f.target, &f.iter, &f.body, &f.orelse)
+            }
+            Statement::AsyncForStatement(f) => {
+                self.for_statement(&f.target, &f.iter, &f.body, &f.orelse)
+            }
+            Statement::WithStatement(w) => self.with_statement(&w.items, &w.body),
+            Statement::AsyncWithStatement(w) => self.with_statement(&w.items, &w.body),
+            Statement::TryStatement(t) => {
+                self.try_statement(&t.body, &t.handlers, &t.orelse, &t.finalbody)
+            }
+            Statement::TryStarStatement(t) => {
+                self.try_statement(&t.body, &t.handlers, &t.orelse, &t.finalbody)
+            }
+            Statement::MatchStmt(m) => self.match_statement(m),
+            _ => self.push(FlowNode::Statement(stmt)),
+        }
+    }
+
+    fn if_statement(&mut self, i: &'a If) {
+        self.push(FlowNode::Expression(&i.test));
+        let before = self.current;
+        let after = self.new_block();
+
+        let body = self.new_block();
+        self.add_edge(
+            before,
+            body,
+            Some(Condition {
+                test: &i.test,
+                value: true,
+            }),
+        );
+        self.current = body;
+        self.statements(&i.body);
+        self.add_edge(self.current, after, None);
+
+        let orelse = self.new_block();
+        self.add_edge(
+            before,
+            orelse,
+            Some(Condition {
+                test: &i.test,
+                value: false,
+            }),
+        );
+        self.current = orelse;
+        self.statements(&i.orelse);
+        self.add_edge(self.current, after, None);
+
+        self.current = after;
+    }
+
+    fn while_statement(&mut self, w: &'a While) {
+        let head = self.new_block();
+        self.add_edge(self.current, head, None);
+        self.current = head;
+        self.push(FlowNode::Expression(&w.test));
+        let after = self.new_block();
+
+        let body = self.new_block();
+        self.add_edge(
+            head,
+            body,
+            Some(Condition {
+                test: &w.test,
+                value: true,
+            }),
+        );
+        self.loops.push(Loop { head, after });
+        self.current = body;
+        self.statements(&w.body);
+        self.add_edge(self.current, head, None);
+        self.loops.pop();
+
+        // The else clause runs when the test becomes false, not after a break
+        let orelse = self.new_block();
+        self.add_edge(
+            head,
+            orelse,
+            Some(Condition {
+                test: &w.test,
+                value: false,
+            }),
+        );
+        self.current = orelse;
+        self.statements(&w.orelse);
+        self.add_edge(self.current, after, None);
+
+        self.current = after;
+    }
+
+    fn for_statement(
+        &mut self,
+        target: &'a Expression,
+        iter: &'a Expression,
+        body: &'a [Statement],
+        orelse: &'a [Statement],
+    ) {
+        self.push(FlowNode::Expression(iter));
+        let head = self.new_block();
+        self.add_edge(self.current, head, None);
+        let after = self.new_block();
+
+        let body_block = self.new_block();
+        self.add_edge(head, body_block, None);
+        self.loops.push(Loop { head, after });
+        self.current = body_block;
+        self.push(FlowNode::Assignment(target));
+        self.statements(body);
+        self.add_edge(self.current, head, None);
+        self.loops.pop();
+
+        let orelse_block = self.new_block();
+        self.add_edge(head, orelse_block, None);
+        self.current = orelse_block;
+        self.statements(orelse);
+        self.add_edge(self.current, after, None);
+
+        self.current = after;
+    }
+
+    // TODO: Context managers whose __exit__ returns bool can swallow exceptions
+    fn with_statement(&mut self, items: &'a [WithItem], body: &'a [Statement]) {
+        for item in items {
+            self.push(FlowNode::Expression(&item.context_expr));
+            if let Some(optional_vars) = &item.optional_vars {
+                self.push(FlowNode::Assignment(optional_vars));
+            }
+        }
+        self.statements(body);
+    }
+
+    fn try_statement(
+        &mut self,
+        body: &'a [Statement],
+        handlers: &'a [ExceptHandler],
+        orelse: &'a [Statement],
+        finalbody: &'a [Statement],
+    ) {
+        let after = self.new_block();
+        let outer_raise = self.raise_target();
+        let outer_return = *self.return_targets.last().unwrap();
+        // Exceptions that are not handled and return statements run the finally clause
+        // before they leave the try statement
+        let abnormal_finally = if finalbody.is_empty() {
+            None
+        } else {
+            Some(self.new_block())
+        };
+        let unhandled = abnormal_finally.unwrap_or(outer_raise);
+        if let Some(abnormal_finally) = abnormal_finally {
+            self.return_targets.push(abnormal_finally);
+        }
+
+        // Any statement of the try body can raise, so the handlers can be reached from
+        // every block of the body and before the first statement of the body runs
+        let dispatch = self.new_block();
+        let body_start = self.new_block();
+        self.add_edge(self.current, body_start, None);
+        self.add_edge(self.current, dispatch, None);
+        self.current = body_start;
+        self.raise_targets.push(dispatch);
+        self.statements(body);
+        self.raise_targets.pop();
+        for block in body_start..self.blocks.len() {
+            self.add_edge(block, dispatch, None);
+        }
+
+        self.raise_targets.push(unhandled);
+        self.statements(orelse);
+        let mut normal_ends = vec![self.current];
+        let handlers_start = self.blocks.len();
+        for handler in handlers {
+            let handler_block = self.new_block();
+            self.add_edge(dispatch, handler_block, None);
+            self.current = handler_block;
+            if let Some(typ) = &handler.typ {
+                self.push(FlowNode::Expression(typ));
+            }
+            if let Some(name) = &handler.name {
+                self.push(FlowNode::Bind(name));
+            }
+            self.statements(&handler.body);
+            if let Some(name) = &handler.name {
+                self.push(FlowNode::Delete(name));
+            }
+            normal_ends.push(self.current);
+        }
+        if !handlers.iter().any(|h| h.typ.is_none()) {
+            self.add_edge(dispatch, unhandled, None);
+        }
+        self.raise_targets.pop();
+
+        let Some(abnormal_finally) = abnormal_finally else {
+            for block in normal_ends {
+                self.add_edge(block, after, None);
+            }
+            self.current = after;
+            return;
+        };
+        self.return_targets.pop();
+        for block in handlers_start..self.blocks.len() {
+            self.add_edge(block, abnormal_finally, None);
+        }
+
+        // The finally clause is built once for the normal path and once for the
+        // paths that leave with an exception or a return
+        let normal_finally = self.new_block();
+        for block in normal_ends {
+            self.add_edge(block, normal_finally, None);
+        }
+        self.current = normal_finally;
+        self.statements(finalbody);
+        self.add_edge(self.current, after, None);
+
+        self.current = abnormal_finally;
+        self.statements(finalbody);
+        self.add_edge(self.current, outer_raise, None);
+        if outer_return != outer_raise {
+            self.add_edge(self.current, outer_return, None);
+        }
+
+        self.current = after;
+    }
+
+    fn match_statement(&mut self, m: &'a Match) {
+        self.push(FlowNode::Expression(&m.subject));
+        let before = self.current;
+        let after = self.new_block();
+        let mut exhaustive = false;
+        for case in &m.cases {
+            let case_block = self.new_block();
+            self.add_edge(before, case_block, None);
+            self.current = case_block;
+            self.push(FlowNode::Pattern(&case.pattern));
+            if let Some(guard) = &case.guard {
+                self.push(FlowNode::Expression(guard));
+            }
+            self.statements(&case.body);
+            self.add_edge(self.current, after, None);
+            // Cases after an irrefutable pattern are never tried
+            if case.guard.is_none() && is_irrefutable(&case.pattern) {
+                exhaustive = true;
+                break;
+            }
+        }
+        if !exhaustive {
+            self.add_edge(before, after, None);
+        }
+        self.current = after;
+    }
+}
+
+/// Patterns that match every subject.
+/// https://peps.python.org/pep-0634/#irrefutable-case-blocks
+fn is_irrefutable(pattern: &MatchPattern) -> bool {
+    match pattern {
+        MatchPattern::MatchAs(a) => a.pattern.as_ref().map_or(true, is_irrefutable),
+        MatchPattern::MatchOr(patterns) => patterns.iter().any(is_irrefutable),
+        _ => false,
+    }
+}
+
+/// Checks whether an expression is a constant with the given truthiness.
+pub fn is_constant_bool(expr: &Expression, value: bool) -> bool {
+    match expr {
+        Expression::Constant(c) => match &c.value {
+            ConstantValue::Bool(b) => *b == value,
+            ConstantValue::Int(i) => (i.bits() == 0) != value,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// How a flow node reads and writes local variables, in execution order.
+#[derive(Debug, Clone, Copy)]
+enum Effect<'a> {
+    Load(&'a Name),
+    Store(&'a str),
+    Delete(&'a str),
+}
+
+fn apply_effects<'a>(
+    effects: &[Effect<'a>],
+    definite: &mut HashSet<&'a str>,
+    maybe: &mut HashSet<&'a str>,
+) {
+    for effect in effects {
+        match effect {
+            Effect::Load(_) => {}
+            Effect::Store(name) => {
+                definite.insert(name);
+                maybe.insert(name);
+            }
+            Effect::Delete(name) => {
+                definite.remove(name);
+                maybe.remove(name);
+            }
+        }
+    }
+}
+
+fn node_effects<'a>(node: &FlowNode<'a>, effects: &mut Vec<Effect<'a>>) {
+    match node {
+        FlowNode::Statement(stmt) => statement_effects(stmt, effects),
+        FlowNode::Expression(expr) => expression_effects(expr, effects),
+        FlowNode::Assignment(target) => target_effects(target, effects),
+        FlowNode::Pattern(pattern) => pattern_effects(pattern, effects),
+        FlowNode::Bind(name) => effects.push(Effect::Store(name)),
+        FlowNode::Delete(name) => effects.push(Effect::Delete(name)),
+    }
+}
+
+fn statement_effects<'a>(stmt: &'a Statement, effects: &mut Vec<Effect<'a>>) {
+    match stmt {
+        Statement::AssignStatement(a) => {
+            expression_effects(&a.value, effects);
+            for target in &a.targets {
+                target_effects(target, effects);
+            }
+        }
+        // An annotation without a value declares the variable without assigning it
+        Statement::AnnAssignStatement(a) => {
+            if let Some(value) = &a.value {
+                expression_effects(value, effects);
+                target_effects(&a.target, effects);
+            }
+        }
+        Statement::AugAssignStatement(a) => {
+            expression_effects(&a.target, effects);
+            expression_effects(&a.value, effects);
+            target_effects(&a.target, effects);
+        }
+        Statement::ExpressionStatement(e) => expression_effects(e, effects),
+        Statement::Assert(a) => {
+            expression_effects(&a.test, effects);
+            if let Some(msg) = &a.msg {
+                expression_effects(msg, effects);
+            }
+        }
+        Statement::Delete(d) => {
+            for target in &d.targets {
+                expression_effects(target, effects);
+                if let Expression::Name(n) = target {
+                    effects.push(Effect::Delete(&n.id));
+                }
+            }
+        }
+        Statement::ReturnStmt(r) => {
+            if let Some(value) = &r.value {
+                expression_effects(value, effects);
+            }
+        }
+        Statement::Raise(r) => {
+            if let Some(exc) = &r.exc {
+                expression_effects(exc, effects);
+            }
+            if let Some(cause) = &r.cause {
+                expression_effects(cause, effects);
+            }
+        }
+        Statement::Import(i) => {
+            for alias in &i.names {
+                // import a.b binds a
+                let name = match &alias.asname {
+                    Some(asname) => asname.as_str(),
+                    None => alias.name.split('.').next().unwrap_or(&alias.name),
+                };
+                effects.push(Effect::Store(name));
+            }
+        }
+        Statement::ImportFrom(i) => {
+            for alias in i.names.iter().filter(|a| a.name != "*") {
+                effects.push(Effect::Store(alias.asname.as_ref().unwrap_or(&alias.name)));
+            }
+        }
+        Statement::FunctionDef(f) => {
+            expressions_effects(&f.decorator_list, effects);
+            arguments_effects(&f.args, effects);
+            effects.push(Effect::Store(intern_lookup(f.name)));
+        }
+        Statement::AsyncFunctionDef(f) => {
+            expressions_effects(&f.decorator_list, effects);
+            arguments_effects(&f.args, effects);
+            effects.push(Effect::Store(intern_lookup(f.name)));
+        }
+        Statement::ClassDef(c) => {
+            expressions_effects(&c.decorator_list, effects);
+            expressions_effects(&c.bases, effects);
+            for keyword in &c.keywords {
+                expression_effects(&keyword.value, effects);
+            }
+            effects.push(Effect::Store(intern_lookup(c.name)));
+        }
+        Statement::TypeAlias(t) => effects.push(Effect::Store(&t.name)),
+        _ => {}
+    }
+}
+
+/// Default values are evaluated where the function is defined. Annotations are
+/// skipped because they can be deferred.
+fn arguments_effects<'a>(args: &'a Arguments, effects: &mut Vec<Effect<'a>>) {
+    expressions_effects(&args.defaults, effects);
+    for default in args.kw_defaults.iter().flatten() {
+        expression_effects(default, effects);
+    }
+}
+
+fn target_effects<'a>(target: &'a Expression, effects: &mut Vec<Effect<'a>>) {
+    match target {
+        Expression::Name(n) => effects.push(Effect::Store(&n.id)),
+        Expression::Tuple(t) => {
+            for element in &t.elements {
+                target_effects(element, effects);
+            }
+        }
+        Expression::List(l) => {
+            for element in &l.elements {
+                target_effects(element, effects);
+            }
+        }
+        Expression::Starred(s) => target_effects(&s.value, effects),
+        Expression::Attribute(a) => expression_effects(&a.value, effects),
+        Expression::Subscript(s) => {
+            expression_effects(&s.value, effects);
+            expression_effects(&s.slice, effects);
+        }
+        _ => expression_effects(target, effects),
+    }
+}
+
+fn expressions_effects<'a>(exprs: &'a [Expression], effects: &mut Vec<Effect<'a>>) {
+    for expr in exprs {
+        expression_effects(expr, effects);
+    }
+}
+
+fn expression_effects<'a>(expr: &'a Expression, effects: &mut Vec<Effect<'a>>) {
+    match expr {
+        Expression::Constant(_) => {}
+        Expression::Name(n) => effects.push(Effect::Load(n)),
+        Expression::List(l) => expressions_effects(&l.elements, effects),
+        Expression::Tuple(t) => expressions_effects(&t.elements, effects),
+        Expression::Set(s) => expressions_effects(&s.elements, effects),
+        Expression::Dict(d) => {
+            for (key, value) in d.keys.iter().zip(d.values.iter()) {
+                expression_effects(key, effects);
+                expression_effects(value, effects);
+            }
+        }
+        Expression::BoolOp(b) => expressions_effects(&b.values, effects),
+        Expression::UnaryOp(u) => expression_effects(&u.operand, effects),
+        Expression::BinOp(b) => {
+            expression_effects(&b.left, effects);
+            expression_effects(&b.right, effects);
+        }
+        Expression::NamedExpr(n) => {
+            expression_effects(&n.value, effects);
+            target_effects(&n.target, effects);
+        }
+        Expression::Yield(y) => {
+            if let Some(value) = &y.value {
+                expression_effects(value, effects);
+            }
+        }
+        Expression::YieldFrom(y) => expression_effects(&y.value, effects),
+        Expression::Starred(s) => expression_effects(&s.value, effects),
+        Expression::Generator(g) => comprehension_effects(&g.generators, &[&g.element], effects),
+        Expression::ListComp(l) => comprehension_effects(&l.generators, &[&l.element], effects),
+        Expression::SetComp(s) => comprehension_effects(&s.generators, &[&s.element], effects),
+        Expression::DictComp(d) => {
+            comprehension_effects(&d.generators, &[&d.key, &d.value], effects)
+        }
+        Expression::Attribute(a) => expression_effects(&a.value, effects),
+        Expression::Subscript(s) => {
+            expression_effects(&s.value, effects);
+            expression_effects(&s.slice, effects);
+        }
+        Expression::Slice(s) => {
+            for part in [&s.lower, &s.upper, &s.step].into_iter().flatten() {
+                expression_effects(part, effects);
+            }
+        }
+        Expression::Call(c) => {
+            expression_effects(&c.func, effects);
+            expressions_effects(&c.args, effects);
+            for keyword in &c.keywords {
+                expression_effects(&keyword.value, effects);
+            }
+            for arg in [&c.starargs, &c.kwargs].into_iter().flatten() {
+                expression_effects(arg, effects);
+            }
+        }
+        Expression::Await(a) => expression_effects(&a.value, effects),
+        Expression::Compare(c) => {
+            expression_effects(&c.left, effects);
+            expressions_effects(&c.comparators, effects);
+        }
+        // The body of a lambda runs later in its own scope
+        Expression::Lambda(l) => arguments_effects(&l.args, effects),
+        Expression::IfExp(i) => {
+            expression_effects(&i.test, effects);
+            expression_effects(&i.body, effects);
+            expression_effects(&i.orelse, effects);
+        }
+        Expression::JoinedStr(j) => expressions_effects(&j.values, effects),
+        Expression::FormattedValue(f) => {
+            expression_effects(&f.value, effects);
+            if let Some(format_spec) = &f.format_spec {
+                expression_effects(format_spec, effects);
+            }
+        }
+    }
+}
+
+/// The first iterable of a comprehension is evaluated in the enclosing scope. The
+/// targets are local to the comprehension, so loads of them are not loads of the
+/// enclosing variables. Assignment expressions still bind in the enclosing scope.
+fn comprehension_effects<'a>(
+    generators: &'a [Comprehension],
+    elements: &[&'a Expression],
+    effects: &mut Vec<Effect<'a>>,
+) {
+    let Some((first, rest)) = generators.split_first() else {
+        return;
+    };
+    expression_effects(&first.iter, effects);
+
+    let mut targets = vec![];
+    for generator in generators {
+        target_effects(&generator.target, &mut targets);
+    }
+    let bound: HashSet<&str> = targets
+        .iter()
+        .filter_map(|effect| match effect {
+            Effect::Store(name) => Some(*name),
+            _ => None,
+        })
+        .collect();
+
+    let mut inner = vec![];
+    expressions_effects(&first.ifs, &mut inner);
+    for generator in rest {
+        expression_effects(&generator.iter, &mut inner);
+        expressions_effects(&generator.ifs, &mut inner);
+    }
+    for element in elements {
+        expression_effects(element, &mut inner);
+    }
+    effects.extend(inner.into_iter().filter(|effect| match effect {
+        Effect::Load(name) => !bound.contains(name.id.as_str()),
+        _ => true,
+    }));
+}
+
+fn pattern_effects<'a>(pattern: &'a MatchPattern, effects: &mut Vec<Effect<'a>>) {
+    match pattern {
+        MatchPattern::MatchValue(v) => expression_effects(&v.value, effects),
+        MatchPattern::MatchSingleton(_) => {}
+        MatchPattern::MatchSequence(patterns) | MatchPattern::MatchOr(patterns) => {
+            for pattern in patterns {
+                pattern_effects(pattern, effects);
+            }
+        }
+        MatchPattern::MatchStar(e) => {
+            if let Expression::Name(n) = e {
+                if n.id != "_" {
+                    effects.push(Effect::Store(&n.id));
+                }
+            }
+        }
+        MatchPattern::MatchMapping(m) => {
+            expressions_effects(&m.keys, effects);
+            for pattern in &m.patterns {
+                pattern_effects(pattern, effects);
+            }
+            if let Some(rest) = &m.rest {
+                effects.push(Effect::Store(rest));
+            }
+        }
+        MatchPattern::MatchAs(a) => {
+            if let Some(pattern) = &a.pattern {
+                pattern_effects(pattern, effects);
+            }
+            if let Some(name) = &a.name {
+                effects.push(Effect::Store(name));
+            }
+        }
+        MatchPattern::MatchClass(c) => {
+            expression_effects(&c.cls, effects);
+            for pattern in c.patterns.iter().chain(c.kwd_patterns.iter()) {
+                pattern_effects(pattern, effects);
+            }
+        }
+    }
+}
@@ -3,29 +3,34 @@ use std::sync::Arc;
 use ast::{Expression, Statement};
 use enderpy_python_parser as parser;
 use enderpy_python_parser::ast::{self, *};
+use enderpy_python_parser::error::ParsingError;
 use enderpy_python_parser::parser::parser::intern_lookup;
 
-use super::{type_evaluator::TypeEvaluator, types::PythonType};
+use super::{
+    type_evaluator::{TypeEvaluator, TypeIssue},
+    types::PythonType,
+};
+use crate::ast_visitor::TraversalVisitor;
 use crate::build::BuildManager;
-use crate::symbol_table::Id;
-use crate::types::ModuleRef;
-use crate::{ast_visitor::TraversalVisitor, diagnostic::CharacterSpan};
+use crate::cfg::{Boundness, ControlFlowGraph};
+use crate::diagnostic::{Diagnostic, Rule, Severity};
+use crate::symbol_table::{Declaration, Id};
+use crate::types::{CallableArgs, CallableParameter, KnownValue, LiteralValue, ModuleRef};
 use rust_lapper::{Interval, Lapper};
 
 #[derive(Clone, Debug)]
 pub struct TypeChecker<'a> {
     pub types: Lapper<u32, PythonType>,
+    pub diagnostics: Vec<Diagnostic>,
     id: Id,
     type_evaluator: TypeEvaluator<'a>,
     build_manager: &'a BuildManager,
     current_scope: u32,
-    prev_scope: u32,
-}
-
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct TypeCheckError {
-    pub msg: String,
-    pub span: CharacterSpan,
+    /// The scopes that enclose the current scope
+    prev_scopes: Vec<u32>,
+    /// Whether the visited code is in the body of an async function, where `await` can
+    /// be used
+    in_async_function: bool,
 }
 
 #[allow(unused)]
@@ -36,8 +41,10 @@ impl<'a> TypeChecker<'a> {
             id,
             build_manager,
             types: Lapper::new(vec![]),
+            diagnostics: vec![],
             current_scope: 0,
-            prev_scope: 0,
+            prev_scopes: vec![],
+            in_async_function: false,
         }
     }
 
@@ -52,8 +59,19 @@ impl<'a> TypeChecker<'a> {
             .get_type(expr, &symbol_table, self.current_scope)
         {
             Ok(t) => t,
+            // Only errors in the checked code are reported, other errors mean that the
+            // type could not be evaluated
             Err(e) => {
-                log::error!("type evaluator error: {} for expr {expr:?}", e);
+                if let Some(issue) = e.downcast_ref::<TypeIssue>() {
+                    self.make_diagnostic(
+                        Rule::GeneralTypeIssue,
+                        issue.to_string(),
+                        expr.get_node().start,
+                        expr.get_node().end,
+                    );
+                } else {
+                    log::debug!("type evaluator error: {} for expr {expr:?}", e);
+                }
                 PythonType::Unknown
             }
         };
@@ -92,14 +110,1121 @@ impl<'a> TypeChecker<'a> {
         });
     }
 
+    fn make_diagnostic(&mut self, code: Rule, body: String, start: u32, end: u32) {
+        self.make_diagnostic_with_suggestion(code, body, None, start, end);
+    }
+
+    fn make_diagnostic_with_suggestion(
+        &mut self,
+        code: Rule,
+        body: String,
+        suggestion: Option<String>,
+        start: u32,
+        end: u32,
+    ) {
+        let file = self.build_manager.files.get(&self.id).unwrap();
+        let range = file.get_range(start, end);
+        self.diagnostics.push(Diagnostic {
+            severity: code.severity(),
+            code,
+            body,
+            suggestion,
+            range,
+        });
+    }
+
+    /// Explains why a value is not assignable to a protocol.
+    fn describe_mismatch(&self, source: &PythonType, target: &PythonType) -> String {
+        match self.type_evaluator.get_protocol_mismatch(source, target) {
+            Some(mismatch) => format!(": {mismatch}"),
+            None => String::new(),
+        }
+    }
+
+    /// Reports an error when the assigned value is not assignable to the declared type.
+    fn check_assignment(&mut self, value: &Expression, annotation: &Expression) {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let declared_type =
+            self.type_evaluator
+                .get_annotation_type(annotation, &symbol_table, self.current_scope);
+        if self.check_typed_dict_literal(value, &declared_type) {
+            return;
+        }
+        let value_type = self.get_value_type(value);
+        if !self
+            .type_evaluator
+            .is_assignable(&value_type, &declared_type)
+        {
+            self.make_diagnostic(
+                Rule::AssignmentTypeMismatch,
+                format!(
+                    "Type \"{value_type}\" is not assignable to declared type \"{declared_type}\"{}",
+                    self.describe_mismatch(&value_type, &declared_type)
+                ),
+                value.get_node().start,
+                value.get_node().end,
+            );
+        }
+    }
+
+    /// Evaluates an annotation in the current scope without recording its type.
+    fn get_declared_type(&self, annotation: &Expression) -> PythonType {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        self.type_evaluator
+            .get_annotation_type(annotation, &symbol_table, self.current_scope)
+    }
+
+    /// Finds the declaration of the function defined at node in the current scope.
+    fn get_function_declaration(&self, name: &str, node: Node) -> Option<Declaration> {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let symbol = symbol_table.lookup_in_scope(name, self.current_scope)?;
+        symbol
+            .declarations
+            .iter()
+            .find(|decl| match decl {
+                Declaration::Function(f) => f.function_node.node == node,
+                Declaration::AsyncFunction(f) => f.function_node.node == node,
+                _ => false,
+            })
+            .cloned()
+    }
+
+    fn check_return_statements(&mut self, return_statements: &[Return], declared: &PythonType) {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        for return_statement in return_statements {
+            let (return_type, node) = match &return_statement.value {
+                Some(value) => {
+                    let return_type = match self.type_evaluator.get_literal_type_of_constant(value)
+                    {
+                        Some(literal_type) => literal_type,
+                        None => self
+                            .type_evaluator
+                            .get_type(value, &symbol_table, self.current_scope)
+                            .unwrap_or(PythonType::Unknown),
+                    };
+                    (return_type, value.get_node())
+                }
+                None => (PythonType::None, return_statement.node),
+            };
+            if !self.type_evaluator.is_assignable(&return_type, declared) {
+                self.make_diagnostic(
+                    Rule::ReturnType,
+                    format!(
+                        "Type \"{return_type}\" is not assignable to return type \"{declared}\"{}",
+                        self.describe_mismatch(&return_type, declared)
+                    ),
+                    node.start,
+                    node.end,
+                );
+            }
+        }
+    }
+
+    /// Checks the values that a generator function yields and returns against the types
+    /// of its declared return type.
+    fn check_generator(
+        &mut self,
+        returns: &Expression,
+        declared: &PythonType,
+        yield_statements: &[Yield],
+        yield_from_statements: &[YieldFrom],
+        return_statements: &[Return],
+    ) {
+        let Some((yield_type, _, return_type)) =
+            self.type_evaluator.get_declared_generator_types(declared)
+        else {
+            self.make_diagnostic(
+                Rule::ReturnType,
+                format!(
+                    "Return type of generator function must be compatible with \"Generator[Any, Any, Any]\", not \"{declared}\""
+                ),
+                returns.get_node().start,
+                returns.get_node().end,
+            );
+            return;
+        };
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let mut yielded = vec![];
+        for yield_statement in yield_statements {
+            yielded.push(match &yield_statement.value {
+                Some(value) => (self.get_value_type(value), value.get_node()),
+                None => (PythonType::None, yield_statement.node),
+            });
+        }
+        for yield_from in yield_from_statements {
+            let delegate = self
+                .type_evaluator
+                .get_type(&yield_from.value, &symbol_table, self.current_scope)
+                .unwrap_or(PythonType::Unknown);
+            yielded.push((
+                self.type_evaluator.get_iterated_type(&delegate),
+                yield_from.value.get_node(),
+            ));
+        }
+        for (value_type, node) in yielded {
+            if !self.type_evaluator.is_assignable(&value_type, &yield_type) {
+                self.make_diagnostic(
+                    Rule::ReturnType,
+                    format!(
+                        "Type \"{value_type}\" is not assignable to yield type \"{yield_type}\"{}",
+                        self.describe_mismatch(&value_type, &yield_type)
+                    ),
+                    node.start,
+                    node.end,
+                );
+            }
+        }
+        self.check_return_statements(return_statements, &return_type);
+    }
+
+    /// Reports functions that can reach the end of their body, which implicitly returns
+    /// None, when None is not assignable to the declared return type.
+    fn check_missing_return(
+        &mut self,
+        body: &[Statement],
+        cfg: &ControlFlowGraph,
+        returns: &Expression,
+        declared: &PythonType,
+    ) {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        // Stubs only declare the signature
+        if symbol_table.is_pyi_file() || is_stub_body(body) {
+            return;
+        }
+        if self
+            .type_evaluator
+            .is_assignable(&PythonType::None, declared)
+        {
+            return;
+        }
+        // Branches that handle every possible value of a name, e.g. all members of an
+        // enum, leave no type for the name at the end
+        let end = body.last().map_or(0, |stmt| stmt.get_node().end);
+        if cfg.is_end_reachable()
+            && !self.type_evaluator.is_end_narrowed_to_never(
+                cfg,
+                end,
+                &symbol_table,
+                self.current_scope,
+            )
+        {
+            self.make_diagnostic(
+                Rule::ReturnType,
+                format!(
+                    "Function with declared return type \"{declared}\" must return value on all code paths"
+                ),
+                returns.get_node().start,
+                returns.get_node().end,
+            );
+        }
+    }
+
+    /// Checks the control flow of the module body. Function and class bodies are checked
+    /// when their definitions are visited.
+    pub fn check_module_body(&mut self, body: &[Statement]) {
+        self.check_control_flow(body, &[]);
+    }
+
+    /// Visits a function definition and checks its body against the declared return type.
+    /// The name of the function starts at name_start.
+    #[allow(clippy::too_many_arguments)]
+    fn check_function(
+        &mut self,
+        node: Node,
+        name: &str,
+        name_start: u32,
+        args: &Arguments,
+        returns: Option<&Expression>,
+        body: &[Statement],
+        is_async: bool,
+    ) {
+        let declared_return_type = returns.map(|r| self.get_declared_type(r));
+        let declaration = self.get_function_declaration(name, node);
+        self.enter_scope(node.start);
+        let in_async_function = std::mem::replace(&mut self.in_async_function, is_async);
+        self.infer_name_type(name, name_start, name_start + name.len() as u32);
+        if let Some(ret_type) = returns {
+            self.visit_expr(ret_type);
+        }
+        for stmt in body {
+            self.visit_stmt(stmt);
+        }
+        self.in_async_function = in_async_function;
+        for arg in parameters(args) {
+            if let Some(annotation) = &arg.annotation {
+                self.infer_annotation_type(annotation);
+            }
+            self.infer_name_type(&arg.arg, arg.node.start, arg.node.end)
+        }
+        let cfg = self.check_control_flow(body, &parameter_names(args));
+        // The declared return type of a coroutine is the type of its awaited value
+        let function = match &declaration {
+            Some(Declaration::Function(f)) => Some((
+                f.is_generator,
+                f.is_overload(),
+                &f.yield_statements,
+                &f.yield_from_statements,
+                &f.return_statements,
+            )),
+            Some(Declaration::AsyncFunction(f)) => Some((
+                f.is_generator,
+                f.is_overload(),
+                &f.yield_statements,
+                &f.yield_from_statements,
+                &f.return_statements,
+            )),
+            _ => None,
+        };
+        if let (
+            Some(returns),
+            Some(declared_return_type),
+            Some((
+                is_generator,
+                is_overload,
+                yield_statements,
+                yield_from_statements,
+                return_statements,
+            )),
+        ) = (returns, &declared_return_type, function)
+        {
+            if is_generator {
+                self.check_generator(
+                    returns,
+                    declared_return_type,
+                    yield_statements,
+                    yield_from_statements,
+                    return_statements,
+                );
+            } else {
+                self.check_return_statements(return_statements, declared_return_type);
+                if !is_overload {
+                    self.check_missing_return(body, &cfg, returns, declared_return_type);
+                }
+            }
+        }
+        self.leave_scope();
+    }
+
+    /// Builds the control flow graph of a scope and reports unreachable code and uses
+    /// of local variables that are not assigned on every path.
+    fn check_control_flow<'b>(
+        &mut self,
+        body: &'b [Statement],
+        parameters: &[&str],
+    ) -> ControlFlowGraph<'b> {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let cfg = ControlFlowGraph::build(body, &|expr| {
+            self.type_evaluator
+                .is_no_return_call(expr, &symbol_table, self.current_scope)
+        });
+        if symbol_table.is_pyi_file() {
+            return cfg;
+        }
+        self.report_unreachable_code(&cfg, body);
+        for unbound in cfg.unbound_names(parameters) {
+            let name = unbound.name;
+            let (rule, message) = match unbound.boundness {
+                Boundness::Unbound => {
+                    (Rule::UnboundVariable, format!("\"{}\" is unbound", name.id))
+                }
+                Boundness::PossiblyUnbound => (
+                    Rule::PossiblyUnbound,
+                    format!("\"{}\" is possibly unbound", name.id),
+                ),
+            };
+            self.make_diagnostic(rule, message, name.node.start, name.node.end);
+        }
+        for name in cfg.free_names() {
+            if self
+                .type_evaluator
+                .is_name_defined(&name.id, &symbol_table, self.current_scope)
+            {
+                continue;
+            }
+            let candidates = self
+                .type_evaluator
+                .get_visible_names(&symbol_table, self.current_scope);
+            self.make_diagnostic_with_suggestion(
+                Rule::UndefinedVariable,
+                format!("\"{}\" is not defined", name.id),
+                did_you_mean(&name.id, &candidates),
+                name.node.start,
+                name.node.end,
+            );
+        }
+        cfg
+    }
+
+    /// Reports an attribute that is accessed on a class, instance or module that does not
+    /// have it.
+    fn check_attribute_access(&mut self, a: &Attribute) {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        if symbol_table.is_pyi_file() {
+            return;
+        }
+        let Ok(receiver) =
+            self.type_evaluator
+                .get_type(&a.value, &symbol_table, self.current_scope)
+        else {
+            return;
+        };
+        if self.type_evaluator.has_attribute(&receiver, &a.attr) != Some(false) {
+            return;
+        }
+        let owner = match &receiver {
+            PythonType::Module(module) => {
+                let module_symbol_table =
+                    self.build_manager.get_symbol_table_by_id(&module.module_id);
+                format!("Module \"{}\"", module_name(&module_symbol_table.file_path))
+            }
+            PythonType::Class(c) => format!("\"{}\"", c.details.name),
+            PythonType::Instance(i) => format!("\"{}\"", i.class_type.details.name),
+            PythonType::LiteralValue(KnownValue {
+                literal_value: LiteralValue::Enum(member),
+            }) => format!("\"{}\"", member.class.details.name),
+            receiver => format!("\"{receiver}\""),
+        };
+        let candidates = self.type_evaluator.get_attribute_names(&receiver);
+        self.make_diagnostic_with_suggestion(
+            Rule::AttributeAccessIssue,
+            format!("{owner} has no attribute \"{}\"", a.attr),
+            did_you_mean(&a.attr, &candidates),
+            a.node.end - a.attr.len() as u32,
+            a.node.end,
+        );
+    }
+
+    /// Visits a comprehension in its own scope. The iterable of the first clause is
+    /// evaluated in the enclosing scope.
+    fn visit_comprehension(
+        &mut self,
+        start: u32,
+        generators: &[Comprehension],
+        elements: &[&Expression],
+    ) {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let enclosing_scope = self.current_scope;
+        let comprehension_scope = symbol_table
+            .get_comprehension_scope(start)
+            .unwrap_or(enclosing_scope);
+        for comprehension in generators {
+            self.visit_expr(&comprehension.iter);
+            self.current_scope = comprehension_scope;
+            self.visit_target(&comprehension.target);
+            for if_expr in &comprehension.ifs {
+                self.visit_expr(if_expr);
+            }
+        }
+        for element in elements {
+            self.visit_expr(element);
+        }
+        self.current_scope = enclosing_scope;
+    }
+
+    /// Visits the target of an assignment. Assigning to an attribute declares it, so
+    /// only the receivers of attributes are checked.
+    fn visit_target(&mut self, target: &Expression) {
+        match target {
+            Expression::Attribute(a) => {
+                self.infer_expr_type(target);
+                self.visit_expr(&a.value);
+            }
+            Expression::Tuple(t) => {
+                self.infer_expr_type(target);
+                for element in &t.elements {
+                    self.visit_target(element);
+                }
+            }
+            Expression::List(l) => {
+                self.infer_expr_type(target);
+                for element in &l.elements {
+                    self.visit_target(element);
+                }
+            }
+            Expression::Starred(s) => {
+                self.infer_expr_type(target);
+                self.visit_target(&s.value);
+            }
+            _ => self.visit_expr(target),
+        }
+    }
+
+    /// Reports the first unreachable statement of every statement list up to the end
+    /// of the list.
+    fn report_unreachable_code(&mut self, cfg: &ControlFlowGraph, body: &[Statement]) {
+        for stmt in body {
+            if !cfg.is_reachable(stmt) {
+                let end = body
+                    .last()
+                    .map_or(stmt.get_node().end, |s| s.get_node().end);
+                self.make_diagnostic(
+                    Rule::UnreachableCode,
+                    "Code is unreachable".to_string(),
+                    stmt.get_node().start,
+                    end,
+                );
+                return;
+            }
+            match stmt {
+                Statement::IfStatement(i) => {
+                    self.report_unreachable_code(cfg, &i.body);
+                    self.report_unreachable_code(cfg, &i.orelse);
+                }
+                Statement::WhileStatement(w) => {
+                    self.report_unreachable_code(cfg, &w.body);
+                    self.report_unreachable_code(cfg, &w.orelse);
+                }
+                Statement::ForStatement(f) => {
+                    self.report_unreachable_code(cfg, &f.body);
+                    self.report_unreachable_code(cfg, &f.orelse);
+                }
+                Statement::AsyncForStatement(f) => {
+                    self.report_unreachable_code(cfg, &f.body);
+                    self.report_unreachable_code(cfg, &f.orelse);
+                }
+                Statement::WithStatement(w) => self.report_unreachable_code(cfg, &w.body),
+                Statement::AsyncWithStatement(w) => self.report_unreachable_code(cfg, &w.body),
+                Statement::TryStatement(t) => {
+                    self.report_unreachable_code(cfg, &t.body);
+                    for handler in &t.handlers {
+                        self.report_unreachable_code(cfg, &handler.body);
+                    }
+                    self.report_unreachable_code(cfg, &t.orelse);
+                    self.report_unreachable_code(cfg, &t.finalbody);
+                }
+                Statement::TryStarStatement(t) => {
+                    self.report_unreachable_code(cfg, &t.body);
+                    for handler in &t.handlers {
+                        self.report_unreachable_code(cfg, &handler.body);
+                    }
+                    self.report_unreachable_code(cfg, &t.orelse);
+                    self.report_unreachable_code(cfg, &t.finalbody);
+                }
+                Statement::MatchStmt(m) => {
+                    for case in &m.cases {
+                        self.report_unreachable_code(cfg, &case.body);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Binds the arguments of a call to the parameters of the called function and
+    /// reports the arguments that cannot be bound or have the wrong type.
+    /// https://docs.python.org/3/reference/expressions.html#calls
+    fn check_call_arguments(&mut self, call: &Call) {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let Some(signature) =
+            self.type_evaluator
+                .get_call_signature(call, &symbol_table, self.current_scope)
+        else {
+            return;
+        };
+        // The signature keeps its overloads when none of them accepts the arguments
+        if signature.is_overloaded() {
+            let candidates: Vec<String> = signature
+                .overloads
+                .iter()
+                .map(|overload| format!("\n  {overload}"))
+                .collect();
+            self.make_diagnostic(
+                Rule::CallIssue,
+                format!(
+                    "No overloads for \"{}\" match the provided arguments, the candidates are:{}",
+                    signature.name,
+                    candidates.concat()
+                ),
+                call.node.start,
+                call.node.end,
+            );
+            return;
+        }
+        let parameters: Vec<&CallableParameter> =
+            signature.signature.iter().map(|p| p.parameter()).collect();
+        let mut assigned = vec![false; parameters.len()];
+        let positional: Vec<usize> = signature
+            .signature
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| {
+                matches!(
+                    p,
+                    CallableArgs::PositionalOnly(_) | CallableArgs::Positional(_)
+                )
+            })
+            .map(|(i, _)| i)
+            .collect();
+        let var_args = signature
+            .signature
+            .iter()
+            .position(|p| matches!(p, CallableArgs::Args(_)));
+        let var_kwargs = signature
+            .signature
+            .iter()
+            .position(|p| matches!(p, CallableArgs::KwArgs(_)));
+        // Unpacked arguments can fill any number of parameters
+        let mut unpacked_positional = false;
+        let mut unpacked_keywords = false;
+
+        let mut next_positional = positional.iter();
+        for arg in call.args.iter() {
+            if matches!(arg, Expression::Starred(_)) {
+                unpacked_positional = true;
+                break;
+            }
+            match next_positional.next() {
+                Some(&index) => {
+                    assigned[index] = true;
+                    self.check_argument_type(arg, parameters[index]);
+                }
+                None => match var_args {
+                    Some(index) => self.check_argument_type(arg, parameters[index]),
+                    None => {
+                        self.make_diagnostic(
+                            Rule::CallIssue,
+                            format!(
+                                "Expected {} positional argument{}",
+                                positional.len(),
+                                if positional.len() == 1 { "" } else { "s" }
+                            ),
+                            arg.get_node().start,
+                            arg.get_node().end,
+                        );
+                        break;
+                    }
+                },
+            }
+        }
+
+        for keyword in call.keywords.iter() {
+            let Some(name) = &keyword.arg else {
+                unpacked_keywords = true;
+                continue;
+            };
+            let parameter = signature.signature.iter().position(|p| {
+                matches!(p, CallableArgs::Positional(_) | CallableArgs::Keyword(_))
+                    && p.parameter().name == *name
+            });
+            match parameter {
+                Some(index) if assigned[index] => self.make_diagnostic(
+                    Rule::CallIssue,
+                    format!("Multiple values for parameter \"{name}\""),
+                    keyword.node.start,
+                    keyword.node.end,
+                ),
+                Some(index) => {
+                    assigned[index] = true;
+                    self.check_argument_type(&keyword.value, parameters[index]);
+                }
+                None => match var_kwargs {
+                    Some(index) => self.check_argument_type(&keyword.value, parameters[index]),
+                    None => self.make_diagnostic(
+                        Rule::CallIssue,
+                        format!("No parameter named \"{name}\""),
+                        keyword.node.start,
+                        keyword.node.end,
+                    ),
+                },
+            }
+        }
+
+        let missing: Vec<String> = signature
+            .signature
+            .iter()
+            .enumerate()
+            .filter(|(index, p)| {
+                let filled_by_unpacking = match p {
+                    CallableArgs::PositionalOnly(_) => unpacked_positional,
+                    CallableArgs::Positional(_) => unpacked_positional || unpacked_keywords,
+                    CallableArgs::Keyword(_) => unpacked_keywords,
+                    CallableArgs::Args(_) | CallableArgs::KwArgs(_) => true,
+                };
+                !assigned[*index] && !p.parameter().has_default && !filled_by_unpacking
+            })
+            .map(|(_, p)| format!("\"{}\"", p.parameter().name))
+            .collect();
+        if !missing.is_empty() {
+            let body = if missing.len() == 1 {
+                format!("Argument missing for parameter {}", missing[0])
+            } else {
+                format!("Arguments missing for parameters {}", missing.join(", "))
+            };
+            self.make_diagnostic(Rule::CallIssue, body, call.node.start, call.node.end);
+        }
+    }
+
+    /// Reports protocols that are passed to `isinstance` or `issubclass` without being
+    /// runtime checkable.
+    /// https://typing.readthedocs.io/en/latest/spec/protocol.html#runtime-checkable-decorator-and-narrowing-types-by-isinstance
+    fn check_instance_check(&mut self, call: &Call) {
+        let Expression::Name(func) = &call.func else {
+            return;
+        };
+        if !matches!(func.id.as_str(), "isinstance" | "issubclass") {
+            return;
+        }
+        let Some(class_info) = call.args.get(1) else {
+            return;
+        };
+        let classes = match class_info {
+            Expression::Tuple(t) => t.elements.iter().collect(),
+            class => vec![class],
+        };
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        for class in classes {
+            let class_type = self
+                .type_evaluator
+                .get_type(class, &symbol_table, self.current_scope)
+                .unwrap_or(PythonType::Unknown);
+            let PythonType::Class(c) = &class_type else {
+                continue;
+            };
+            if self.type_evaluator.is_protocol_class(&class_type)
+                && !c.details.is_runtime_checkable()
+            {
+                self.make_diagnostic(
+                    Rule::GeneralTypeIssue,
+                    "Instance or class checks can only be used with @runtime_checkable protocol classes"
+                        .to_string(),
+                    class.get_node().start,
+                    class.get_node().end,
+                );
+            }
+        }
+    }
+
+    /// Python rejects classes whose bases cannot be linearized into a method resolution
+    /// order, e.g. `class C(A, B)` when B derives from A.
+    fn check_mro(&mut self, c: &ClassDef) {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let name = intern_lookup(c.name);
+        let PythonType::Class(class) =
+            self.type_evaluator
+                .get_name_type(name, None, &symbol_table, self.current_scope)
+        else {
+            return;
+        };
+        if !self.type_evaluator.has_consistent_mro(&class) {
+            let start = self.class_name_start(c);
+            self.make_diagnostic(
+                Rule::GeneralTypeIssue,
+                "Cannot create consistent method ordering".to_string(),
+                start,
+                start + name.len() as u32,
+            );
+        }
+    }
+
+    /// The position of the name of a class definition. The node of a decorated class
+    /// starts at its first decorator.
+    fn class_name_start(&self, c: &ClassDef) -> u32 {
+        let file = self.build_manager.files.get(&self.id).unwrap();
+        let after_decorators = c
+            .decorator_list
+            .last()
+            .map_or(c.node.start, |d| d.get_node().end);
+        let text = &file.source[after_decorators as usize..c.node.end as usize];
+        let Some(keyword) = text.find("class") else {
+            return c.node.start + 6;
+        };
+        let name = text[keyword + 5..].trim_start();
+        (c.node.end as usize - name.len()) as u32
+    }
+
+    /// Reports dataclass fields that `__init__` cannot take in the order they are declared
+    /// and slots that are declared by both the class and the dataclass decorator.
+    fn check_dataclass(&mut self, c: &ClassDef) {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let name = intern_lookup(c.name);
+        let PythonType::Class(class) =
+            self.type_evaluator
+                .get_name_type(name, None, &symbol_table, self.current_scope)
+        else {
+            return;
+        };
+        let Some(options) = self.type_evaluator.get_dataclass_options(&class) else {
+            return;
+        };
+        let mut after_default = false;
+        for field in self
+            .type_evaluator
+            .get_dataclass_fields(&class)
+            .iter()
+            .filter(|field| field.init && !field.kw_only)
+        {
+            if field.has_default {
+                after_default = true;
+            } else if after_default && field.declared_in == class.details.qual_name {
+                self.make_diagnostic(
+                    Rule::GeneralTypeIssue,
+                    "Fields without default values cannot appear after fields with default values"
+                        .to_string(),
+                    field.node.start,
+                    field.node.end,
+                );
+            }
+        }
+        if !options.slots {
+            return;
+        }
+        if let Some(slots) =
+            symbol_table.lookup_attribute("__slots__", class.details.class_scope_id)
+        {
+            let node = slots.last_declaration().declaration_path().node;
+            self.make_diagnostic(
+                Rule::GeneralTypeIssue,
+                "__slots__ is already defined in class".to_string(),
+                node.start,
+                node.end,
+            );
+        }
+    }
+
+    /// Reports assignments to the attributes of frozen dataclass instances and to the
+    /// read-only items of typed dicts.
+    fn check_assignment_target(&mut self, target: &Expression) {
+        let value = match target {
+            Expression::Attribute(a) => &a.value,
+            Expression::Subscript(s) => &s.value,
+            _ => return,
+        };
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let class = match self
+            .type_evaluator
+            .get_type(value, &symbol_table, self.current_scope)
+        {
+            Ok(PythonType::Class(c)) => c,
+            Ok(PythonType::Instance(i)) => i.class_type,
+            _ => return,
+        };
+        let message = match target {
+            Expression::Attribute(a) if self.type_evaluator.is_frozen_dataclass(&class) => {
+                format!(
+                    "Cannot assign to attribute \"{}\" of frozen dataclass \"{}\"",
+                    a.attr, class.details.name
+                )
+            }
+            Expression::Subscript(s) => {
+                let Expression::Constant(key) = &s.slice else {
+                    return;
+                };
+                let ConstantValue::Str(key) = &key.value else {
+                    return;
+                };
+                let read_only = self
+                    .type_evaluator
+                    .get_typed_dict_fields(&class)
+                    .is_some_and(|fields| fields.iter().any(|f| f.name == *key && f.read_only));
+                if !read_only {
+                    return;
+                }
+                format!("\"{key}\" is a read-only key in \"{}\"", class.details.name)
+            }
+            _ => return,
+        };
+        self.make_diagnostic(
+            Rule::GeneralTypeIssue,
+            message,
+            target.get_node().start,
+            target.get_node().end,
+        );
+    }
+
+    /// Checks a value that is assigned to a property against the type that its setter
+    /// accepts.
+    fn check_property_assignment(&mut self, target: &Expression, value: &Expression) {
+        let Expression::Attribute(attribute) = target else {
+            return;
+        };
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let (class, arguments) =
+            match self
+                .type_evaluator
+                .get_type(&attribute.value, &symbol_table, self.current_scope)
+            {
+                Ok(PythonType::Class(c)) => {
+                    let arguments = c.specialized.clone();
+                    (c, arguments)
+                }
+                Ok(PythonType::Instance(i)) => (i.class_type, i.specialized_type_parameters),
+                _ => return,
+            };
+        let Some(setter_type) =
+            self.type_evaluator
+                .get_property_setter_type(&class, &arguments, &attribute.attr)
+        else {
+            return;
+        };
+        let value_type = self.get_value_type(value);
+        if !self.type_evaluator.is_assignable(&value_type, &setter_type) {
+            self.make_diagnostic(
+                Rule::AssignmentTypeMismatch,
+                format!(
+                    "Type \"{value_type}\" is not assignable to attribute \"{}\" of type \"{setter_type}\"{}",
+                    attribute.attr,
+                    self.describe_mismatch(&value_type, &setter_type)
+                ),
+                value.get_node().start,
+                value.get_node().end,
+            );
+        }
+    }
+
+    /// Reports values that cannot be unpacked to a tuple or list target, because they are
+    /// not iterable or they are tuples that do not have as many elements as there are
+    /// targets. Nested targets are checked with the types of the values that they get.
+    fn check_unpacking(&mut self, target: &Expression, value_type: &PythonType) {
+        let targets = match target {
+            Expression::Tuple(t) => &t.elements,
+            Expression::List(l) => &l.elements,
+            _ => return,
+        };
+        let star = targets
+            .iter()
+            .position(|t| matches!(t, Expression::Starred(_)));
+        let elements = self.type_evaluator.get_tuple_elements(value_type);
+        if elements.is_none() && !self.is_iterable(value_type) {
+            self.make_diagnostic(
+                Rule::GeneralTypeIssue,
+                format!("\"{value_type}\" is not iterable and cannot be unpacked"),
+                target.get_node().start,
+                target.get_node().end,
+            );
+            return;
+        }
+        let Some(target_types) =
+            self.type_evaluator
+                .get_unpacked_target_types(value_type, targets.len(), star)
+        else {
+            let elements = elements.unwrap_or_default();
+            let received = match elements.iter().filter(|e| !e.is_unbounded()).count() {
+                fixed if fixed < elements.len() => format!("at least {fixed}"),
+                fixed => fixed.to_string(),
+            };
+            let expected = match star {
+                Some(_) => format!("at least {}", targets.len() - 1),
+                None => targets.len().to_string(),
+            };
+            self.make_diagnostic(
+                Rule::AssignmentTypeMismatch,
+                format!("Tuple size mismatch; expected {expected} but received {received}"),
+                target.get_node().start,
+                target.get_node().end,
+            );
+            return;
+        };
+        for (target, target_type) in targets.iter().zip(target_types.iter()) {
+            self.check_unpacking(target, target_type);
+        }
+    }
+
+    /// Whether a value can be iterated, with `__iter__` or the `__getitem__` method of
+    /// the old sequence protocol. Values of unknown types are assumed to be iterable.
+    fn is_iterable(&self, value_type: &PythonType) -> bool {
+        if *value_type == PythonType::None {
+            return false;
+        }
+        ["__iter__", "__getitem__"]
+            .iter()
+            .any(|method| self.type_evaluator.has_attribute(value_type, method) != Some(false))
+    }
+
+    /// Checks the items of a dict literal that is assigned to a typed dict. Returns false
+    /// when the value is not a dict literal or the expected type is not a typed dict.
+    fn check_typed_dict_literal(&mut self, value: &Expression, expected: &PythonType) -> bool {
+        let Expression::Dict(dict) = value else {
+            return false;
+        };
+        let class = match expected {
+            PythonType::Class(c) => c,
+            PythonType::Instance(i) => &i.class_type,
+            _ => return false,
+        };
+        let Some(fields) = self.type_evaluator.get_typed_dict_fields(class) else {
+            return false;
+        };
+        let mut keys = vec![];
+        for (key, item) in dict.keys.iter().zip(dict.values.iter()) {
+            let Expression::Constant(constant) = key else {
+                continue;
+            };
+            let ConstantValue::Str(name) = &constant.value else {
+                continue;
+            };
+            keys.push(name.as_str());
+            let Some(field) = fields.iter().find(|f| f.name == *name) else {
+                self.make_diagnostic(
+                    Rule::AssignmentTypeMismatch,
+                    format!(
+                        "\"{name}\" is an undefined item in \"{}\"",
+                        class.details.name
+                    ),
+                    key.get_node().start,
+                    key.get_node().end,
+                );
+                continue;
+            };
+            let item_type = self.get_value_type(item);
+            if !self
+                .type_evaluator
+                .is_assignable(&item_type, &field.python_type)
+            {
+                self.make_diagnostic(
+                    Rule::AssignmentTypeMismatch,
+                    format!(
+                        "Type \"{item_type}\" is not assignable to item \"{name}\" of type \"{}\"",
+                        field.python_type
+                    ),
+                    item.get_node().start,
+                    item.get_node().end,
+                );
+            }
+        }
+        let missing: Vec<String> = fields
+            .iter()
+            .filter(|f| f.required && !keys.contains(&f.name.as_str()))
+            .map(|f| format!("\"{}\"", f.name))
+            .collect();
+        if !missing.is_empty() && dict.keys.len() == dict.values.len() {
+            self.make_diagnostic(
+                Rule::AssignmentTypeMismatch,
+                format!(
+                    "{} {} required in \"{}\"",
+                    missing.join(", "),
+                    if missing.len() == 1 { "is" } else { "are" },
+                    class.details.name
+                ),
+                value.get_node().start,
+                value.get_node().end,
+            );
+        }
+        true
+    }
+
+    /// The type of an assigned value. Constants are typed as literals so that they can be
+    /// assigned to literal types.
+    fn get_value_type(&self, value: &Expression) -> PythonType {
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        match self.type_evaluator.get_literal_type_of_constant(value) {
+            Some(literal_type) => literal_type,
+            None => self
+                .type_evaluator
+                .get_type(value, &symbol_table, self.current_scope)
+                .unwrap_or(PythonType::Unknown),
+        }
+    }
+
+    /// Reports `await` outside of async functions and awaited values that are not
+    /// awaitable.
+    fn check_await(&mut self, a: &Await) {
+        if !self.in_async_function {
+            self.make_diagnostic(
+                Rule::GeneralTypeIssue,
+                "\"await\" allowed only within async function".to_string(),
+                a.node.start,
+                a.node.end,
+            );
+        }
+        let awaited = self.get_value_type(&a.value);
+        if self.type_evaluator.get_awaited_type(&awaited).is_none() {
+            self.make_diagnostic(
+                Rule::GeneralTypeIssue,
+                format!("\"{awaited}\" is not awaitable"),
+                a.value.get_node().start,
+                a.value.get_node().end,
+            );
+        }
+    }
+
+    /// Reports calls of async functions whose coroutine is discarded without awaiting it,
+    /// so the body of the function never runs.
+    fn check_unused_coroutine(&mut self, e: &Expression) {
+        let Expression::Call(call) = e else {
+            return;
+        };
+        if let PythonType::Coroutine(_) = self.get_value_type(e) {
+            self.make_diagnostic(
+                Rule::UnusedCoroutine,
+                "Result of async function call is not used; use \"await\" or assign result to variable".to_string(),
+                call.node.start,
+                call.node.end,
+            );
+        }
+    }
+
+    /// Reports a value that is used as the iterable of `async for` or the context manager
+    /// of `async with` when it does not have the method that the statement calls.
+    fn check_async_protocol(&mut self, value: &Expression, method: &str, statement: &str) {
+        let value_type = self.get_value_type(value);
+        if self.type_evaluator.has_attribute(&value_type, method) == Some(false) {
+            self.make_diagnostic(
+                Rule::GeneralTypeIssue,
+                format!(
+                    "\"{value_type}\" cannot be used with \"{statement}\" because it does not implement \"{method}\""
+                ),
+                value.get_node().start,
+                value.get_node().end,
+            );
+        }
+    }
+
+    fn check_argument_type(&mut self, arg: &Expression, parameter: &CallableParameter) {
+        if self.check_typed_dict_literal(arg, &parameter.python_type) {
+            return;
+        }
+        let arg_type = self.get_value_type(arg);
+        if !self
+            .type_evaluator
+            .is_assignable(&arg_type, &parameter.python_type)
+        {
+            self.make_diagnostic(
+                Rule::ArgumentType,
+                format!(
+                    "Argument of type \"{arg_type}\" is not assignable to parameter \"{}\" of type \"{}\"{}",
+                    parameter.name,
+                    parameter.python_type,
+                    self.describe_mismatch(&arg_type, &parameter.python_type)
+                ),
+                arg.get_node().start,
+                arg.get_node().end,
+            );
+        }
+    }
+
+    pub fn add_syntax_error(&mut self, error: &ParsingError) {
+        let ParsingError::InvalidSyntax { msg, span, .. } = error;
+        let start = span.0 as u32;
+        self.make_diagnostic(
+            Rule::InvalidSyntax,
+            msg.clone(),
+            start,
+            start + span.1 as u32,
+        );
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    pub fn dump_diagnostics(&self) -> String {
+        let mut str = String::new();
+        for diagnostic in self.diagnostics.iter() {
+            str.push_str(&format!("{diagnostic}\n"));
+        }
+        str
+    }
+
     fn enter_scope(&mut self, pos: u32) {
         let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
-        self.prev_scope = self.current_scope;
+        self.prev_scopes.push(self.current_scope);
         self.current_scope = symbol_table.get_scope(pos);
     }
 
     fn leave_scope(&mut self) {
-        self.current_scope = self.prev_scope;
+        self.current_scope = self.prev_scopes.pop().unwrap_or(0);
     }
 
     pub fn dump_types(&self) -> String {
@@ -142,7 +1267,10 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
     fn visit_stmt(&mut self, s: &Statement) {
         // map all statements and call visit
         match s {
-            Statement::ExpressionStatement(e) => self.visit_expr(e),
+            Statement::ExpressionStatement(e) => {
+                self.visit_expr(e);
+                self.check_unused_coroutine(e);
+            }
             Statement::Import(i) => self.visit_import(i),
             Statement::ImportFrom(i) => self.visit_import_from(i),
             Statement::AssignStatement(a) => self.visit_assign(a),
@@ -170,6 +1298,7 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
             Statement::AsyncWithStatement(w) => self.visit_async_with(w),
             Statement::AsyncFunctionDef(f) => self.visit_async_function_def(f),
             Statement::TypeAlias(a) => self.visit_type_alias(a),
+            Statement::Invalid(i) => self.visit_invalid(i),
         }
     }
 
@@ -250,7 +1379,24 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
 
     fn visit_for(&mut self, f: &parser::ast::For) {
         self.visit_expr(&f.iter);
-        self.visit_expr(&f.target);
+        self.visit_target(&f.target);
+        let item_type = self
+            .type_evaluator
+            .get_iterated_type(&self.get_value_type(&f.iter));
+        self.check_unpacking(&f.target, &item_type);
+        for stmt in &f.body {
+            self.visit_stmt(stmt);
+        }
+    }
+
+    fn visit_async_for(&mut self, f: &parser::ast::AsyncFor) {
+        self.visit_expr(&f.iter);
+        self.check_async_protocol(&f.iter, "__aiter__", "async for");
+        self.visit_target(&f.target);
+        let item_type = self
+            .type_evaluator
+            .get_async_iterated_type(&self.get_value_type(&f.iter));
+        self.check_unpacking(&f.target, &item_type);
         for stmt in &f.body {
             self.visit_stmt(stmt);
         }
@@ -262,9 +1408,29 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
         }
         for with_items in &w.items {
             self.visit_expr(&with_items.context_expr);
-            match &with_items.optional_vars {
-                Some(items) => self.visit_expr(items),
-                None => (),
+            if let Some(items) = &with_items.optional_vars {
+                self.visit_target(items);
+                let context_manager = self.get_value_type(&with_items.context_expr);
+                let entered = self
+                    .type_evaluator
+                    .get_entered_type(&context_manager, false);
+                self.check_unpacking(items, &entered);
+            }
+        }
+    }
+
+    fn visit_async_with(&mut self, w: &parser::ast::AsyncWith) {
+        for stmt in &w.body {
+            self.visit_stmt(stmt);
+        }
+        for with_items in &w.items {
+            self.visit_expr(&with_items.context_expr);
+            self.check_async_protocol(&with_items.context_expr, "__aenter__", "async with");
+            if let Some(items) = &with_items.optional_vars {
+                self.visit_target(items);
+                let context_manager = self.get_value_type(&with_items.context_expr);
+                let entered = self.type_evaluator.get_entered_type(&context_manager, true);
+                self.check_unpacking(items, &entered);
             }
         }
     }
@@ -310,41 +1476,38 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
     }
 
     fn visit_function_def(&mut self, f: &Arc<parser::ast::FunctionDef>) {
-        let file = &self.build_manager.files.get(&self.id).unwrap();
-        self.enter_scope(f.node.start);
         let name = intern_lookup(f.name);
-        self.infer_name_type(name, f.node.start + 4, f.node.start + 4 + name.len() as u32);
-        if let Some(ret_type) = &f.returns {
-            self.visit_expr(ret_type);
-        }
-        for stmt in &f.body {
-            self.visit_stmt(stmt);
-        }
-        for (arg, _index) in f.args.args.iter().zip(0..) {
-            if let Some(annotation) = &arg.annotation {
-                self.infer_annotation_type(annotation);
-            }
-            self.infer_name_type(&arg.arg, arg.node.start, arg.node.end)
-        }
-
-        self.leave_scope();
+        self.check_function(
+            f.node,
+            name,
+            f.node.start + 4,
+            &f.args,
+            f.returns.as_ref(),
+            &f.body,
+            false,
+        );
     }
 
     fn visit_async_function_def(&mut self, f: &Arc<parser::ast::AsyncFunctionDef>) {
-        let file = &self.build_manager.files.get(&self.id).unwrap();
-        self.enter_scope(f.node.start);
         let name = intern_lookup(f.name);
-        self.infer_name_type(name, f.node.start + 9, f.node.start + 9 + name.len() as u32);
-        for stmt in &f.body {
-            self.visit_stmt(stmt);
-        }
-        self.leave_scope();
+        self.check_function(
+            f.node,
+            name,
+            f.node.start + 10,
+            &f.args,
+            f.returns.as_ref(),
+            &f.body,
+            true,
+        );
     }
 
     fn visit_class_def(&mut self, c: &Arc<parser::ast::ClassDef>) {
         let file = &self.build_manager.files.get(&self.id).unwrap();
         let name = intern_lookup(c.name);
-        self.infer_name_type(name, c.node.start + 6, c.node.start + 6 + name.len() as u32);
+        let start = self.class_name_start(c);
+        self.infer_name_type(name, start, start + name.len() as u32);
+        self.check_dataclass(c);
+        self.check_mro(c);
 
         self.enter_scope(c.node.start);
         for base in &c.bases {
@@ -353,9 +1516,12 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
         for dec in &c.decorator_list {
             self.visit_expr(dec);
         }
+        let in_async_function = std::mem::replace(&mut self.in_async_function, false);
         for stmt in &c.body {
             self.visit_stmt(stmt);
         }
+        self.in_async_function = in_async_function;
+        self.check_control_flow(&c.body, &[]);
         for keyword in &c.keywords {
             self.visit_expr(&keyword.value);
         }
@@ -456,8 +1622,8 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
     }
 
     fn visit_bin_op(&mut self, b: &BinOp) {
-        let l_type = self.infer_expr_type(&b.left);
-        let r_type = self.infer_expr_type(&b.right);
+        self.visit_expr(&b.left);
+        self.visit_expr(&b.right);
     }
 
     fn visit_named_expr(&mut self, _n: &NamedExpression) {
@@ -479,49 +1645,25 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
         self.visit_expr(&_s.value);
     }
 
-    fn visit_generator(&mut self, _g: &Generator) {
-        self.visit_expr(&_g.element);
-        for comprehension in &_g.generators {
-            self.visit_expr(&comprehension.iter);
-            for if_expr in &comprehension.ifs {
-                self.visit_expr(if_expr);
-            }
-        }
+    fn visit_generator(&mut self, g: &Generator) {
+        self.visit_comprehension(g.node.start, &g.generators, &[&g.element]);
     }
 
-    fn visit_list_comp(&mut self, _l: &ListComp) {
-        self.visit_expr(&_l.element);
-        for comprehension in &_l.generators {
-            self.visit_expr(&comprehension.iter);
-            for if_expr in &comprehension.ifs {
-                self.visit_expr(if_expr);
-            }
-        }
+    fn visit_list_comp(&mut self, l: &ListComp) {
+        self.visit_comprehension(l.node.start, &l.generators, &[&l.element]);
     }
 
-    fn visit_set_comp(&mut self, _s: &SetComp) {
-        self.visit_expr(&_s.element);
-        for comprehension in &_s.generators {
-            self.visit_expr(&comprehension.iter);
-            for if_expr in &comprehension.ifs {
-                self.visit_expr(if_expr);
-            }
-        }
+    fn visit_set_comp(&mut self, s: &SetComp) {
+        self.visit_comprehension(s.node.start, &s.generators, &[&s.element]);
     }
 
-    fn visit_dict_comp(&mut self, _d: &DictComp) {
-        self.visit_expr(&_d.key);
-        self.visit_expr(&_d.value);
-        for comprehension in &_d.generators {
-            self.visit_expr(&comprehension.iter);
-            for if_expr in &comprehension.ifs {
-                self.visit_expr(if_expr);
-            }
-        }
+    fn visit_dict_comp(&mut self, d: &DictComp) {
+        self.visit_comprehension(d.node.start, &d.generators, &[&d.key, &d.value]);
     }
 
     fn visit_attribute(&mut self, a: &Attribute) {
-        self.infer_expr_type(&a.value);
+        self.visit_expr(&a.value);
+        self.check_attribute_access(a);
     }
 
     fn visit_subscript(&mut self, _s: &Subscript) {
@@ -542,17 +1684,20 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
     }
 
     fn visit_call(&mut self, c: &Call) {
-        self.infer_expr_type(&c.func);
+        self.visit_expr(&c.func);
         for arg in &c.args {
             self.visit_expr(arg);
         }
         for keyword in &c.keywords {
             self.visit_expr(&keyword.value);
         }
+        self.check_call_arguments(c);
+        self.check_instance_check(c);
     }
 
     fn visit_await(&mut self, _a: &Await) {
         self.visit_expr(&_a.value);
+        self.check_await(_a);
     }
 
     fn visit_compare(&mut self, _c: &Compare) {
@@ -563,7 +1708,9 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
     }
 
     fn visit_lambda(&mut self, _l: &Lambda) {
+        let in_async_function = std::mem::replace(&mut self.in_async_function, false);
         self.visit_expr(&_l.body);
+        self.in_async_function = in_async_function;
         // todo: this can share a visit args with functions
     }
 
@@ -587,13 +1734,22 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
 
     fn visit_assign(&mut self, a: &Assign) {
         self.visit_expr(&a.value);
+        let symbol_table = self.build_manager.get_symbol_table_by_id(&self.id);
+        let value_type = self
+            .type_evaluator
+            .get_type(&a.value, &symbol_table, self.current_scope)
+            .unwrap_or(PythonType::Unknown);
         for target in &a.targets {
-            self.visit_expr(target);
+            self.visit_target(target);
+            self.check_assignment_target(target);
+            self.check_property_assignment(target, &a.value);
+            self.check_unpacking(target, &value_type);
         }
     }
     fn visit_ann_assign(&mut self, _a: &AnnAssign) {
         if let Some(value) = &_a.value {
             self.visit_expr(value);
+            self.check_assignment(value, &_a.annotation);
         }
         self.infer_expr_type(&_a.target);
     }
@@ -601,6 +1757,7 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
     fn visit_aug_assign(&mut self, _a: &AugAssign) {
         self.visit_expr(&_a.value);
         self.visit_expr(&_a.target);
+        self.check_assignment_target(&_a.target);
     }
 
     fn visit_assert(&mut self, _a: &Assert) {
@@ -648,6 +1805,89 @@ impl<'a> TraversalVisitor for TypeChecker<'a> {
     fn visit_nonlocal(&mut self, _n: &Nonlocal) {}
 }
 
+/// A body that only contains a docstring and `...` declares a function without
+/// implementing it, e.g. in protocols and abstract methods.
+fn is_stub_body(body: &[Statement]) -> bool {
+    body.iter().all(|stmt| match stmt {
+        Statement::ExpressionStatement(e) => matches!(
+            e.as_ref(),
+            Expression::Constant(c)
+                if matches!(c.value, ConstantValue::Ellipsis | ConstantValue::Str(_))
+        ),
+        _ => false,
+    })
+}
+
+/// Names of the parameters of a function, which are bound when its body starts.
+fn parameter_names(args: &Arguments) -> Vec<&str> {
+    parameters(args).map(|arg| arg.arg.as_str()).collect()
+}
+
+/// All the parameters of a function, including `*args` and `**kwargs`.
+fn parameters(args: &Arguments) -> impl Iterator<Item = &Arg> {
+    args.posonlyargs
+        .iter()
+        .chain(args.args.iter())
+        .chain(args.vararg.iter())
+        .chain(args.kwonlyargs.iter())
+        .chain(args.kwarg.iter())
+}
+
+/// The name of a module, packages are named after their directory.
+fn module_name(path: &std::path::Path) -> String {
+    let path = match path.file_stem() {
+        Some(stem) if stem == "__init__" => path.parent().unwrap_or(path),
+        _ => path,
+    };
+    path.file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().to_string())
+}
+
+/// Suggests the candidate that is closest to a name that is not found, when it is close
+/// enough to be a typo.
+fn did_you_mean(name: &str, candidates: &[String]) -> Option<String> {
+    let length = name.chars().count();
+    let max_distance = (length / 3).max(1);
+    candidates
+        .iter()
+        .filter(|candidate| {
+            candidate.as_str() != name && candidate.starts_with('_') == name.starts_with('_')
+        })
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance && *distance < length)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| format!("Did you mean \"{candidate}\"?"))
+}
+
+/// The number of characters that have to be inserted, deleted or substituted, or pairs of
+/// adjacent characters that have to be swapped, to turn one string into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // distances[i][j] is the distance between the first i characters of a and the first
+    // j characters of b
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = substitution
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs, path::PathBuf};
@@ -666,6 +1906,26 @@ mod tests {
         checker.dump_types()
     }
 
+    fn snapshot_type_checker_diagnostics(path: PathBuf) -> String {
+        let settings = Settings::test_settings();
+        let manager = BuildManager::new(settings);
+        let root = &PathBuf::from("");
+        manager.build(root);
+        manager.build_one(root, &path);
+        let id = manager.paths.get(&path).unwrap();
+        let file = manager.files.get(&id).unwrap();
+        let checker = manager.type_check(&path, &file);
+        checker.dump_diagnostics()
+    }
+
+    fn content_with_line_numbers(contents: &str) -> String {
+        let mut content_with_line_numbers = String::new();
+        for (i, line) in contents.lines().enumerate() {
+            content_with_line_numbers.push_str(&format!("{}: {}\n", i + 1, line));
+        }
+        content_with_line_numbers
+    }
+
     macro_rules! type_eval_test {
         ($test_name:ident, $test_file:expr) => {
             #[test]
@@ -674,16 +1934,11 @@ mod tests {
                 let contents = fs::read_to_string(&path).unwrap();
                 let result = snapshot_type_checker_types(path);
 
-                let mut content_with_line_numbers = String::new();
-                for (i, line) in contents.lines().enumerate() {
-                    content_with_line_numbers.push_str(&format!("{}: {}\n", i + 1, line));
-                }
-
                 // TODO move this redaction setting to a central place
                 let mut settings = insta::Settings::clone_current();
                 settings.add_filter(r"module_name: .*.typeshed.", "module_name: [TYPESHED].");
                 settings.set_snapshot_path("../test_data/output/");
-                settings.set_description(content_with_line_numbers);
+                settings.set_description(content_with_line_numbers(&contents));
                 // TODO CLEAN THE classes name
                 settings.bind(|| {
                     insta::assert_snapshot!(result);
@@ -697,6 +1952,21 @@ mod tests {
         generics_basic,
         "test_data/inputs/conformance_tests/generics_basic.py"
     );
+    type_eval_test!(literal_types, "test_data/inputs/literal_types.py");
+    type_eval_test!(binary_operators, "test_data/inputs/binary_operators.py");
+    type_eval_test!(unary_and_compare, "test_data/inputs/unary_and_compare.py");
+    type_eval_test!(narrowing, "test_data/inputs/narrowing.py");
+    type_eval_test!(generics_solving, "test_data/inputs/generics_solving.py");
+    type_eval_test!(enums, "test_data/inputs/enums.py");
+    type_eval_test!(overloads, "test_data/inputs/overloads.py");
+    type_eval_test!(decorators, "test_data/inputs/decorators.py");
+    type_eval_test!(methods, "test_data/inputs/methods.py");
+    type_eval_test!(attribute_lookup, "test_data/inputs/attribute_lookup.py");
+    type_eval_test!(generators, "test_data/inputs/generators.py");
+    type_eval_test!(comprehensions, "test_data/inputs/comprehensions.py");
+    type_eval_test!(async_functions, "test_data/inputs/async_functions.py");
+    type_eval_test!(tuples, "test_data/inputs/tuples.py");
+    type_eval_test!(unpacking, "test_data/inputs/unpacking.py");
     type_eval_test!(import_star_lookup, "test_data/inputs/import_star_test/a.py");
     type_eval_test!(
         annotations_coroutine,
@@ -710,4 +1980,109 @@ mod tests {
         annotations_forward_refs,
         "test_data/inputs/conformance_tests/annotations_forward_refs.py"
     );
+
+    macro_rules! diagnostics_test {
+        ($test_name:ident, $test_file:expr) => {
+            #[test]
+            fn $test_name() {
+                let path = PathBuf::from($test_file);
+                let contents = fs::read_to_string(&path).unwrap();
+                let result = snapshot_type_checker_diagnostics(path);
+
+                let mut settings = insta::Settings::clone_current();
+                settings.set_snapshot_path("../test_data/output/");
+                settings.set_description(content_with_line_numbers(&contents));
+                settings.bind(|| {
+                    insta::assert_snapshot!(result);
+                });
+            }
+        };
+    }
+
+    diagnostics_test!(
+        diagnostics_not_callable,
+        "test_data/inputs/diagnostics/not_callable.py"
+    );
+    diagnostics_test!(
+        diagnostics_syntax_error,
+        "test_data/inputs/diagnostics/syntax_error.py"
+    );
+    diagnostics_test!(
+        diagnostics_unsupported_operator,
+        "test_data/inputs/diagnostics/unsupported_operator.py"
+    );
+    diagnostics_test!(
+        diagnostics_unsupported_comparison,
+        "test_data/inputs/diagnostics/unsupported_comparison.py"
+    );
+    diagnostics_test!(
+        diagnostics_assignment_mismatch,
+        "test_data/inputs/diagnostics/assignment_mismatch.py"
+    );
+    diagnostics_test!(
+        diagnostics_call_arguments,
+        "test_data/inputs/diagnostics/call_arguments.py"
+    );
+    diagnostics_test!(
+        diagnostics_return_type,
+        "test_data/inputs/diagnostics/return_type.py"
+    );
+    diagnostics_test!(
+        diagnostics_control_flow,
+        "test_data/inputs/diagnostics/control_flow.py"
+    );
+    diagnostics_test!(
+        diagnostics_protocols,
+        "test_data/inputs/diagnostics/protocols.py"
+    );
+    diagnostics_test!(
+        diagnostics_dataclasses,
+        "test_data/inputs/diagnostics/dataclasses.py"
+    );
+    diagnostics_test!(
+        diagnostics_named_tuples,
+        "test_data/inputs/diagnostics/named_tuples.py"
+    );
+    diagnostics_test!(
+        diagnostics_typed_dicts,
+        "test_data/inputs/diagnostics/typed_dicts.py"
+    );
+    diagnostics_test!(diagnostics_enums, "test_data/inputs/diagnostics/enums.py");
+    diagnostics_test!(
+        diagnostics_overloads,
+        "test_data/inputs/diagnostics/overloads.py"
+    );
+    diagnostics_test!(
+        diagnostics_decorators,
+        "test_data/inputs/diagnostics/decorators.py"
+    );
+    diagnostics_test!(
+        diagnostics_class_hierarchy,
+        "test_data/inputs/diagnostics/class_hierarchy.py"
+    );
+    diagnostics_test!(
+        diagnostics_unknown_names,
+        "test_data/inputs/diagnostics/unknown_names.py"
+    );
+    diagnostics_test!(
+        diagnostics_generators,
+        "test_data/inputs/diagnostics/generators.py"
+    );
+    diagnostics_test!(
+        diagnostics_async_functions,
+        "test_data/inputs/diagnostics/async_functions.py"
+    );
+    diagnostics_test!(diagnostics_tuples, "test_data/inputs/diagnostics/tuples.py");
+    diagnostics_test!(
+        diagnostics_unpacking,
+        "test_data/inputs/diagnostics/unpacking.py"
+    );
+    diagnostics_test!(
+        diagnostics_leading_function,
+        "test_data/inputs/diagnostics/leading_function.py"
+    );
+    diagnostics_test!(
+        diagnostics_type_vars,
+        "test_data/inputs/diagnostics/type_vars.py"
+    );
 }
@@ -0,0 +1,241 @@
+//! Dataclasses and classes that are created by `dataclass_transform` decorators, base
+//! classes or metaclasses.
+//!
+//! The methods of these classes are not written in the class body but synthesized from
+//! the annotated fields of the class, so they are described here and the type evaluator
+//! creates their types.
+//! https://typing.readthedocs.io/en/latest/spec/dataclasses.html
+
+use enderpy_python_parser::ast::{self, Expression, Node, Statement};
+
+use crate::types::PythonType;
+
+/// Methods that are synthesized by a dataclass unless the class defines them itself.
+pub const SYNTHESIZED_MEMBERS: [&str; 11] = [
+    "__init__",
+    "__eq__",
+    "__lt__",
+    "__le__",
+    "__gt__",
+    "__ge__",
+    "__hash__",
+    "__match_args__",
+    "__dataclass_fields__",
+    "__slots__",
+    "__replace__",
+];
+
+/// The options that are passed to the dataclass decorator or the class keywords of a
+/// dataclass transform. Transforms can change the default values of these options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataclassOptions {
+    pub init: bool,
+    pub eq: bool,
+    pub order: bool,
+    pub frozen: bool,
+    pub unsafe_hash: bool,
+    pub match_args: bool,
+    pub kw_only: bool,
+    pub slots: bool,
+    /// Names of the functions that describe fields, e.g. `field`
+    pub field_specifiers: Vec<String>,
+}
+
+impl Default for DataclassOptions {
+    fn default() -> Self {
+        Self {
+            init: true,
+            eq: true,
+            order: false,
+            frozen: false,
+            unsafe_hash: false,
+            match_args: true,
+            kw_only: false,
+            slots: false,
+            field_specifiers: vec!["field".to_string()],
+        }
+    }
+}
+
+impl DataclassOptions {
+    /// The default options of classes created by a transform, from the keywords of the
+    /// `dataclass_transform` call.
+    pub fn from_transform(keywords: &[ast::Keyword]) -> Self {
+        let mut options = Self::default();
+        for keyword in keywords {
+            match keyword.arg.as_deref() {
+                Some("eq_default") => set_bool(&mut options.eq, &keyword.value),
+                Some("order_default") => set_bool(&mut options.order, &keyword.value),
+                Some("kw_only_default") => set_bool(&mut options.kw_only, &keyword.value),
+                Some("frozen_default") => set_bool(&mut options.frozen, &keyword.value),
+                Some("field_specifiers") => {
+                    if let Expression::Tuple(t) = &keyword.value {
+                        options.field_specifiers = t.elements.iter().filter_map(name_of).collect();
+                    }
+                }
+                _ => {}
+            }
+        }
+        options
+    }
+
+    /// Overrides the options with the keywords that are passed to the decorator or the
+    /// class definition.
+    pub fn apply_keywords(&mut self, keywords: &[ast::Keyword]) {
+        for keyword in keywords {
+            let option = match keyword.arg.as_deref() {
+                Some("init") => &mut self.init,
+                Some("eq") => &mut self.eq,
+                Some("order") => &mut self.order,
+                Some("frozen") => &mut self.frozen,
+                Some("unsafe_hash") => &mut self.unsafe_hash,
+                Some("match_args") => &mut self.match_args,
+                Some("kw_only") => &mut self.kw_only,
+                Some("slots") => &mut self.slots,
+                _ => continue,
+            };
+            set_bool(option, &keyword.value);
+        }
+    }
+}
+
+/// A field of a dataclass as it is declared in the class body.
+#[derive(Debug, Clone)]
+pub struct DataclassField {
+    pub name: String,
+    /// The declared type, or the type argument of `InitVar`
+    pub python_type: PythonType,
+    pub has_default: bool,
+    /// Whether the field is a parameter of the synthesized `__init__`
+    pub init: bool,
+    pub kw_only: bool,
+    /// Init-only variables are parameters of `__init__` but not fields of the instances
+    pub init_var: bool,
+    /// The qualified name of the class that declares the field
+    pub declared_in: String,
+    pub node: Node,
+}
+
+/// How a field is declared by an annotated assignment in the class body.
+pub struct FieldDeclaration<'a> {
+    pub name: &'a str,
+    pub annotation: &'a Expression,
+    pub has_default: bool,
+    pub init: bool,
+    pub kw_only: Option<bool>,
+    pub init_var: bool,
+    pub node: Node,
+}
+
+/// The fields that are declared in the body of a dataclass, in the order of their
+/// declaration. `kw_only` is the default of the class, fields after a `KW_ONLY` marker
+/// are always keyword only.
+pub fn field_declarations<'a>(
+    body: &'a [Statement],
+    options: &DataclassOptions,
+) -> Vec<FieldDeclaration<'a>> {
+    let mut declarations = vec![];
+    let mut after_kw_only_marker = false;
+    for statement in body {
+        let Statement::AnnAssignStatement(a) = statement else {
+            continue;
+        };
+        let Expression::Name(target) = &a.target else {
+            continue;
+        };
+        if is_special_annotation(&a.annotation, "ClassVar") {
+            continue;
+        }
+        if is_special_annotation(&a.annotation, "KW_ONLY") {
+            after_kw_only_marker = true;
+            continue;
+        }
+        let (annotation, init_var) = match &a.annotation {
+            Expression::Subscript(s) if is_special_annotation(&s.value, "InitVar") => {
+                (&s.slice, true)
+            }
+            annotation => (annotation, false),
+        };
+        let mut declaration = FieldDeclaration {
+            name: &target.id,
+            annotation,
+            has_default: a.value.is_some(),
+            init: true,
+            kw_only: after_kw_only_marker.then_some(true),
+            init_var,
+            node: target.node,
+        };
+        if let Some(Expression::Call(specifier)) = &a.value {
+            if name_of(&specifier.func).is_some_and(|name| options.field_specifiers.contains(&name))
+            {
+                declaration.has_default = false;
+                for keyword in &specifier.keywords {
+                    match keyword.arg.as_deref() {
+                        Some("default" | "default_factory" | "factory") => {
+                            declaration.has_default = true
+                        }
+                        Some("init") => set_bool(&mut declaration.init, &keyword.value),
+                        Some("kw_only") => {
+                            let mut kw_only = false;
+                            set_bool(&mut kw_only, &keyword.value);
+                            declaration.kw_only = Some(kw_only);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        declarations.push(declaration);
+    }
+    declarations
+}
+
+/// Returns the callee and the keywords of a decorator, e.g. `dataclass` and `frozen=True`
+/// for `@dataclass(frozen=True)`.
+pub fn decorator_call(decorator: &Expression) -> (&Expression, &[ast::Keyword]) {
+    match decorator {
+        Expression::Call(c) => (&c.func, &c.keywords),
+        decorator => (decorator, &[]),
+    }
+}
+
+/// Whether the decorator is `dataclasses.dataclass`
+pub fn is_dataclass_decorator(decorator: &Expression) -> bool {
+    let (callee, _) = decorator_call(decorator);
+    name_of(callee).is_some_and(|name| name == "dataclass")
+}
+
+/// Returns the keywords of the `dataclass_transform` decorator in a decorator list.
+pub fn find_dataclass_transform(decorator_list: &[Expression]) -> Option<&[ast::Keyword]> {
+    decorator_list.iter().find_map(|decorator| {
+        let (callee, keywords) = decorator_call(decorator);
+        name_of(callee)
+            .is_some_and(|name| name == "dataclass_transform")
+            .then_some(keywords)
+    })
+}
+
+/// The name that an expression refers to, e.g. `field` for `dataclasses.field`
+pub fn name_of(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::Name(n) => Some(n.id.clone()),
+        Expression::Attribute(a) => Some(a.attr.clone()),
+        _ => None,
+    }
+}
+
+fn is_special_annotation(annotation: &Expression, name: &str) -> bool {
+    let annotation = match annotation {
+        Expression::Subscript(s) => &s.value,
+        annotation => annotation,
+    };
+    name_of(annotation).is_some_and(|n| n == name)
+}
+
+fn set_bool(option: &mut bool, value: &Expression) {
+    if let Expression::Constant(c) = value {
+        if let ast::ConstantValue::Bool(b) = c.value {
+            *option = b;
+        }
+    }
+}
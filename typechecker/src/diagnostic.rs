@@ -2,6 +2,9 @@ use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Diagnostic {
+    /// The rule that produced this diagnostic.
+    pub code: Rule,
+    pub severity: Severity,
     /// The message body to display to the user, to explain the diagnostic.
     pub body: String,
     /// The message to display to the user, to explain the suggested fix.
@@ -9,6 +12,103 @@ pub struct Diagnostic {
     pub range: Range,
 }
 
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{} - {}: {} ({})",
+            self.range.start.line, self.range.start.character, self.severity, self.body, self.code
+        )?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\n    help: {suggestion}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Information,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Information => "info",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Every check that the type checker performs has a rule.
+/// The rule code is printed next to the diagnostic so users can tell which
+/// check reported it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Rule {
+    /// Errors found while evaluating the type of an expression that do not
+    /// belong to a more specific rule.
+    GeneralTypeIssue,
+    /// Syntax errors reported by the parser.
+    InvalidSyntax,
+    /// A value is assigned to a variable whose declared type does not accept it.
+    AssignmentTypeMismatch,
+    /// The arguments of a call cannot be bound to the parameters of the called function.
+    CallIssue,
+    /// An argument is not assignable to the type of its parameter.
+    ArgumentType,
+    /// A returned value does not match the declared return type of the function.
+    ReturnType,
+    /// Statements that no path from the start of the scope executes.
+    UnreachableCode,
+    /// A local variable is used but it is not assigned on any path leading to the use.
+    UnboundVariable,
+    /// A local variable is used but it is not assigned on every path leading to the use.
+    PossiblyUnbound,
+    /// An attribute is accessed on a class, instance or module that does not have it.
+    AttributeAccessIssue,
+    /// A name is used but no scope that is visible from the use defines it.
+    UndefinedVariable,
+    /// A coroutine is created by calling an async function but it is never awaited.
+    UnusedCoroutine,
+}
+
+impl Rule {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Rule::GeneralTypeIssue => "general-type-issue",
+            Rule::InvalidSyntax => "invalid-syntax",
+            Rule::AssignmentTypeMismatch => "assignment-type-mismatch",
+            Rule::CallIssue => "call-issue",
+            Rule::ArgumentType => "argument-type",
+            Rule::ReturnType => "return-type",
+            Rule::UnreachableCode => "unreachable-code",
+            Rule::UnboundVariable => "unbound-variable",
+            Rule::PossiblyUnbound => "possibly-unbound",
+            Rule::AttributeAccessIssue => "attribute-access-issue",
+            Rule::UndefinedVariable => "undefined-variable",
+            Rule::UnusedCoroutine => "unused-coroutine",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Rule::UnreachableCode => Severity::Information,
+            Rule::PossiblyUnbound => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Range {
     pub start: Position,
@@ -26,6 +126,3 @@ impl Display for Position {
         write!(f, "(line: {}, character: {})", self.line, self.character)
     }
 }
-
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct CharacterSpan(pub usize, pub usize);
@@ -1,5 +1,4 @@
 use core::panic;
-use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
@@ -7,12 +6,16 @@ use std::sync::Arc;
 use crate::ast_visitor::TraversalVisitor;
 use enderpy_python_parser as parser;
 use enderpy_python_parser::ast::*;
-use parser::{ast, get_row_col_position, parser::parser::Parser};
+use parser::{ast, error::ParsingError, get_row_col_position, parser::parser::Parser};
 use std::sync::atomic::Ordering;
 
 use crate::build::ResolvedImports;
 use crate::symbol_table;
-use crate::{diagnostic::Position, semantic_analyzer::SemanticAnalyzer, symbol_table::SymbolTable};
+use crate::{
+    diagnostic::{Position, Range},
+    semantic_analyzer::SemanticAnalyzer,
+    symbol_table::SymbolTable,
+};
 
 #[derive(Clone, Debug)]
 pub enum ImportKinds<'a> {
@@ -31,6 +34,7 @@ pub struct EnderpyFile {
     pub source: String,
     pub line_starts: Vec<u32>,
     pub tree: ast::Module,
+    pub parse_errors: Vec<ParsingError>,
 }
 
 impl<'a> Eq for EnderpyFile {}
@@ -59,16 +63,7 @@ impl<'a> EnderpyFile {
             std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("cannot read file {path:?}"));
 
         let mut parser = Parser::new(&source);
-        let parse_result = catch_unwind(AssertUnwindSafe(|| parser.parse()));
-        let tree = match parse_result {
-            Ok(Ok(module)) => module,
-            Ok(Err(_)) => {
-                panic!("Cannot parse file : {path:?}");
-            }
-            Err(_) => {
-                panic!("Cannot parse file : {path:?}");
-            }
-        };
+        let (tree, parse_errors) = parser.parse_with_errors();
         let line_starts = parser.lexer.line_starts;
 
         let id = if path.ends_with("builtins.pyi") {
@@ -83,6 +78,7 @@ impl<'a> EnderpyFile {
             line_starts,
             followed,
             tree,
+            parse_errors,
             path: Arc::new(path),
         }
     }
@@ -125,6 +121,23 @@ impl<'a> EnderpyFile {
         }
     }
 
+    /// Returns the range of the given offsets. Lines are 1-based and
+    /// characters are 0-based.
+    pub fn get_range(&self, start: u32, end: u32) -> Range {
+        let (start_line_num, start_line_column, end_line_num, end_line_column) =
+            get_row_col_position(start, end, &self.line_starts);
+        Range {
+            start: Position {
+                line: start_line_num,
+                character: start_line_column,
+            },
+            end: Position {
+                line: end_line_num,
+                character: end_line_column,
+            },
+        }
+    }
+
     /// entry point to fill up the symbol table from the global definitions
     pub fn populate_symbol_table(&mut self, imports: &ResolvedImports) -> SymbolTable {
         let mut sem_anal = SemanticAnalyzer::new(self, imports);
@@ -1,6 +1,8 @@
 use std::path::Path;
 
 mod ast_visitor;
+mod cfg;
+mod dataclass;
 mod file;
 mod ruff_python_import_resolver;
 mod symbol_table;
@@ -18,7 +20,6 @@ pub(crate) mod builtins {
     pub const TUPLE_TYPE: &str = "tuple";
     pub const DICT_TYPE: &str = "dict";
     pub const SET_TYPE: &str = "set";
-    pub const ITER_TYPE: &str = "Iterator";
 }
 
 const PROJECT_ROOT_MARKERS: [&str; 1] = ["pyproject.toml"];
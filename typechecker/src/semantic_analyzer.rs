@@ -12,9 +12,9 @@ use crate::{
     file::EnderpyFile,
     ruff_python_import_resolver::module_descriptor::ImportModuleDescriptor,
     symbol_table::{
-        Alias, AsyncFunction, Class, Declaration, DeclarationPath, Function, Parameter,
-        SymbolFlags, SymbolTable, SymbolTableNode, SymbolTableScope, SymbolTableType, TypeAlias,
-        Variable,
+        Alias, AsyncFunction, Class, ComprehensionTarget, Declaration, DeclarationPath, Function,
+        Parameter, SymbolFlags, SymbolTable, SymbolTableNode, SymbolTableScope, SymbolTableType,
+        TargetBinding, TypeAlias, UnpackTarget, Variable,
     },
 };
 
@@ -33,10 +33,11 @@ pub struct SemanticAnalyzer<'a> {
     pub function_information: FunctionInformation,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct FunctionInformation {
     pub return_statements: Vec<ast::Return>,
     pub yield_statements: Vec<ast::Yield>,
+    pub yield_from_statements: Vec<ast::YieldFrom>,
 }
 
 #[allow(unused)]
@@ -50,6 +51,7 @@ impl<'a> SemanticAnalyzer<'a> {
             function_information: FunctionInformation {
                 return_statements: Vec::new(),
                 yield_statements: Vec::new(),
+                yield_from_statements: Vec::new(),
             },
         }
     }
@@ -75,7 +77,8 @@ impl<'a> SemanticAnalyzer<'a> {
         target: &Expression,
         value: Option<Expression>,
         type_annotation: Option<Expression>,
-        for_stmt: Option<parser::ast::For>,
+        binding: Option<TargetBinding>,
+        unpack_path: Vec<UnpackTarget>,
     ) {
         match target {
             Expression::Name(n) => {
@@ -89,7 +92,8 @@ impl<'a> SemanticAnalyzer<'a> {
                     type_annotation,
                     inferred_type_source: value,
                     is_constant: false,
-                    for_target: for_stmt,
+                    unpack_path,
+                    binding,
                 });
 
                 let mut symbol_flags = SymbolFlags::empty();
@@ -99,16 +103,39 @@ impl<'a> SemanticAnalyzer<'a> {
 
                 self.create_symbol(n.id.clone(), decl, symbol_flags)
             }
-            Expression::Tuple(t) => {
-                for elm in t.elements.iter() {
+            Expression::Tuple(_) | Expression::List(_) => {
+                let elements = match target {
+                    Expression::Tuple(t) => &t.elements,
+                    Expression::List(l) => &l.elements,
+                    _ => unreachable!(),
+                };
+                let star = elements
+                    .iter()
+                    .position(|e| matches!(e, Expression::Starred(_)));
+                for (index, elm) in elements.iter().enumerate() {
+                    let mut unpack_path = unpack_path.clone();
+                    unpack_path.push(UnpackTarget {
+                        index,
+                        count: elements.len(),
+                        star,
+                    });
                     self.create_variable_declaration_symbol(
                         elm,
                         value.clone(),
                         type_annotation.clone(),
-                        for_stmt.clone(),
+                        binding.clone(),
+                        unpack_path,
                     )
                 }
             }
+            // The star target is at the star index of the last unpack target in the path
+            Expression::Starred(s) => self.create_variable_declaration_symbol(
+                &s.value,
+                value,
+                type_annotation,
+                binding,
+                unpack_path,
+            ),
             Expression::Attribute(a) => {
                 let member_access_info = get_member_access_info(&self.symbol_table, &a.value);
                 let symbol_flags = if member_access_info.is_some_and(|x| x) {
@@ -117,17 +144,9 @@ impl<'a> SemanticAnalyzer<'a> {
                     SymbolFlags::CLASS_MEMBER
                 };
 
-                let function_assigns_attribute = if let Some(function_def) =
-                    self.symbol_table.current_scope().kind.as_function()
-                {
-                    // TODO: some python usual names to be interned
-                    intern_lookup(function_def.name) == "__init__"
-                        || intern_lookup(function_def.name) == "__new__"
-                } else {
-                    false
-                };
-
-                if function_assigns_attribute {
+                // Attributes of self and cls that are assigned in any method are declared
+                // on the class
+                if member_access_info.is_some() {
                     let declaration_path = DeclarationPath::new(
                         self.symbol_table.id,
                         a.node,
@@ -138,7 +157,8 @@ impl<'a> SemanticAnalyzer<'a> {
                         type_annotation,
                         inferred_type_source: value,
                         is_constant: false,
-                        for_target: for_stmt,
+                        unpack_path,
+                        binding,
                     });
 
                     self.create_symbol(a.attr.clone(), declaration, symbol_flags);
@@ -148,6 +168,53 @@ impl<'a> SemanticAnalyzer<'a> {
         }
     }
 
+    /// Comprehensions bind their targets in their own scope. The iterable of the first
+    /// clause is evaluated in the enclosing scope and the rest of the comprehension in the
+    /// comprehension scope.
+    fn visit_comprehension(
+        &mut self,
+        name: &str,
+        node: ast::Node,
+        generators: &[ast::Comprehension],
+        elements: &[&Expression],
+    ) {
+        let enclosing_scope_id = self.symbol_table.current_scope_id;
+        if let Some(first) = generators.first() {
+            self.visit_expr(&first.iter);
+        }
+        self.symbol_table.push_scope(SymbolTableScope::new(
+            SymbolTableType::Comprehension,
+            name.to_owned(),
+            node.start,
+            enclosing_scope_id,
+        ));
+        for (index, comprehension) in generators.iter().enumerate() {
+            let iter_scope = if index == 0 {
+                enclosing_scope_id
+            } else {
+                self.visit_expr(&comprehension.iter);
+                self.symbol_table.current_scope_id
+            };
+            self.create_variable_declaration_symbol(
+                &comprehension.target,
+                None,
+                None,
+                Some(TargetBinding::Comprehension(ComprehensionTarget {
+                    comprehension: comprehension.clone(),
+                    iter_scope,
+                })),
+                vec![],
+            );
+            for if_expr in &comprehension.ifs {
+                self.visit_expr(if_expr);
+            }
+        }
+        for element in elements {
+            self.visit_expr(element);
+        }
+        self.symbol_table.exit_scope();
+    }
+
     fn add_arguments_definitions(&mut self, args: &parser::ast::Arguments) {
         let defaults_len = args.defaults.len();
         for (pos_only, index) in args.posonlyargs.iter().zip(0..args.posonlyargs.len()) {
@@ -294,6 +361,7 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
             Statement::AsyncWithStatement(w) => self.visit_async_with(w),
             Statement::AsyncFunctionDef(f) => self.visit_async_function_def(f),
             Statement::TypeAlias(a) => self.visit_type_alias(a),
+            Statement::Invalid(i) => self.visit_invalid(i),
         }
     }
 
@@ -386,6 +454,7 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
     }
 
     fn visit_if(&mut self, i: &parser::ast::If) {
+        self.visit_expr(&i.test);
         for stmt in &i.body {
             self.visit_stmt(stmt);
         }
@@ -395,48 +464,76 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
     }
 
     fn visit_while(&mut self, w: &parser::ast::While) {
+        self.visit_expr(&w.test);
         for stmt in &w.body {
             self.visit_stmt(stmt)
         }
     }
 
     fn visit_for(&mut self, f: &parser::ast::For) {
-        self.create_variable_declaration_symbol(&f.target, None, None, Some(f.clone()));
+        self.create_variable_declaration_symbol(
+            &f.target,
+            None,
+            None,
+            Some(TargetBinding::For(f.clone())),
+            vec![],
+        );
+        self.visit_expr(&f.iter);
         for stmt in &f.body {
             self.visit_stmt(stmt);
         }
     }
 
     fn visit_async_for(&mut self, f: &parser::ast::AsyncFor) {
+        self.create_variable_declaration_symbol(
+            &f.target,
+            None,
+            None,
+            Some(TargetBinding::AsyncFor(f.clone())),
+            vec![],
+        );
+        self.visit_expr(&f.iter);
         for stmt in &f.body {
             self.visit_stmt(stmt);
         }
     }
 
     fn visit_with(&mut self, w: &parser::ast::With) {
-        for stmt in &w.body {
-            self.visit_stmt(stmt);
-        }
         for with_items in &w.items {
             self.visit_expr(&with_items.context_expr);
-            match &with_items.optional_vars {
-                Some(items) => self.visit_expr(items),
-                None => (),
+            if let Some(items) = &with_items.optional_vars {
+                self.create_variable_declaration_symbol(
+                    items,
+                    None,
+                    None,
+                    Some(TargetBinding::With(with_items.clone())),
+                    vec![],
+                );
+                self.visit_expr(items);
             }
         }
-    }
-
-    fn visit_async_with(&mut self, w: &parser::ast::AsyncWith) {
         for stmt in &w.body {
             self.visit_stmt(stmt);
         }
+    }
+
+    fn visit_async_with(&mut self, w: &parser::ast::AsyncWith) {
         for with_items in &w.items {
             self.visit_expr(&with_items.context_expr);
-            match &with_items.optional_vars {
-                Some(items) => self.visit_expr(items),
-                None => (),
+            if let Some(items) = &with_items.optional_vars {
+                self.create_variable_declaration_symbol(
+                    items,
+                    None,
+                    None,
+                    Some(TargetBinding::AsyncWith(with_items.clone())),
+                    vec![],
+                );
+                self.visit_expr(items);
             }
         }
+        for stmt in &w.body {
+            self.visit_stmt(stmt);
+        }
     }
 
     fn visit_try(&mut self, t: &parser::ast::Try) {
@@ -512,8 +609,7 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
 
         self.add_arguments_definitions(&f.args);
 
-        // TODO: clone
-        let prev_function_information = self.function_information.clone();
+        let prev_function_information = std::mem::take(&mut self.function_information);
 
         for stmt in f.body.iter() {
             self.visit_stmt(stmt);
@@ -521,6 +617,8 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
 
         let return_statements = std::mem::take(&mut self.function_information.return_statements);
         let yield_statements = std::mem::take(&mut self.function_information.yield_statements);
+        let yield_from_statements =
+            std::mem::take(&mut self.function_information.yield_from_statements);
         self.function_information = prev_function_information;
 
         for type_parameter in &f.type_params {
@@ -546,9 +644,10 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
             declaration_path,
             function_node: f.clone(),
             is_method: self.is_inside_class(),
-            is_generator: !yield_statements.is_empty(),
+            is_generator: !yield_statements.is_empty() || !yield_from_statements.is_empty(),
             return_statements,
             yield_statements,
+            yield_from_statements,
             raise_statements: vec![],
         });
         let flags = SymbolFlags::empty();
@@ -575,8 +674,7 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
 
         self.add_arguments_definitions(&f.args);
 
-        // TODO: clone
-        let prev_function_information = self.function_information.clone();
+        let prev_function_information = std::mem::take(&mut self.function_information);
 
         for stmt in f.body.iter() {
             self.visit_stmt(stmt);
@@ -584,6 +682,8 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
 
         let return_statements = std::mem::take(&mut self.function_information.return_statements);
         let yield_statements = std::mem::take(&mut self.function_information.yield_statements);
+        let yield_from_statements =
+            std::mem::take(&mut self.function_information.yield_from_statements);
         self.function_information = prev_function_information;
 
         for type_parameter in &f.type_params {
@@ -608,9 +708,10 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
             declaration_path,
             function_node: f.clone(),
             is_method: self.is_inside_class(),
-            is_generator: !yield_statements.is_empty(),
+            is_generator: !yield_statements.is_empty() || !yield_from_statements.is_empty(),
             return_statements,
             yield_statements,
+            yield_from_statements,
             raise_statements: vec![],
         });
         let flags = SymbolFlags::empty();
@@ -694,6 +795,7 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
     }
 
     fn visit_match(&mut self, m: &parser::ast::Match) {
+        self.visit_expr(&m.subject);
         for case in &m.cases {
             for stmt in &case.body {
                 self.visit_stmt(stmt);
@@ -709,77 +811,145 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
         }
     }
 
-    fn visit_tuple(&mut self, _t: &parser::ast::Tuple) {}
+    fn visit_tuple(&mut self, t: &parser::ast::Tuple) {
+        for elm in t.elements.iter() {
+            self.visit_expr(elm);
+        }
+    }
 
-    fn visit_dict(&mut self, _d: &parser::ast::Dict) {}
+    fn visit_dict(&mut self, d: &parser::ast::Dict) {
+        for elm in d.keys.iter().chain(d.values.iter()) {
+            self.visit_expr(elm);
+        }
+    }
 
-    fn visit_set(&mut self, _s: &parser::ast::Set) {}
+    fn visit_set(&mut self, s: &parser::ast::Set) {
+        for elm in s.elements.iter() {
+            self.visit_expr(elm);
+        }
+    }
 
     fn visit_name(&mut self, _n: &Name) {}
 
-    fn visit_bool_op(&mut self, _b: &parser::ast::BoolOperation) {}
+    fn visit_bool_op(&mut self, b: &parser::ast::BoolOperation) {
+        for value in b.values.iter() {
+            self.visit_expr(value);
+        }
+    }
 
-    fn visit_unary_op(&mut self, _u: &parser::ast::UnaryOperation) {}
+    fn visit_unary_op(&mut self, u: &parser::ast::UnaryOperation) {
+        self.visit_expr(&u.operand);
+    }
 
-    fn visit_bin_op(&mut self, _b: &parser::ast::BinOp) {}
+    fn visit_bin_op(&mut self, b: &parser::ast::BinOp) {
+        self.visit_expr(&b.left);
+        self.visit_expr(&b.right);
+    }
 
-    fn visit_named_expr(&mut self, _n: &parser::ast::NamedExpression) {}
+    fn visit_named_expr(&mut self, n: &parser::ast::NamedExpression) {
+        self.visit_expr(&n.value);
+    }
 
     // TODO: clone
     fn visit_yield(&mut self, y: &parser::ast::Yield) {
         self.function_information.yield_statements.push(y.clone());
     }
 
-    fn visit_yield_from(&mut self, _y: &parser::ast::YieldFrom) {}
+    fn visit_yield_from(&mut self, y: &parser::ast::YieldFrom) {
+        self.function_information
+            .yield_from_statements
+            .push(y.clone());
+    }
 
-    fn visit_starred(&mut self, _s: &parser::ast::Starred) {}
+    fn visit_starred(&mut self, s: &parser::ast::Starred) {
+        self.visit_expr(&s.value);
+    }
 
-    fn visit_generator(&mut self, _g: &parser::ast::Generator) {}
+    fn visit_generator(&mut self, g: &parser::ast::Generator) {
+        self.visit_comprehension("<genexpr>", g.node, &g.generators, &[&g.element]);
+    }
 
-    fn visit_list_comp(&mut self, _l: &parser::ast::ListComp) {}
+    fn visit_list_comp(&mut self, l: &parser::ast::ListComp) {
+        self.visit_comprehension("<listcomp>", l.node, &l.generators, &[&l.element]);
+    }
 
-    fn visit_set_comp(&mut self, _s: &parser::ast::SetComp) {}
+    fn visit_set_comp(&mut self, s: &parser::ast::SetComp) {
+        self.visit_comprehension("<setcomp>", s.node, &s.generators, &[&s.element]);
+    }
 
-    fn visit_dict_comp(&mut self, _d: &parser::ast::DictComp) {}
+    fn visit_dict_comp(&mut self, d: &parser::ast::DictComp) {
+        self.visit_comprehension("<dictcomp>", d.node, &d.generators, &[&d.key, &d.value]);
+    }
 
     // Imagine it's always store
-    fn visit_attribute(&mut self, _a: &parser::ast::Attribute) {}
+    fn visit_attribute(&mut self, a: &parser::ast::Attribute) {
+        self.visit_expr(&a.value);
+    }
 
-    fn visit_subscript(&mut self, _s: &parser::ast::Subscript) {}
+    fn visit_subscript(&mut self, s: &parser::ast::Subscript) {
+        self.visit_expr(&s.value);
+        self.visit_expr(&s.slice);
+    }
 
-    fn visit_slice(&mut self, _s: &parser::ast::Slice) {}
+    fn visit_slice(&mut self, s: &parser::ast::Slice) {
+        for bound in [&s.lower, &s.upper, &s.step].into_iter().flatten() {
+            self.visit_expr(bound);
+        }
+    }
 
-    fn visit_call(&mut self, _c: &parser::ast::Call) {
-        // TODO: more arguments
-        for arg in &_c.args {
+    fn visit_call(&mut self, c: &parser::ast::Call) {
+        self.visit_expr(&c.func);
+        for arg in &c.args {
             self.visit_expr(arg);
         }
+        for keyword in &c.keywords {
+            self.visit_expr(&keyword.value);
+        }
     }
 
-    fn visit_await(&mut self, _a: &parser::ast::Await) {}
+    fn visit_await(&mut self, a: &parser::ast::Await) {
+        self.visit_expr(&a.value);
+    }
 
-    fn visit_compare(&mut self, _c: &parser::ast::Compare) {}
+    fn visit_compare(&mut self, c: &parser::ast::Compare) {
+        self.visit_expr(&c.left);
+        for comparator in &c.comparators {
+            self.visit_expr(comparator);
+        }
+    }
 
     fn visit_lambda(&mut self, _l: &parser::ast::Lambda) {}
 
-    fn visit_if_exp(&mut self, _i: &parser::ast::IfExp) {}
+    fn visit_if_exp(&mut self, i: &parser::ast::IfExp) {
+        self.visit_expr(&i.test);
+        self.visit_expr(&i.body);
+        self.visit_expr(&i.orelse);
+    }
 
-    fn visit_joined_str(&mut self, _j: &parser::ast::JoinedStr) {}
+    fn visit_joined_str(&mut self, j: &parser::ast::JoinedStr) {
+        for value in &j.values {
+            self.visit_expr(value);
+        }
+    }
 
-    fn visit_formatted_value(&mut self, _f: &parser::ast::FormattedValue) {}
+    fn visit_formatted_value(&mut self, f: &parser::ast::FormattedValue) {
+        self.visit_expr(&f.value);
+    }
 
     fn visit_alias(&mut self, _a: &parser::ast::Alias) {}
 
     fn visit_assign(&mut self, assign: &parser::ast::Assign) {
         let value = &assign.value;
-        if assign.targets.len() > 1 {
-            todo!("multiple assignment not supported");
+        // Every target of `a = b = value` is assigned the value
+        for target in &assign.targets {
+            self.create_variable_declaration_symbol(
+                target,
+                Some(value.clone()),
+                None,
+                None,
+                vec![],
+            );
         }
-        let target = assign
-            .targets
-            .last()
-            .expect("Assignment has at least one target");
-        self.create_variable_declaration_symbol(target, Some(value.clone()), None, None);
 
         self.visit_expr(&assign.value);
     }
@@ -792,6 +962,7 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
             value.clone(),
             Some(a.annotation.clone()),
             None,
+            vec![],
         );
 
         if let Some(val) = &a.value {
@@ -804,7 +975,9 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
         self.visit_expr(&a.value);
     }
 
-    fn visit_assert(&mut self, _a: &parser::ast::Assert) {}
+    fn visit_assert(&mut self, a: &parser::ast::Assert) {
+        self.visit_expr(&a.test);
+    }
 
     fn visit_pass(&mut self, _p: &parser::ast::Pass) {}
 
@@ -813,6 +986,9 @@ impl<'a> TraversalVisitor for SemanticAnalyzer<'a> {
     // TODO: clone
     fn visit_return(&mut self, r: &parser::ast::Return) {
         self.function_information.return_statements.push(r.clone());
+        if let Some(value) = &r.value {
+            self.visit_expr(value);
+        }
     }
 
     fn visit_raise(&mut self, _r: &parser::ast::Raise) {}
@@ -859,11 +1035,13 @@ pub fn get_member_access_info(
     // Check if one of the decorators is a classmethod or staticmethod
     let mut is_class_member = false;
     for decorator in function_def.decorator_list.iter() {
-        if let parser::ast::Expression::Call(call) = decorator {
-            if let Some(name) = call.func.as_name() {
-                if name.id == "classmethod" {
-                    is_class_member = true;
-                }
+        let decorator = match decorator {
+            parser::ast::Expression::Call(call) => &call.func,
+            decorator => decorator,
+        };
+        if let Some(name) = decorator.as_name() {
+            if name.id == "classmethod" {
+                is_class_member = true;
             }
         }
     }
@@ -205,10 +205,10 @@ impl SymbolTable {
         self.scopes.push(new_scope);
     }
 
-    /// Sets the current scope to the scope that starts at the given position
+    /// Sets the current scope to the function or class scope that starts at the given position
     pub fn set_scope(&mut self, pos: u32) {
         self.prev_scope_id = Some(self.current_scope_id);
-        let scope = self.scopes.iter().find(|scope| scope.start_pos == pos);
+        let scope = self.scopes.iter().find(|scope| scope.is_definition_at(pos));
         if let Some(scope) = scope {
             self.current_scope_id = scope.id;
         } else {
@@ -216,9 +216,10 @@ impl SymbolTable {
         }
     }
 
-    /// Returns the id of the scope starting at position
+    /// Returns the id of the function or class scope starting at position. The module scope
+    /// also starts at 0 so it is never matched here.
     pub fn get_scope(&self, pos: u32) -> u32 {
-        let scope = self.scopes.iter().find(|scope| scope.start_pos == pos);
+        let scope = self.scopes.iter().find(|scope| scope.is_definition_at(pos));
         if let Some(scope) = scope {
             scope.id
         } else {
@@ -226,6 +227,15 @@ impl SymbolTable {
         }
     }
 
+    /// The scope of the comprehension that starts at the given position. Comprehensions in
+    /// expressions that the semantic analyzer does not visit have no scope.
+    pub fn get_comprehension_scope(&self, pos: u32) -> Option<u32> {
+        self.scopes
+            .iter()
+            .find(|scope| scope.start_pos == pos && scope.kind.is_comprehension())
+            .map(|scope| scope.id)
+    }
+
     pub fn revert_scope(&mut self) {
         self.current_scope_id = self.prev_scope_id.expect("no previous scope");
     }
@@ -267,10 +277,10 @@ impl SymbolTable {
             self.current_scope_mut()
         };
 
-        if let Some(existing_symbol) = scope.symbols.get(&symbol_node.name) {
-            symbol_node
+        if let Some(existing_symbol) = scope.symbols.get_mut(&symbol_node.name) {
+            existing_symbol
                 .declarations
-                .extend(existing_symbol.declarations.clone());
+                .append(&mut symbol_node.declarations);
         } else {
             scope.symbols.insert(symbol_node.name.clone(), symbol_node);
         }
@@ -319,6 +329,15 @@ impl SymbolTableScope {
         }
     }
 
+    pub fn symbols(&self) -> impl Iterator<Item = &SymbolTableNode> {
+        self.symbols.values()
+    }
+
+    /// Whether this is the scope of a function or class definition that starts at `pos`
+    fn is_definition_at(&self, pos: u32) -> bool {
+        self.start_pos == pos && (self.kind.is_function() || self.kind.is_class())
+    }
+
     pub fn global_scope() -> Self {
         SymbolTableScope {
             id: 0,
@@ -340,6 +359,9 @@ pub enum SymbolTableType {
     Module,
     Class(Arc<ClassDef>),
     Function(Arc<FunctionDef>),
+    /// List, set and dict comprehensions and generator expressions bind their targets in
+    /// their own scope
+    Comprehension,
 }
 
 bitflags! {
@@ -435,8 +457,44 @@ pub struct Variable {
     pub type_annotation: Option<ast::Expression>,
     pub inferred_type_source: Option<ast::Expression>,
     pub is_constant: bool,
-    // Determines if this variable was introduced as part of a for statement
-    pub for_target: Option<ast::For>,
+    /// Where the variable is in the tuple targets that the value is unpacked to, from the
+    /// outermost target. Empty when the variable is the whole target.
+    pub unpack_path: Vec<UnpackTarget>,
+    // Determines if this variable was introduced as the target of a statement or clause
+    // that is not an assignment e.g. `a` in: for a in []:
+    pub binding: Option<TargetBinding>,
+}
+
+/// A statement or clause that binds its target to a value that it gets from an
+/// expression, e.g. the items that a `for` statement iterates over.
+#[derive(Debug, Clone)]
+pub enum TargetBinding {
+    For(ast::For),
+    AsyncFor(ast::AsyncFor),
+    /// The context manager of a `with` statement, the target is bound to what entering
+    /// it returns
+    With(ast::WithItem),
+    AsyncWith(ast::WithItem),
+    Comprehension(ComprehensionTarget),
+}
+
+/// The clause of a comprehension that binds a target, e.g. `for a in b` in `[a for a in b]`.
+#[derive(Debug, Clone)]
+pub struct ComprehensionTarget {
+    pub comprehension: ast::Comprehension,
+    /// The scope that the iterable is evaluated in. The iterable of the first clause is
+    /// evaluated in the scope that encloses the comprehension.
+    pub iter_scope: u32,
+}
+
+/// The position of a target in a tuple target, e.g. `b` is at index 1 of 2 targets in
+/// `a, b = value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnpackTarget {
+    pub index: usize,
+    pub count: usize,
+    /// The index of the star target of the tuple target, e.g. 1 in `a, *b = value`
+    pub star: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -449,11 +507,39 @@ pub struct Function {
     pub return_statements: Vec<ast::Return>,
     /// yield statements that are reachable in the top level function body
     pub yield_statements: Vec<ast::Yield>,
+    /// yield from statements that are reachable in the top level function body
+    pub yield_from_statements: Vec<ast::YieldFrom>,
     /// raise statements that are reachable in the top level function body
     pub raise_statements: Vec<ast::Raise>,
 }
 
-impl Function {}
+impl Function {
+    /// Whether the function is decorated with `typing.overload`
+    pub fn is_overload(&self) -> bool {
+        is_decorated_with(&self.function_node.decorator_list, "overload")
+    }
+
+    /// Whether the function is decorated with `property`
+    pub fn is_property(&self) -> bool {
+        is_decorated_with(&self.function_node.decorator_list, "property")
+    }
+
+    /// Whether the function is decorated with `@<property>.setter`
+    pub fn is_property_setter(&self) -> bool {
+        self.function_node
+            .decorator_list
+            .iter()
+            .any(|d| matches!(d, ast::Expression::Attribute(a) if a.attr == "setter"))
+    }
+
+    /// Whether the function is decorated with `@<property>.setter` or `@<property>.deleter`
+    pub fn is_property_accessor(&self) -> bool {
+        self.function_node
+            .decorator_list
+            .iter()
+            .any(|d| matches!(d, ast::Expression::Attribute(a) if a.attr == "setter" || a.attr == "deleter"))
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct AsyncFunction {
@@ -465,10 +551,28 @@ pub struct AsyncFunction {
     pub return_statements: Vec<ast::Return>,
     /// yield statements that are reachable in the top level function body
     pub yield_statements: Vec<ast::Yield>,
+    /// yield from statements that are reachable in the top level function body
+    pub yield_from_statements: Vec<ast::YieldFrom>,
     /// raise statements that are reachable in the top level function body
     pub raise_statements: Vec<ast::Raise>,
 }
 
+impl AsyncFunction {
+    /// Whether the function is decorated with `typing.overload`
+    pub fn is_overload(&self) -> bool {
+        is_decorated_with(&self.function_node.decorator_list, "overload")
+    }
+}
+
+/// Whether one of the decorators is the name, e.g. `overload` or `typing.overload`.
+fn is_decorated_with(decorator_list: &[ast::Expression], name: &str) -> bool {
+    decorator_list.iter().any(|d| match d {
+        ast::Expression::Name(n) => n.id == name,
+        ast::Expression::Attribute(a) => a.attr == name,
+        _ => false,
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct Class {
     pub name: String,
@@ -503,6 +607,17 @@ impl Class {
         }
     }
 
+    /// Whether the class is decorated with `typing.runtime_checkable`
+    pub fn is_runtime_checkable(&self) -> bool {
+        self.class_node.as_ref().is_some_and(|class_node| {
+            class_node.decorator_list.iter().any(|d| match d {
+                ast::Expression::Name(n) => n.id == "runtime_checkable",
+                ast::Expression::Attribute(a) => a.attr == "runtime_checkable",
+                _ => false,
+            })
+        })
+    }
+
     /// Class node refers to SpecialForm in typeshed
     /// TODO: needs improvements mostly set the correct values
     pub fn new_special(
@@ -572,6 +687,37 @@ impl SymbolTableNode {
             .expect("There must be at least one declaration")
     }
 
+    /// The declarations of an overloaded function that are decorated with
+    /// `typing.overload`, in the order of their declaration. The implementation is not
+    /// one of them.
+    pub fn overloads(&self) -> Vec<&Function> {
+        self.declarations
+            .iter()
+            .filter_map(|decl| decl.as_function())
+            .filter(|f| f.is_overload())
+            .collect()
+    }
+
+    /// Property accessors declare a function several times with the same name. Until they
+    /// are evaluated separately the first declaration is used as the type of the symbol.
+    pub fn first_property_accessor(&self) -> Option<&Function> {
+        let mut functions = self
+            .declarations
+            .iter()
+            .filter_map(|decl| decl.as_function());
+        let first = functions.clone().next()?;
+        functions.any(|f| f.is_property_accessor()).then_some(first)
+    }
+
+    /// The declaration of a symbol that a comprehension binds, e.g. `a` in `[a for a in b]`
+    pub fn comprehension_target_declaration(&self) -> Option<&Declaration> {
+        self.declarations.iter().find(|declaration| {
+            declaration
+                .as_variable()
+                .is_some_and(|v| matches!(v.binding, Some(TargetBinding::Comprehension(_))))
+        })
+    }
+
     pub fn get_declaration_until_pos(&self, pos: u32) -> Option<&Declaration> {
         // TODO: Handle iterating declarations from last to first
         self.declarations.iter().find(|&declaration| {
@@ -6,26 +6,34 @@ use enderpy_python_parser::{self as parser, parser::parser::intern_lookup};
 use parser::ast;
 use parser::parser::parser::Parser;
 use std::{
-    cell::Cell,
+    cell::{Cell, RefCell},
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Display},
     panic::{catch_unwind, AssertUnwindSafe},
     sync::Arc,
 };
 use tracing::{error, instrument, span, trace, Level};
 
 use miette::{bail, Result};
-use parser::ast::Expression;
+use parser::ast::{Expression, GetNode};
 
 use super::{
     builtins,
     types::{
-        self, CallableType, ClassType, InstanceType, LiteralValue, ModuleRef, PythonType, TypeVar,
+        self, CallableType, ClassFields, ClassType, EnumMember, InstanceType, KnownValue,
+        LiteralValue, MethodKind, ModuleRef, NamedTupleField, PythonType, TypeVar, TypedDictField,
     },
 };
 use crate::{
     build::BuildManager,
+    cfg::{Binding, BlockId, ControlFlowGraph, Edge, FlowNode, ENTRY},
+    dataclass::{self, DataclassField, DataclassOptions},
     semantic_analyzer::get_member_access_info,
-    symbol_table::{self, Class, Declaration, DeclarationPath, Id, SymbolTable, SymbolTableNode},
-    types::CallableArgs,
+    symbol_table::{
+        self, Class, Declaration, DeclarationPath, Id, SymbolTable, SymbolTableNode,
+        SymbolTableType, TargetBinding, UnpackTarget,
+    },
+    types::{CallableArgs, CallableParameter},
 };
 
 const LITERAL_TYPE_PARAMETER_MSG: &str = "Type arguments for 'Literal' must be None, a literal value (int, bool, str, or bytes), or an enum value";
@@ -33,13 +41,73 @@ const LITERAL_TYPE_PARAMETER_MSG: &str = "Type arguments for 'Literal' must be N
 // allowed as parameters
 const UNION_TYPE_PARAMETER_MSG: &str = "Type arguments for 'Union' must be names or literal values";
 
+/// An error in the evaluated code that is reported to the user, e.g. calling a value that
+/// is not callable. Other errors of the evaluator are failures to evaluate a type.
+#[derive(Debug)]
+pub struct TypeIssue(pub String);
+
+impl fmt::Display for TypeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TypeIssue {}
+
+impl miette::Diagnostic for TypeIssue {}
+
 const SPECIAL_FORM: &str = "_SpecialForm";
+/// The name of the type var that `typing.Self` stands for
+const SELF_TYPE_VAR: &str = "Self";
+/// The types that the type vars of a generic function or class stand for, by the name
+/// of the type var.
+type TypeVarSolution = HashMap<String, PythonType>;
+/// Names that every module defines without declaring them.
+const MODULE_ATTRIBUTES: [&str; 9] = [
+    "__name__",
+    "__doc__",
+    "__file__",
+    "__package__",
+    "__spec__",
+    "__loader__",
+    "__path__",
+    "__dict__",
+    "__builtins__",
+];
+/// Attributes that every class has or that only affect the class object itself. They are
+/// not part of the interface that a protocol describes.
+const PROTOCOL_EXCLUDED_MEMBERS: [&str; 7] = [
+    "__slots__",
+    "__init__",
+    "__new__",
+    "__class_getitem__",
+    "__doc__",
+    "__module__",
+    "__annotations__",
+];
 #[derive(Clone, Debug)]
 pub struct TypeEvaluator<'a> {
     build_manager: &'a BuildManager,
     flags: Cell<GetTypeFlags>,
+    /// Pairs of (class, protocol) qualified names that are being compared
+    protocol_checks: RefCell<Vec<(String, String)>>,
+    /// (symbol table, position) of the names whose type is being evaluated along the
+    /// flow graph
+    flow_references: RefCell<Vec<(Id, u32)>>,
+    /// Whether the calls at (symbol table, position) never return
+    no_return_calls: RefCell<HashMap<(Id, u32), bool>>,
+    /// (symbol table, position) of the explicit type aliases that are being evaluated
+    type_alias_references: RefCell<Vec<(Id, u32)>>,
+    /// The method resolution orders of the classes by their (symbol table, class scope,
+    /// qualified name). Err holds the fallback order of an inconsistent hierarchy.
+    mro_cache: RefCell<MroCache>,
+    /// (symbol table, scope) of the generator functions whose yielded and returned values
+    /// are being evaluated
+    generator_references: RefCell<Vec<(Id, u32)>>,
 }
 
+type MroCache = HashMap<(Id, u32, String), Result<Vec<ClassType>, Vec<ClassType>>>;
+
 bitflags::bitflags! {
     #[repr(transparent)]
     #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -56,6 +124,39 @@ bitflags::bitflags! {
         ///    See more: https://peps.python.org/pep-0563/#backwards-compatibility
         ///    An example: https://github.com/python/typing/blob/main/conformance/tests/annotations_forward_refs.py#L78
         const DEFERRED = 1 << 0;
+        /// Names are resolved from their declarations only. Used while the flow graph
+        /// that narrows the names is built.
+        const NO_FLOW_ANALYSIS = 1 << 1;
+    }
+}
+
+/// The reason why a class does not satisfy a protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolMismatch {
+    /// The class does not have the member.
+    Missing(String),
+    /// The class has the member but its type is not assignable to the protocol member.
+    Incompatible(String),
+    /// The class derives from the protocol with a type argument that is not assignable
+    /// to the type argument of the protocol.
+    TypeArgument {
+        source: Box<PythonType>,
+        target: Box<PythonType>,
+    },
+}
+
+impl Display for ProtocolMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolMismatch::Missing(member) => write!(f, "\"{member}\" is not present"),
+            ProtocolMismatch::Incompatible(member) => {
+                write!(f, "\"{member}\" is an incompatible type")
+            }
+            ProtocolMismatch::TypeArgument { source, target } => write!(
+                f,
+                "type argument \"{source}\" is not assignable to \"{target}\""
+            ),
+        }
     }
 }
 
@@ -65,6 +166,12 @@ impl<'a> TypeEvaluator<'a> {
         TypeEvaluator {
             build_manager,
             flags: Cell::new(GetTypeFlags::empty()),
+            protocol_checks: RefCell::new(vec![]),
+            flow_references: RefCell::new(vec![]),
+            no_return_calls: RefCell::new(HashMap::new()),
+            type_alias_references: RefCell::new(vec![]),
+            mro_cache: RefCell::new(HashMap::new()),
+            generator_references: RefCell::new(vec![]),
         }
     }
     /// Entry point function to get type of an expression. The expression passed
@@ -84,16 +191,16 @@ impl<'a> TypeEvaluator<'a> {
                 let typ = match &c.value {
                     // Constants are not literals unless they are explicitly
                     // typing.readthedocs.io/en/latest/spec/literal.html#backwards-compatibility
-                    ast::ConstantValue::Int => self.get_builtin_type("int"),
-                    ast::ConstantValue::Float => self.get_builtin_type("float"),
+                    ast::ConstantValue::Int(_) => self.get_builtin_type("int"),
+                    ast::ConstantValue::Float(_) => self.get_builtin_type("float"),
                     ast::ConstantValue::Str(_) => self.get_builtin_type("str"),
                     ast::ConstantValue::Bool(_) => self.get_builtin_type("bool"),
                     ast::ConstantValue::None => Some(PythonType::None),
-                    ast::ConstantValue::Bytes => self.get_builtin_type("bytes"),
+                    ast::ConstantValue::Bytes(_) => self.get_builtin_type("bytes"),
                     ast::ConstantValue::Ellipsis => Some(PythonType::Any),
+                    ast::ConstantValue::Complex { .. } => self.get_builtin_type("complex"),
                     // TODO: implement
                     ast::ConstantValue::Tuple => Some(PythonType::Unknown),
-                    ast::ConstantValue::Complex => Some(PythonType::Unknown),
                 };
                 Ok(match typ {
                     Some(t) => t,
@@ -108,89 +215,107 @@ impl<'a> TypeEvaluator<'a> {
             }
             ast::Expression::Call(call) => {
                 let called_function = &call.func;
-                match called_function {
-                    ast::Expression::Subscript(subscript) => {
-                        todo!("initialized a class with type parameter type")
+                // Errors in the called expression are reported when the checker visits it.
+                let called_type = self
+                    .get_type(called_function, symbol_table, scope_id)
+                    .unwrap_or(PythonType::Unknown);
+                if let PythonType::Callable(c) = &called_type {
+                    let return_type =
+                        self.get_return_type_of_callable(c, call, symbol_table, scope_id);
+                    Ok(return_type)
+                } else if let PythonType::Class(c) = &called_type {
+                    if let Some(class) =
+                        self.create_class_with_fields(c, call, symbol_table, scope_id)
+                    {
+                        return Ok(PythonType::Class(class));
                     }
-                    _ => {
-                        let called_type = self.get_type(called_function, symbol_table, scope_id)?;
-                        if let PythonType::Callable(c) = &called_type {
-                            let return_type = self.get_return_type_of_callable(
-                                c,
-                                &call.args,
-                                symbol_table,
-                                scope_id,
-                            );
-                            Ok(return_type)
-                        } else if let PythonType::Class(c) = &called_type {
-                            Ok(called_type)
-                        } else if let PythonType::TypeVar(t) = &called_type {
-                            let Some(first_arg) = call.args.first() else {
-                                bail!("TypeVar must be called with a name");
-                            };
-                            let type_name = match first_arg {
-                                ast::Expression::Constant(ref str_const) => {
-                                    match &str_const.value {
-                                        ast::ConstantValue::Str(_) => {
-                                            let decl_id = t.decl_id;
-                                            let file = &self
-                                                .build_manager
-                                                .files
-                                                .get(&symbol_table.id)
-                                                .unwrap();
-                                            str_const.get_value(&file.source).to_string()
-                                        }
-                                        _ => panic!("TypeVar first arg must be a string"),
-                                    }
-                                }
-                                _ => panic!("TypeVar must be called with at least one arg"),
-                            };
+                    Ok(self.get_constructed_class_type(c, call, symbol_table, scope_id))
+                } else if let PythonType::TypeVar(t) = &called_type {
+                    let Some(first_arg) = call.args.first() else {
+                        bail!("TypeVar must be called with a name");
+                    };
+                    let type_name = match first_arg {
+                        ast::Expression::Constant(ref str_const) => match &str_const.value {
+                            ast::ConstantValue::Str(name) => name.clone(),
+                            _ => panic!("TypeVar first arg must be a string"),
+                        },
+                        _ => panic!("TypeVar must be called with at least one arg"),
+                    };
 
-                            let bounds: Vec<PythonType> = call
-                                .args
-                                .iter()
-                                .skip(1)
-                                .map(|arg| {
-                                    self.get_type(arg, symbol_table, scope_id)
-                                        .unwrap_or(PythonType::Unknown)
-                                })
-                                .collect();
-
-                            // Disallow specifying a single bound
-                            if bounds.len() == 1 {
-                                bail!("TypeVar must be called with at least two bounds");
-                            }
+                    let bounds: Vec<PythonType> = call
+                        .args
+                        .iter()
+                        .skip(1)
+                        .map(|arg| {
+                            self.get_type(arg, symbol_table, scope_id)
+                                .unwrap_or(PythonType::Unknown)
+                        })
+                        .collect();
 
-                            // Disallow specifying a type var as a bound
-                            if bounds.iter().any(|b| matches!(b, PythonType::TypeVar(_))) {
-                                bail!("TypeVar cannot be used as a bound");
-                            }
+                    // Disallow specifying a single bound
+                    if bounds.len() == 1 {
+                        bail!("TypeVar must be called with at least two bounds");
+                    }
 
-                            if bounds
-                                .iter()
-                                .filter(|x| {
-                                    let Some(class) = x.as_class() else {
-                                        return false;
-                                    };
+                    // Disallow specifying a type var as a bound
+                    if bounds.iter().any(|b| matches!(b, PythonType::TypeVar(_))) {
+                        bail!("TypeVar cannot be used as a bound");
+                    }
 
-                                    !class.specialized.is_empty()
-                                })
-                                .count()
-                                != 0
-                            {
-                                trace!("type var bounds cannot contain other type vars");
-                                bail!("type var bounds cannot contain other type vars");
-                            }
+                    if bounds
+                        .iter()
+                        .filter(|x| {
+                            let Some(class) = x.as_class() else {
+                                return false;
+                            };
 
-                            Ok(PythonType::TypeVar(TypeVar {
-                                name: type_name.to_string(),
-                                bounds,
-                                decl_id: t.decl_id,
-                            }))
-                        } else {
-                            bail!("{called_type:?} is not callable");
-                        }
+                            !class.specialized.is_empty()
+                        })
+                        .count()
+                        != 0
+                    {
+                        trace!("type var bounds cannot contain other type vars");
+                        bail!("type var bounds cannot contain other type vars");
+                    }
+
+                    let upper_bound = call
+                        .keywords
+                        .iter()
+                        .find(|k| k.arg.as_deref() == Some("bound"))
+                        .map(|k| {
+                            Box::new(
+                                self.get_type(&k.value, symbol_table, scope_id)
+                                    .unwrap_or(PythonType::Unknown),
+                            )
+                        });
+                    if upper_bound.is_some() && !bounds.is_empty() {
+                        bail!("TypeVar cannot have both a bound and constraints");
                     }
+
+                    Ok(PythonType::TypeVar(TypeVar {
+                        name: type_name.to_string(),
+                        bounds,
+                        upper_bound,
+                        decl_id: t.decl_id,
+                    }))
+                } else if let Some(call_method) = called_type
+                    .as_instance()
+                    .and_then(|i| self.get_call_method(i, symbol_table))
+                {
+                    Ok(
+                        self.get_return_type_of_callable(
+                            &call_method,
+                            call,
+                            symbol_table,
+                            scope_id,
+                        ),
+                    )
+                } else if matches!(called_type, PythonType::Unknown | PythonType::Any) {
+                    Ok(called_type)
+                } else {
+                    bail!(TypeIssue(format!(
+                        "Object of type \"{called_type}\" is not callable"
+                    )));
                 }
             }
             ast::Expression::List(l) => {
@@ -208,16 +333,11 @@ impl<'a> TypeEvaluator<'a> {
                 )))
             }
             ast::Expression::Tuple(t) => {
-                let elm_type =
-                    self.get_sequence_type_from_elements(&t.elements, symbol_table, scope_id);
-
-                let class_type = self
-                    .get_builtin_type(builtins::TUPLE_TYPE)
-                    .expect("builtin tuple type not found");
-                let Some(c) = class_type.class() else {
-                    panic!("Tuple type is not a class");
-                };
-                Ok(PythonType::Instance(InstanceType::new(c, vec![elm_type])))
+                let elements =
+                    self.get_tuple_expression_elements(&t.elements, symbol_table, scope_id);
+                Ok(self
+                    .create_tuple_instance(elements)
+                    .expect("builtin tuple type not found"))
             }
             ast::Expression::Dict(d) => {
                 let key_type =
@@ -255,44 +375,67 @@ impl<'a> TypeEvaluator<'a> {
                 ast::UnaryOperator::Not => Ok(self.get_builtin_type("bool").expect("typeshed")),
                 ast::UnaryOperator::UAdd
                 | ast::UnaryOperator::USub
-                | ast::UnaryOperator::Invert => Ok(PythonType::Unknown),
+                | ast::UnaryOperator::Invert => self.unary_op_result_type(
+                    &self
+                        .get_type(&u.operand, symbol_table, scope_id)
+                        .unwrap_or(PythonType::Unknown),
+                    &u.op,
+                    symbol_table,
+                ),
             },
             ast::Expression::NamedExpr(e) => self.get_type(&e.value, symbol_table, scope_id),
-            ast::Expression::Yield(a) => {
-                let yield_type = match a.value {
-                    Some(ref v) => self.get_type(v, symbol_table, scope_id)?,
-                    None => PythonType::None,
+            // The value of a yield expression is what is sent to the generator
+            ast::Expression::Yield(_) => Ok(self.get_send_type(symbol_table, scope_id)),
+            // The value of a yield from expression is what the delegated generator returns
+            ast::Expression::YieldFrom(yf) => {
+                let delegate = self.get_type(&yf.value, symbol_table, scope_id)?;
+                Ok(self.get_generator_return_type(&delegate))
+            }
+            ast::Expression::Starred(s) => self.get_type(&s.value, symbol_table, scope_id),
+            // Comprehensions evaluate their elements in their own scope
+            ast::Expression::Generator(g) => {
+                let Some(scope) = symbol_table.get_comprehension_scope(g.node.start) else {
+                    return Ok(PythonType::Unknown);
+                };
+                let element_type = self.get_type(&g.element, symbol_table, scope)?;
+                // Generator expressions with an `async for` clause are async generators
+                let (name, arguments) = if g.generators.iter().any(|c| c.is_async) {
+                    ("AsyncGenerator", vec![element_type, PythonType::None])
+                } else {
+                    (
+                        "Generator",
+                        vec![element_type, PythonType::None, PythonType::None],
+                    )
+                };
+                let Some(generator) = self.get_typing_class(name) else {
+                    return Ok(PythonType::Unknown);
                 };
-                let builtin_type = self.get_builtin_type(builtins::ITER_TYPE);
-                todo!()
+                Ok(PythonType::Instance(InstanceType::new(
+                    generator, arguments,
+                )))
             }
-            ast::Expression::YieldFrom(yf) => {
-                let yield_type = match &yf.value {
-                    ast::Expression::List(ref l) => {
-                        self.get_sequence_type_from_elements(&l.elements, symbol_table, scope_id)
-                    }
-                    _ => panic!("TODO: infer type from yield from"),
+            ast::Expression::ListComp(l) => {
+                let Some(scope) = symbol_table.get_comprehension_scope(l.node.start) else {
+                    return Ok(PythonType::Unknown);
                 };
-                todo!()
+                let element_type = self.get_type(&l.element, symbol_table, scope)?;
+                Ok(self.get_builtin_instance(builtins::LIST_TYPE, vec![element_type]))
             }
-            ast::Expression::Starred(s) => Ok(PythonType::Unknown),
-            ast::Expression::Generator(g) => {
-                // This is not correct
-                // let mut comp_targets: HashMap<String, Type> = HashMap::new();
-                // for gens in &g.generators {
-                //     match *gens.target.clone() {
-                //         ast::Expression::Name(n) => {
-                //             comp_targets.insert(n.id, self.get_type(&gens.iter));
-                //         }
-                //         _ => panic!("comprehension target must be a name, or does it?"),
-                //     }
-                // }
-
-                Ok(PythonType::Unknown)
+            ast::Expression::SetComp(s) => {
+                let Some(scope) = symbol_table.get_comprehension_scope(s.node.start) else {
+                    return Ok(PythonType::Unknown);
+                };
+                let element_type = self.get_type(&s.element, symbol_table, scope)?;
+                Ok(self.get_builtin_instance(builtins::SET_TYPE, vec![element_type]))
+            }
+            ast::Expression::DictComp(d) => {
+                let Some(scope) = symbol_table.get_comprehension_scope(d.node.start) else {
+                    return Ok(PythonType::Unknown);
+                };
+                let key_type = self.get_type(&d.key, symbol_table, scope)?;
+                let value_type = self.get_type(&d.value, symbol_table, scope)?;
+                Ok(self.get_builtin_instance(builtins::DICT_TYPE, vec![key_type, value_type]))
             }
-            ast::Expression::ListComp(_) => Ok(PythonType::Unknown),
-            ast::Expression::SetComp(_) => Ok(PythonType::Unknown),
-            ast::Expression::DictComp(_) => Ok(PythonType::Unknown),
             /*
             When attribute is accessed there are multilple cases:
 
@@ -327,17 +470,24 @@ impl<'a> TypeEvaluator<'a> {
                 // Case 1
                 // This is self or cls
                 let file = &self.build_manager.files.get(&symbol_table.id).unwrap();
-                if get_member_access_info(symbol_table, &a.value).is_some() {
+                if let Some(is_instance_member) = get_member_access_info(symbol_table, &a.value) {
                     let enclosing_parent_class = symbol_table.get_enclosing_class_scope();
                     if let Some(enclosing_parent_class) = enclosing_parent_class {
-                        let symbol_table_node =
-                            symbol_table.lookup_attribute(&a.attr, enclosing_parent_class.id);
-                        let res = match symbol_table_node {
-                            Some(node) => self.get_symbol_type(node, symbol_table, None),
-                            None => panic!("cannot find symbol table node for attribute access"),
-                        };
+                        // Attributes that the class does not declare are inherited or
+                        // missing, those are looked up on the type of the receiver.
+                        if let Some(node) =
+                            symbol_table.lookup_attribute(&a.attr, enclosing_parent_class.id)
+                        {
+                            let res = self.get_symbol_type(node, symbol_table, None);
+                            let receiver = self
+                                .get_type(&a.value, symbol_table, scope_id)
+                                .unwrap_or(PythonType::Unknown);
 
-                        return Ok(res);
+                            if is_instance_member {
+                                return Ok(self.bind_member(res, &receiver));
+                            }
+                            return Ok(self.bind_class_member(res, &receiver));
+                        }
                     }
                 }
 
@@ -349,19 +499,40 @@ impl<'a> TypeEvaluator<'a> {
                         return Ok(PythonType::Unknown);
                     }
                 };
+                if let Some(enum_attribute) = self.get_enum_attribute_type(&value_type, &a.attr) {
+                    return Ok(enum_attribute);
+                }
                 match value_type {
-                    PythonType::Class(ref c) => {
-                        let attribute_on_c = self.lookup_on_class(symbol_table, c, &a.attr);
-                        if let Some(attribute_on_c) = attribute_on_c {
-                            Ok(attribute_on_c)
-                        } else {
-                            Ok(PythonType::Unknown)
-                        }
+                    PythonType::Class(ref c)
+                        if self.refers_to_class(&a.value, symbol_table, scope_id) =>
+                    {
+                        Ok(self
+                            .get_attribute_of_class_object(&value_type, c, &a.attr)
+                            .unwrap_or(PythonType::Unknown))
                     }
+                    PythonType::Class(ref c) => Ok(self
+                        .get_attribute_of_class(&value_type, c, &c.specialized, &a.attr)
+                        .unwrap_or(PythonType::Unknown)),
+                    PythonType::Instance(ref i) => Ok(self
+                        .get_attribute_of_class(
+                            &value_type,
+                            &i.class_type,
+                            &i.specialized_type_parameters,
+                            &a.attr,
+                        )
+                        .unwrap_or(PythonType::Unknown)),
                     PythonType::Module(module) => {
                         let module_sym_table = self.get_symbol_table(&module.module_id);
                         Ok(self.get_name_type(&a.attr, None, &module_sym_table, 0))
                     }
+                    // Other attributes of enum values are looked up on the enum class
+                    PythonType::LiteralValue(KnownValue {
+                        literal_value: LiteralValue::Enum(ref member),
+                    }) => Ok(self
+                        .lookup_on_class(symbol_table, &member.class, &a.attr)
+                        .map_or(PythonType::Unknown, |attribute| {
+                            self.bind_member(attribute, &value_type)
+                        })),
                     // Anything you perform a get attribute on should at least resolve using object
                     // builtin because everything is an object :)
                     _ => {
@@ -373,24 +544,53 @@ impl<'a> TypeEvaluator<'a> {
                         let attribute_on_c =
                             self.lookup_on_class(symbol_table, object_class, &a.attr);
                         if let Some(attribute_on_c) = attribute_on_c {
-                            Ok(attribute_on_c)
+                            Ok(self.bind_member(attribute_on_c, &value_type))
                         } else {
                             Ok(PythonType::Unknown)
                         }
                     }
                 }
             }
-            ast::Expression::BinOp(b) => Ok(self.bin_op_result_type(
-                &self.get_type(&b.left, symbol_table, scope_id)?,
-                &self.get_type(&b.right, symbol_table, scope_id)?,
+            ast::Expression::BinOp(b) => self.bin_op_result_type(
+                &self
+                    .get_type(&b.left, symbol_table, scope_id)
+                    .unwrap_or(PythonType::Unknown),
+                &self
+                    .get_type(&b.right, symbol_table, scope_id)
+                    .unwrap_or(PythonType::Unknown),
                 &b.op,
-            )),
+                symbol_table,
+            ),
             ast::Expression::Subscript(s) => {
-                let value_type = self.get_type(&s.value, symbol_table, scope_id)?;
+                let value_type = match self
+                    .get_type(&s.value, symbol_table, scope_id)
+                    .unwrap_or(PythonType::Unknown)
+                {
+                    // Subscripting a value of `type[C]` subscripts the class C
+                    PythonType::Class(c) if c.details.qual_name == "builtins.type" => {
+                        match c.specialized.first() {
+                            Some(PythonType::Class(inner)) => PythonType::Class(inner.clone()),
+                            _ => PythonType::Class(c),
+                        }
+                    }
+                    value_type => value_type,
+                };
                 let span = span!(Level::TRACE, "Subscript", value_type = display(&value_type),);
                 let _guard = span.enter();
 
+                if let Some(item_type) = self.get_item_type_of_fields(&value_type, &s.slice)? {
+                    return Ok(item_type);
+                }
+
                 let typ = match value_type {
+                    PythonType::Class(ref c)
+                        if c.specialized.is_empty() && c.details.qual_name == "builtins.tuple" =>
+                    {
+                        let mut new_class = c.clone();
+                        new_class.specialized =
+                            self.get_tuple_type_arguments(&s.slice, symbol_table, scope_id);
+                        PythonType::Class(new_class)
+                    }
                     PythonType::Class(ref c) => {
                         // If type parameters are not set then this is setting them.
                         if c.specialized.is_empty() {
@@ -478,7 +678,10 @@ impl<'a> TypeEvaluator<'a> {
                                 let ret_type = self.resolve_generics(
                                     &callable.return_type,
                                     &c.class_type.type_parameters,
-                                    &c.specialized_type_parameters,
+                                    &class_type_arguments(
+                                        &c.class_type,
+                                        &c.specialized_type_parameters,
+                                    ),
                                 );
                                 match ret_type {
                                     PythonType::TypeVar(ref tv) => {
@@ -519,15 +722,14 @@ impl<'a> TypeEvaluator<'a> {
             }
             ast::Expression::Slice(_) => Ok(PythonType::Unknown),
             ast::Expression::Await(a) => {
-                let awaited_type = self.get_type(&a.value, symbol_table, scope_id)?;
-                let typ = match awaited_type {
-                    PythonType::Coroutine(callable) => callable.return_type.clone(),
-                    _ => unimplemented!("Can other things be awaited?"),
-                };
-
-                Ok(typ)
+                let awaited_type = self
+                    .get_type(&a.value, symbol_table, scope_id)
+                    .unwrap_or(PythonType::Unknown);
+                Ok(self
+                    .get_awaited_type(&awaited_type)
+                    .unwrap_or(PythonType::Unknown))
             }
-            ast::Expression::Compare(_) => Ok(PythonType::Unknown),
+            ast::Expression::Compare(c) => self.compare_result_type(c, symbol_table, scope_id),
             ast::Expression::Lambda(_) => Ok(PythonType::Unknown),
             ast::Expression::IfExp(_) => Ok(PythonType::Unknown),
             ast::Expression::JoinedStr(_) => Ok(self.get_builtin_type("str").expect("typeshed")),
@@ -551,7 +753,12 @@ impl<'a> TypeEvaluator<'a> {
         let expr_type = match type_annotation {
             Expression::Name(name) => {
                 // TODO: Reject this type if the name refers to a variable.
-                self.get_name_type(&name.id, Some(name.node.start), symbol_table, scope_id)
+                match self.get_name_type(&name.id, Some(name.node.start), symbol_table, scope_id) {
+                    PythonType::Class(c) if c.details.special && c.details.name == "Self" => self
+                        .get_self_type_var(symbol_table, scope_id)
+                        .unwrap_or(PythonType::Unknown),
+                    name_type => name_type,
+                }
             }
             Expression::Constant(ref c) => match c.value {
                 ast::ConstantValue::None => PythonType::None,
@@ -561,10 +768,8 @@ impl<'a> TypeEvaluator<'a> {
                 // 2. Module is preferred over local scope so we first check module scope and
                 //    then local scope.
                 //    https://peps.python.org/pep-0563/#backwards-compatibility
-                ast::ConstantValue::Str(_) => {
-                    let source = self.build_manager.files.get(&symbol_table.id).unwrap();
-                    let value = c.get_value(&source.source);
-                    let mut parser = Parser::new(&value);
+                ast::ConstantValue::Str(ref value) => {
+                    let mut parser = Parser::new(value);
                     // Wrap the parsing logic inside a `catch_unwind` block
                     let parse_result = catch_unwind(AssertUnwindSafe(|| parser.parse()));
 
@@ -609,7 +814,8 @@ impl<'a> TypeEvaluator<'a> {
                 };
                 if class_type.details.special {
                     return match class_type.details.name.as_str() {
-                        "Literal" => self.handle_literal_type(s),
+                        "Literal" => self.handle_literal_type(s, symbol_table, scope_id),
+                        "Callable" => self.handle_callable_type(s, symbol_table, scope_id),
                         "Union" => {
                             // try to convert subscript value into tuple and send the tuple
                             // items as parameters to union type
@@ -617,16 +823,52 @@ impl<'a> TypeEvaluator<'a> {
                                 Expression::Tuple(t) => &t.elements,
                                 _ => todo!(),
                             };
-                            self.handle_union_type(union_parameters.to_vec())
+                            self.handle_union_type(
+                                union_parameters.to_vec(),
+                                symbol_table,
+                                scope_id,
+                            )
                         }
                         "Optional" => {
                             let inner_value =
                                 self.get_annotation_type(&s.slice, symbol_table, scope_id);
                             PythonType::Optional(Box::new(inner_value))
                         }
+                        "Tuple" => {
+                            let Some(mut tuple_class) = self
+                                .get_builtin_type(builtins::TUPLE_TYPE)
+                                .and_then(|t| t.class())
+                            else {
+                                return PythonType::Unknown;
+                            };
+                            tuple_class.specialized =
+                                self.get_tuple_type_arguments(&s.slice, symbol_table, scope_id);
+                            PythonType::Class(tuple_class)
+                        }
+                        // Qualifiers of typed dict items and unpacked keyword arguments
+                        "Required" | "NotRequired" | "ReadOnly" | "Unpack" => {
+                            self.get_annotation_type(&s.slice, symbol_table, scope_id)
+                        }
+                        // The narrowed type is kept so that calls can narrow their argument
+                        "TypeGuard" | "TypeIs" => {
+                            let narrowed_type =
+                                self.get_annotation_type(&s.slice, symbol_table, scope_id);
+                            PythonType::Class(ClassType::new(
+                                class_type.details.clone(),
+                                class_type.type_parameters.clone(),
+                                class_type.base_classes.clone(),
+                                vec![narrowed_type],
+                            ))
+                        }
                         _ => PythonType::Any,
                     };
                 }
+                if class_type.details.qual_name == "builtins.tuple" {
+                    let mut tuple_class = class_type.clone();
+                    tuple_class.specialized =
+                        self.get_tuple_type_arguments(&s.slice, symbol_table, scope_id);
+                    return PythonType::Class(tuple_class);
+                }
                 // TODO: Here we need to specify any for any of the unspecialised parameters
                 let mut initialized_type_parameters: Vec<PythonType> = vec![];
                 match &s.slice {
@@ -671,7 +913,7 @@ impl<'a> TypeEvaluator<'a> {
                     ast::BinaryOperator::BitOr => {
                         // flatten the bit or expression if the left and right are also bit or
                         let union_parameters = self.flatten_bit_or(b);
-                        self.handle_union_type(union_parameters)
+                        self.handle_union_type(union_parameters, symbol_table, scope_id)
                     }
                     // TODO: check if other binary operators are allowed
                     _ => todo!(),
@@ -708,6 +950,11 @@ impl<'a> TypeEvaluator<'a> {
 
         let find_in_current_symbol_table = symbol_table.lookup_in_scope(name, scope_id);
         if let Some(f) = find_in_current_symbol_table {
+            if let Some(position) = position {
+                if let Some(t) = self.get_flow_type(name, position, f, symbol_table, scope_id) {
+                    return t;
+                }
+            }
             return self.get_symbol_type(f, symbol_table, position);
         };
 
@@ -739,6 +986,41 @@ impl<'a> TypeEvaluator<'a> {
         }
     }
 
+    /// Whether a name resolves from the scope, it is looked up the same way as in
+    /// `get_name_type`.
+    pub fn is_name_defined(&self, name: &str, symbol_table: &SymbolTable, scope_id: u32) -> bool {
+        symbol_table.lookup_in_scope(name, scope_id).is_some()
+            || MODULE_ATTRIBUTES.contains(&name)
+            || self.is_star_imported(name, symbol_table)
+            || self
+                .get_symbol_table(&Id(0))
+                .lookup_in_scope(name, 0)
+                .is_some()
+    }
+
+    /// The names that resolve from the scope, the ones of the innermost scope first and
+    /// builtins last.
+    pub fn get_visible_names(&self, symbol_table: &SymbolTable, scope_id: u32) -> Vec<String> {
+        let mut names = vec![];
+        let mut scope = symbol_table.get_scope_by_id(scope_id);
+        while let Some(current) = scope {
+            names.extend(sorted_symbol_names(current));
+            scope = symbol_table.parent_scope(current);
+        }
+        names.extend(sorted_symbol_names(
+            self.get_symbol_table(&Id(0)).global_scope(),
+        ));
+        names
+    }
+
+    fn is_star_imported(&self, name: &str, symbol_table: &SymbolTable) -> bool {
+        symbol_table
+            .star_imports
+            .iter()
+            .flat_map(|star_import| star_import.resolved_ids.iter())
+            .any(|id| self.get_symbol_table(id).lookup_in_scope(name, 0).is_some())
+    }
+
     /// Get the type of a symbol node based on declarations
     fn get_symbol_type(
         &self,
@@ -761,6 +1043,9 @@ impl<'a> TypeEvaluator<'a> {
                     || (symbol_table.is_pyi_file())
                 {
                     Some(symbol.last_declaration())
+                } else if let Some(target) = symbol.comprehension_target_declaration() {
+                    // The element of a comprehension comes before its targets in the source
+                    Some(target)
                 } else {
                     symbol.get_declaration_until_pos(position)
                 }
@@ -777,6 +1062,16 @@ impl<'a> TypeEvaluator<'a> {
             };
             decl
         };
+        self.get_declaration_type(symbol, decl, position)
+    }
+
+    /// Get the type of one declaration of a symbol
+    fn get_declaration_type(
+        &self,
+        symbol: &SymbolTableNode,
+        decl: &Declaration,
+        position: Option<u32>,
+    ) -> PythonType {
         let decl_scope = decl.declaration_path().scope_id;
         let symbol_table = self.get_dec_symbol_table(decl.declaration_path());
         let result = match decl {
@@ -795,6 +1090,21 @@ impl<'a> TypeEvaluator<'a> {
                             decl_scope,
                         );
                         PythonType::Class(ClassType::new(class_symbol, vec![], vec![], vec![]))
+                    } else if let (true, Some(source)) = (
+                        is_special_form_name(type_annotation, "TypeAlias"),
+                        &v.inferred_type_source,
+                    ) {
+                        // An explicit type alias stands for the type that it is assigned.
+                        // Recursive aliases are not expanded where they refer to themselves.
+                        let key = (symbol_table.id, v.declaration_path.node.start);
+                        if self.type_alias_references.borrow().contains(&key) {
+                            return PythonType::Unknown;
+                        }
+                        self.type_alias_references.borrow_mut().push(key);
+                        let alias_type =
+                            self.get_annotation_type(source, &symbol_table, decl_scope);
+                        self.type_alias_references.borrow_mut().pop();
+                        alias_type
                     } else {
                         var_type
                     }
@@ -817,77 +1127,59 @@ impl<'a> TypeEvaluator<'a> {
                     if let Some(b_type) = builtin_type {
                         b_type
                     } else {
-                        self.get_type(source, &symbol_table, decl_scope)
-                            .unwrap_or(PythonType::Unknown)
+                        let value_type = self
+                            .get_type(source, &symbol_table, decl_scope)
+                            .unwrap_or(PythonType::Unknown);
+                        self.get_unpacked_type(value_type, &v.unpack_path)
                     }
-                // If the variable was created using a for statement e.g. `a` in: for a in []:
-                } else if let Some(for_stmt) = &v.for_target {
-                    if let Some(position) = position {
-                        if for_stmt.node.end < position {
-                            error!("for loop variable used after for loop {}", &symbol.name);
-                            return PythonType::Unknown;
+                } else if let Some(binding) = &v.binding {
+                    let get_type = |expr: &Expression, scope_id: u32| {
+                        self.get_type(expr, &symbol_table, scope_id)
+                            .unwrap_or(PythonType::Unknown)
+                    };
+                    let bound_type = match binding {
+                        // If the variable was created using a for statement e.g. `a` in: for a in []:
+                        TargetBinding::For(for_stmt) => {
+                            if let Some(position) = position {
+                                if for_stmt.node.end < position {
+                                    error!(
+                                        "for loop variable used after for loop {}",
+                                        &symbol.name
+                                    );
+                                    return PythonType::Unknown;
+                                }
+                            }
+                            let iter_type = self
+                                .get_type(&for_stmt.iter, &symbol_table, decl_scope)
+                                .unwrap_or_else(|_| {
+                                    panic!("iterating over unknown {:?}", for_stmt)
+                                });
+                            self.get_iterated_type(&iter_type)
                         }
-                    }
-                    let iter_type = self
-                        .get_type(&for_stmt.iter, &symbol_table, decl_scope)
-                        .unwrap_or_else(|_| panic!("iterating over unknown {:?}", for_stmt));
-                    match iter_type {
-                        PythonType::Instance(instance_type) => {
-                            let iter_method = match self.lookup_on_class(
-                                &symbol_table,
-                                &instance_type.class_type,
-                                "__iter__",
-                            ) {
-                                Some(PythonType::Callable(c)) => c,
-                                Some(other) => panic!("iter method was not callable: {}", other),
-                                None => panic!("next method not found"),
-                            };
-
-                            let Some(iter_method_type) = self
-                                .resolve_generics(
-                                    &iter_method.return_type,
-                                    &instance_type.class_type.type_parameters,
-                                    &instance_type.specialized_type_parameters,
-                                )
-                                .class()
-                            else {
-                                panic!("iter method return type is not class");
-                            };
-
-                            let next_method = match self.lookup_on_class(
-                                &symbol_table,
-                                &iter_method_type,
-                                "__next__",
-                            ) {
-                                Some(PythonType::Callable(c)) => c,
-                                Some(other) => panic!("next method was not callable: {}", other),
-                                None => panic!("next method not found"),
-                            };
-
-                            self.resolve_generics(
-                                &next_method.return_type,
-                                &iter_method_type.type_parameters,
-                                &iter_method_type.specialized,
-                            )
+                        TargetBinding::AsyncFor(for_stmt) => {
+                            self.get_async_iterated_type(&get_type(&for_stmt.iter, decl_scope))
                         }
-                        _ => {
-                            error!("iterating over a {} is not defined", iter_type);
-                            PythonType::Unknown
+                        TargetBinding::With(item) => {
+                            self.get_entered_type(&get_type(&item.context_expr, decl_scope), false)
                         }
-                    }
+                        TargetBinding::AsyncWith(item) => {
+                            self.get_entered_type(&get_type(&item.context_expr, decl_scope), true)
+                        }
+                        TargetBinding::Comprehension(target) => {
+                            let iter_type = get_type(&target.comprehension.iter, target.iter_scope);
+                            if target.comprehension.is_async {
+                                self.get_async_iterated_type(&iter_type)
+                            } else {
+                                self.get_iterated_type(&iter_type)
+                            }
+                        }
+                    };
+                    self.get_unpacked_type(bound_type, &v.unpack_path)
                 } else {
                     PythonType::Unknown
                 }
             }
-            Declaration::Function(f) => {
-                self.get_function_type(
-                    &symbol_table,
-                    f,
-                    // to be able to get the function signature correctly we use the scope id of the
-                    // function. Since parameters are defined in that scope.
-                    symbol_table.get_scope(f.function_node.node.start),
-                )
-            }
+            Declaration::Function(f) => self.get_function_symbol_type(symbol, f, &symbol_table),
             Declaration::AsyncFunction(f) => self.get_async_function_type(
                 &symbol_table,
                 f,
@@ -903,21 +1195,10 @@ impl<'a> TypeEvaluator<'a> {
                     } else {
                         annotated_type
                     }
+                } else if p.is_first {
+                    self.get_receiver_parameter_type(&symbol_table, decl_scope)
+                        .unwrap_or(PythonType::Unknown)
                 } else {
-                    if p.is_first
-                        && (p.parameter_node.arg == "self" || p.parameter_node.arg == "cls")
-                    {
-                        let class_scope = symbol_table
-                            .get_enclosing_class_scope_of_scope(decl_scope)
-                            .map(|s| symbol_table.get_scope_by_id(s.0).expect("not found"))
-                            .expect("method must be in a class");
-                        let parent_scope =
-                            symbol_table.parent_scope(class_scope).expect("no parent");
-                        let class_def = symbol_table
-                            .lookup_in_scope(&class_scope.name, parent_scope.id)
-                            .expect("class def not found");
-                        return self.get_symbol_type(class_def, &symbol_table, position);
-                    }
                     PythonType::Unknown
                 }
             }
@@ -1037,6 +1318,7 @@ impl<'a> TypeEvaluator<'a> {
             return Ok(PythonType::TypeVar(TypeVar {
                 name: "".to_string(),
                 bounds: vec![],
+                upper_bound: None,
                 decl_id: symbol_table.id,
             }));
         }
@@ -1049,27 +1331,37 @@ impl<'a> TypeEvaluator<'a> {
             }
             None => {}
         };
-        // Bases can also add generic type parameters to the class
-        // For example: class A(metaclass=Generic)
-        match &class_symbol.class_node {
-            Some(ref k) => {
-                for keyword in k.keywords.iter() {
-                    bases.push(&keyword.value);
-                }
-            }
-            None => {}
-        };
+        // The metaclass is not a base class, attributes that are not found on the class
+        // and its bases are looked up on it.
+        let metaclass = class_symbol
+            .class_node
+            .iter()
+            .flat_map(|class_node| class_node.keywords.iter())
+            .find(|keyword| keyword.arg.as_deref() == Some("metaclass"))
+            .and_then(|keyword| {
+                self.get_type(&keyword.value, symbol_table, class_decl_scope)
+                    .ok()
+            })
+            .and_then(|metaclass| metaclass.class());
 
         let mut class_def_type_parameters = vec![];
+        let mut generic_type_parameters = None;
         let mut base_classes = vec![];
-        let mut specialized_type_parameters = vec![];
         for base_class in bases {
             let base_type = self.get_type(base_class, symbol_table, class_decl_scope);
             let Ok(PythonType::Class(c)) = base_type else {
                 continue;
             };
+            // `Generic[...]` determines the order of the type parameters
+            let is_generic = matches!(c.details.name.as_str(), "Generic" | "_Generic");
+            let are_unique =
+                (0..c.specialized.len()).all(|i| !c.specialized[..i].contains(&c.specialized[i]));
+            if is_generic && !c.specialized.is_empty() && are_unique {
+                generic_type_parameters = Some(c.specialized.clone());
+            }
             let Some(possible_type_parameter) = base_class.as_subscript() else {
-                class_def_type_parameters.extend(c.type_parameters);
+                class_def_type_parameters.extend(c.type_parameters.clone());
+                base_classes.push(c);
                 continue;
             };
             match &possible_type_parameter.slice {
@@ -1087,14 +1379,9 @@ impl<'a> TypeEvaluator<'a> {
                         symbol_table,
                         class_decl_scope,
                     );
-                    if class_def_type_parameters.contains(&type_parameter) {
-                        continue;
-                    }
-                    // If a type parameter wa
-                    if class_def_type_parameters.contains(&type_parameter) {
-                        continue;
+                    if !class_def_type_parameters.contains(&type_parameter) {
+                        class_def_type_parameters.push(type_parameter);
                     }
-                    class_def_type_parameters.push(type_parameter);
                 }
                 ast::Expression::Tuple(type_parameters) => {
                     let mut tuple_type_parameters = vec![];
@@ -1113,24 +1400,32 @@ impl<'a> TypeEvaluator<'a> {
                     }
                     class_def_type_parameters.extend(tuple_type_parameters);
                 }
-                // TODO: if the type parameters are not specialized in the class definition then
-                // they should be any. So here we should check if these type parameters are
-                // specified in the class definition.
+                // Type vars that are nested in the type arguments of a base class are type
+                // parameters of the class too, e.g. `class C(Set[tuple[K, V]])`
                 _ => {
-                    for ele in c.type_parameters.iter() {
-                        specialized_type_parameters.push(PythonType::Any);
+                    let mut type_vars = vec![];
+                    c.specialized
+                        .iter()
+                        .for_each(|t| collect_type_vars(t, &mut type_vars));
+                    for type_var in type_vars {
+                        let type_parameter = PythonType::TypeVar(type_var.clone());
+                        if !class_def_type_parameters.contains(&type_parameter) {
+                            class_def_type_parameters.push(type_parameter);
+                        }
                     }
                 }
             };
             base_classes.push(c);
         }
 
-        Ok(PythonType::Class(ClassType::new(
+        let mut class_type = ClassType::new(
             class_symbol.clone(),
-            class_def_type_parameters,
+            generic_type_parameters.unwrap_or(class_def_type_parameters),
             base_classes,
-            specialized_type_parameters,
-        )))
+            vec![],
+        );
+        class_type.metaclass = metaclass.map(Box::new);
+        Ok(PythonType::Class(class_type))
     }
 
     fn get_sequence_type_from_elements(
@@ -1174,7 +1469,7 @@ impl<'a> TypeEvaluator<'a> {
                     })
             }
             Declaration::Function(f) => {
-                self.get_function_type(&builtins_symbol_table, f, decl.declaration_path().scope_id)
+                self.get_function_symbol_type(builtin_symbol, f, &builtins_symbol_table)
             }
             Declaration::AsyncFunction(f) => self.get_async_function_type(
                 &builtins_symbol_table,
@@ -1186,6 +1481,14 @@ impl<'a> TypeEvaluator<'a> {
         Some(found_declaration)
     }
 
+    /// An instance of a generic builtin class, e.g. `list[int]`
+    fn get_builtin_instance(&self, name: &str, type_arguments: Vec<PythonType>) -> PythonType {
+        match self.get_builtin_type(name).and_then(|t| t.class()) {
+            Some(c) => PythonType::Instance(InstanceType::new(c, type_arguments)),
+            None => PythonType::Unknown,
+        }
+    }
+
     /// This function flattens a chain of bit or expressions
     /// For example: a | b | c | d
     /// will be flattened to [a, b, c, d]
@@ -1225,8 +1528,20 @@ impl<'a> TypeEvaluator<'a> {
     /// expressions are the parameters of the union type
     /// in case of t1 | t2 | t3, expressions are [t1, t2, t3]
     /// and in case of Union[t1, t2, t3], expressions are [t1, t2, t3]
-    fn handle_union_type(&self, expressions: Vec<Expression>) -> PythonType {
-        PythonType::Unknown
+    fn handle_union_type(
+        &self,
+        expressions: Vec<Expression>,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> PythonType {
+        if expressions.is_empty() {
+            return PythonType::Unknown;
+        }
+        let types = expressions
+            .iter()
+            .map(|expr| self.get_annotation_type(expr, symbol_table, scope_id))
+            .collect();
+        PythonType::union(types)
     }
 
     /// TODO: Need to complete this when types are more complete
@@ -1236,502 +1551,5251 @@ impl<'a> TypeEvaluator<'a> {
     }
 
     // https://peps.python.org/pep-0586
-    fn handle_literal_type(&self, s: &ast::Subscript) -> PythonType {
+    fn handle_literal_type(
+        &self,
+        s: &ast::Subscript,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> PythonType {
         // Only simple parameters are allowed for literal type:
         // https://peps.python.org/pep-0586/#legal-and-illegal-parameterizations
-        let value = self.get_literal_value_from_param(&s.slice.clone());
-        if value.len() > 1 {
-            todo!("MultiValue literal type is not supported yet")
+        // `Literal[1, 2]` is the union of its values
+        let values = self.get_literal_value_from_param(&s.slice, symbol_table, scope_id);
+        if values.is_empty() {
+            return PythonType::Unknown;
         }
-
-        PythonType::LiteralValue(super::types::KnownValue {
-            literal_value: value.last().unwrap().clone(),
-        })
+        PythonType::union(
+            values
+                .into_iter()
+                .map(|literal_value| PythonType::LiteralValue(KnownValue { literal_value }))
+                .collect(),
+        )
     }
 
-    /// Write a function that takes in an expression which is a parameter to a
-    /// literal type and returns the LiteralValue of the parameter.
-    /// Literal values might contain a tuple, that's why the return type is a
-    /// vector.
-    pub fn get_literal_value_from_param(&self, expr: &Expression) -> Vec<LiteralValue> {
-        let val = match expr {
-            Expression::Constant(c) => {
-                match c.value.clone() {
-                    ast::ConstantValue::Bool(_) => LiteralValue::Bool,
-                    ast::ConstantValue::Int => LiteralValue::Int,
-                    ast::ConstantValue::Float => LiteralValue::Float,
-                    ast::ConstantValue::Str(_) => LiteralValue::Str,
-                    ast::ConstantValue::Bytes => LiteralValue::Bytes,
-                    ast::ConstantValue::None => LiteralValue::None,
-                    // Tuple is illegal if it has parentheses, otherwise it's allowed and the output
-                    // a multiValued type Currently even mypy does not support
-                    // this, who am I to do it? https://mypy-play.net/?mypy=latest&python=3.10&gist=0df0421d5c85f3b75f65a51cae8616ce
-                    ast::ConstantValue::Tuple => {
-                        LiteralValue::Int
-                        // if t.len() == 1 {
-                        //     match t[0].value.clone() {
-                        //         ast::ConstantValue::Bool => LiteralValue::Bool,
-                        //         ast::ConstantValue::Int => LiteralValue::Int,
-                        //         ast::ConstantValue::Float => LiteralValue::Float,
-                        //         ast::ConstantValue::Str => LiteralValue::Str,
-                        //         ast::ConstantValue::Bytes => LiteralValue::Bytes,
-                        //         ast::ConstantValue::None => LiteralValue::None,
-                        //         _ => panic!("Tuple type with illegal parameter"),
-                        //     }
-                        // } else {
-                        //     let literal_values = t
-                        //         .iter()
-                        //         .map(|c| match c.value {
-                        //             ast::ConstantValue::Bool => LiteralValue::Bool,
-                        //             ast::ConstantValue::Int => LiteralValue::Int,
-                        //             ast::ConstantValue::Float => LiteralValue::Float,
-                        //             ast::ConstantValue::Str => LiteralValue::Str,
-                        //             ast::ConstantValue::Bytes => LiteralValue::Bytes,
-                        //             ast::ConstantValue::None => LiteralValue::None,
-                        //             _ => panic!("Tuple type with illegal parameter"),
-                        //         })
-                        //         .collect();
-                        //     return literal_values;
-                        // }
-                    }
-                    // Illegal parameter
-                    ast::ConstantValue::Ellipsis => {
-                        panic!("Literal type with ellipsis value is not supported")
-                    }
-                    ast::ConstantValue::Complex => {
-                        panic!("Literal type with complex value is not supported")
-                    }
-                }
-            }
-            // Only can be enum values
-            Expression::Attribute(a) => {
-                let value = match &a.value {
-                    Expression::Name(n) => &n.id,
-                    _ => panic!("Literal type with attribute value can only be a name"),
-                };
-                LiteralValue::Str
-            }
-            Expression::Subscript(s) => {
-                match &s.value {
-                    Expression::Name(n) => {
-                        if !self.is_literal(n.id.clone()) {
-                            panic!("{}", LITERAL_TYPE_PARAMETER_MSG)
-                        }
-                        // When there is a literal inside a literal we flatten it
-                        return self.get_literal_value_from_param(&s.slice);
-                    }
-                    _ => panic!("{}", LITERAL_TYPE_PARAMETER_MSG),
-                };
-            }
-            // Illegal parameter
+    /// The type of the first parameter of a method when it is not annotated. It is the
+    /// instance that the method is bound to, or the class for class methods. Static
+    /// methods and functions outside of class bodies do not bind their first parameter.
+    fn get_receiver_parameter_type(
+        &self,
+        symbol_table: &SymbolTable,
+        function_scope_id: u32,
+    ) -> Option<PythonType> {
+        let function_scope = symbol_table.get_scope_by_id(function_scope_id)?;
+        let SymbolTableType::Function(function_def) = &function_scope.kind else {
+            return None;
+        };
+        let class_scope = symbol_table.parent_scope(function_scope)?;
+        if !matches!(class_scope.kind, SymbolTableType::Class(_)) {
+            return None;
+        }
+        let class_type = self.get_enclosing_class_type(symbol_table, class_scope.id)?;
+        match method_kind(&function_def.decorator_list) {
+            MethodKind::Static => None,
+            MethodKind::Class => Some(PythonType::Class(class_type)),
+            // The instance of a generic class is specialized with its own type parameters
             _ => {
-                panic!("Literal type with illegal parameter, can only be a constant value or enum")
+                let type_parameters = class_type.type_parameters.clone();
+                Some(PythonType::Instance(InstanceType::new(
+                    class_type,
+                    type_parameters,
+                )))
             }
-        };
-
-        vec![val]
+        }
     }
 
-    pub fn bin_op_result_type(
+    /// The class whose body contains the scope.
+    fn get_enclosing_class_type(
         &self,
-        t1: &PythonType,
-        t2: &PythonType,
-        op: &ast::BinaryOperator,
-    ) -> PythonType {
-        // Dummy
-        t1.clone()
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Option<ClassType> {
+        let class_scope_id = symbol_table.get_enclosing_class_scope_of_scope(scope_id)?;
+        let class_scope = symbol_table.get_scope_by_id(class_scope_id.0)?;
+        let parent_scope = symbol_table.parent_scope(class_scope)?;
+        let class_def = symbol_table.lookup_in_scope(&class_scope.name, parent_scope.id)?;
+        self.get_symbol_type(class_def, symbol_table, None).class()
     }
 
-    pub fn is_literal(&self, name: String) -> bool {
-        name.as_str() == "Literal"
+    /// `Self` is a type var that is bound to the class that it is used in. It is solved
+    /// to the type of the object that a method is accessed through.
+    /// https://typing.readthedocs.io/en/latest/spec/generics.html#use-in-method-signatures
+    fn get_self_type_var(&self, symbol_table: &SymbolTable, scope_id: u32) -> Option<PythonType> {
+        let class_type = self.get_enclosing_class_type(symbol_table, scope_id)?;
+        Some(PythonType::TypeVar(TypeVar {
+            name: SELF_TYPE_VAR.to_string(),
+            bounds: vec![],
+            upper_bound: Some(Box::new(PythonType::Class(class_type))),
+            decl_id: symbol_table.id,
+        }))
     }
 
-    // TODO: If the return type of type parameter then use the passed args to determine it's type
-    // TODO: The any type can be assumed for the type parameters that are not set but we are not
-    // correctly checking if it's set or not.
-    fn get_return_type_of_callable(
+    /// `Callable[[A, B], R]` takes positional arguments of the types A and B and returns R.
+    /// `Callable[..., R]` takes any arguments and `Callable[P, R]` takes the parameters
+    /// that the param spec P stands for.
+    /// https://typing.readthedocs.io/en/latest/spec/callables.html
+    fn handle_callable_type(
         &self,
-        f_type: &CallableType,
-        args: &Vec<ast::Expression>,
+        s: &ast::Subscript,
         symbol_table: &SymbolTable,
         scope_id: u32,
     ) -> PythonType {
-        let ret_type = f_type.return_type.clone();
-
-        let PythonType::TypeVar(ref type_var) = ret_type else {
-            return ret_type;
+        let Expression::Tuple(t) = &s.slice else {
+            return PythonType::Unknown;
         };
+        let [parameters, return_annotation] = t.elements.as_slice() else {
+            return PythonType::Unknown;
+        };
+        let return_type = self.get_annotation_type(return_annotation, symbol_table, scope_id);
+        let signature = match parameters {
+            Expression::List(l) => l
+                .elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| {
+                    CallableArgs::PositionalOnly(CallableParameter::new(
+                        format!("__p{i}"),
+                        self.get_annotation_type(e, symbol_table, scope_id),
+                        false,
+                    ))
+                })
+                .collect(),
+            Expression::Constant(c) if matches!(c.value, ast::ConstantValue::Ellipsis) => {
+                param_spec_signature(PythonType::Any)
+            }
+            Expression::Name(n) => {
+                let is_param_spec = self
+                    .get_type(parameters, symbol_table, scope_id)
+                    .ok()
+                    .and_then(|t| self.get_class_of_operand(&t))
+                    .is_some_and(|c| c.details.name == "ParamSpec");
+                if !is_param_spec {
+                    return PythonType::Unknown;
+                }
+                param_spec_signature(PythonType::TypeVar(TypeVar {
+                    name: n.id.clone(),
+                    bounds: vec![],
+                    upper_bound: None,
+                    decl_id: symbol_table.id,
+                }))
+            }
+            // TODO: `Concatenate` adds parameters in front of a param spec
+            _ => return PythonType::Unknown,
+        };
+        PythonType::Callable(Box::new(CallableType::new(
+            String::new(),
+            signature,
+            return_type,
+            false,
+        )))
+    }
 
-        // If the return type is type var, then check all the passed arguments and find the
-        // argument that was passed in the place of that type var and then use the type of passed
-        // argument here.
-        // If the type var is repeated then make sure all the passed arguments for that type var
-        // have the same type. Otherwise return Typing::Unknown
-        let target_name = &type_var.name;
-
-        let mut values_matching_type_param: Option<PythonType> = None;
-
-        for (index, callable_arg) in f_type.signature.iter().enumerate() {
-            trace!("checking if arg is contains the type var. arg: {callable_arg}, type var: {target_name}");
-            match callable_arg.get_type() {
-                PythonType::TypeVar(arg_type_var) => {
-                    if arg_type_var.name != *target_name {
-                        continue;
+    /// Returns the signature that the arguments of a call are matched against.
+    /// Methods that are accessed on a class or an instance are returned without their
+    /// self parameter and calling a class uses the signature of its `__init__` method.
+    /// Returns None when the called object is not known well enough to check the call.
+    /// Calls of overloaded functions use the first overload that accepts the arguments.
+    /// When no overload does, the signature is returned with the overloads it was
+    /// matched against.
+    pub fn get_call_signature(
+        &self,
+        call: &ast::Call,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Option<CallableType> {
+        let called_type = self.get_type(&call.func, symbol_table, scope_id).ok()?;
+        match called_type {
+            PythonType::Callable(callable) => {
+                if !self.has_known_receiver(&call.func, symbol_table, scope_id) {
+                    return None;
+                }
+                if callable.is_overloaded() {
+                    let candidates = callable.overloads.clone();
+                    return Some(self.select_overload(candidates, call, symbol_table, scope_id));
+                }
+                if callable.name == "_replace" {
+                    if let Some(fields) =
+                        self.get_named_tuple_receiver_fields(call, symbol_table, scope_id)
+                    {
+                        let signature = fields
+                            .into_iter()
+                            .map(|field| {
+                                CallableArgs::Keyword(CallableParameter::new(
+                                    field.name,
+                                    field.python_type,
+                                    true,
+                                ))
+                            })
+                            .collect();
+                        return Some(CallableType::new(
+                            callable.name.clone(),
+                            signature,
+                            callable.return_type.clone(),
+                            false,
+                        ));
                     }
-                    let passed_arg = args.get(index).expect("arg not found");
-                    let passed_arg_type = self
-                        .get_type(passed_arg, symbol_table, scope_id)
-                        .expect("cannot get type for parameter");
-                    match values_matching_type_param {
-                        Some(ref v) => {
-                            // TODO: This should not be an equality check but assignable check
-                            // Also the condition in block below.
-                            // https://github.com/python/typing/blob/main/conformance/tests/generics_basic.py#L68
-                            if *v != passed_arg_type {
-                                error!("Two different types were passed for one type parameter first: {v} second: {passed_arg_type}");
-                                return PythonType::Unknown;
-                            }
-                        }
-                        None => values_matching_type_param = Some(passed_arg_type),
-                    };
                 }
-                PythonType::Class(class_type) => {
-                    for (type_param_index, type_parameter) in
-                        class_type.type_parameters.iter().enumerate()
+                if callable.name == "replace"
+                    && callable
+                        .signature
+                        .iter()
+                        .any(|arg| matches!(arg, CallableArgs::KwArgs(_)))
+                {
+                    if let Some(signature) =
+                        self.get_replace_signature(&callable, call, symbol_table, scope_id)
                     {
-                        let Some(type_var) = type_parameter.as_type_var() else {
-                            continue;
-                        };
-                        if type_var.name != *target_name {
-                            continue;
-                        }
-                        // The instance that is passed in place of this class has the type of that
-                        // type var
-                        let passed_arg = args.get(index).expect("arg not found");
-                        let passed_arg_type = self
-                            .get_type(passed_arg, symbol_table, scope_id)
-                            .expect("cannot get type for parameter");
-
-                        let Some(passed_arg_instance) = passed_arg_type.as_instance() else {
-                            error!("expected an instance to be passed for a class type {class_type} but got {passed_arg_type}");
-                            continue;
-                        };
-
-                        let final_type = passed_arg_instance
-                            .specialized_type_parameters
-                            .get(type_param_index)
-                            .unwrap_or(&PythonType::Any)
-                            .clone();
-
-                        match values_matching_type_param {
-                            Some(ref v) => {
-                                if *v != final_type {
-                                    error!("Two different types were passed for one type parameter first: {v} second: {passed_arg_type}");
-                                    return PythonType::Unknown;
-                                }
-                            }
-                            None => values_matching_type_param = Some(final_type),
-                        };
+                        return Some(signature);
                     }
-
-                    for (type_param_index, type_parameter) in
-                        class_type.specialized.iter().enumerate()
-                    {
-                        let Some(type_var) = type_parameter.as_type_var() else {
-                            continue;
-                        };
-                        if type_var.name != *target_name {
-                            continue;
-                        }
-                        // The instance that is passed in place of this class has the type of that
-                        // type var
-                        let passed_arg = args.get(index).expect("arg not found");
-                        let passed_arg_type = self
-                            .get_type(passed_arg, symbol_table, scope_id)
-                            .expect("cannot get type for parameter");
-
-                        let Some(passed_arg_instance) = passed_arg_type.as_instance() else {
-                            continue;
-                        };
-
-                        let final_type = passed_arg_instance
-                            .specialized_type_parameters
-                            .get(type_param_index)
-                            .unwrap_or(&PythonType::Any)
-                            .clone();
-
-                        match values_matching_type_param {
-                            Some(ref v) => {
-                                if *v != final_type {
-                                    error!("Two different types were passed for one type parameter first: {v} second: {passed_arg_type}");
-                                    return PythonType::Unknown;
-                                }
-                            }
-                            None => values_matching_type_param = Some(final_type),
-                        };
+                }
+                Some(*callable)
+            }
+            PythonType::Class(c) => {
+                if let Some(fields) = self.get_named_tuple_fields(&c) {
+                    let signature = fields
+                        .into_iter()
+                        .map(|field| {
+                            CallableArgs::Positional(CallableParameter::new(
+                                field.name,
+                                field.python_type,
+                                field.has_default,
+                            ))
+                        })
+                        .collect();
+                    return Some(CallableType::new(
+                        c.details.name.clone(),
+                        signature,
+                        PythonType::Class(c),
+                        false,
+                    ));
+                }
+                if let Some(fields) = self.get_typed_dict_fields(&c) {
+                    let signature = fields
+                        .into_iter()
+                        .map(|field| {
+                            CallableArgs::Keyword(CallableParameter::new(
+                                field.name,
+                                field.python_type,
+                                !field.required,
+                            ))
+                        })
+                        .collect();
+                    return Some(CallableType::new(
+                        c.details.name.clone(),
+                        signature,
+                        PythonType::Class(c),
+                        false,
+                    ));
+                }
+                if c.details.special {
+                    return None;
+                }
+                let bases: Vec<ClassType> = self
+                    .get_base_classes(&c)
+                    .into_iter()
+                    .filter_map(|base| base.class())
+                    .filter(|base| base.details.qual_name != "builtins.object")
+                    .collect();
+                let defines = |class: &ClassType, name: &str| {
+                    self.get_dec_symbol_table(&class.details.declaration_path)
+                        .lookup_attribute(name, class.details.class_scope_id)
+                        .is_some()
+                        || self.get_synthesized_dataclass_member(class, name).is_some()
+                };
+                // TODO: Classes that define `__new__` or use a metaclass with `__call__` are
+                // constructed through those methods.
+                if bases.iter().any(|base| defines(base, "__new__")) {
+                    return None;
+                }
+                let init_class = bases.iter().find(|base| defines(base, "__init__"))?;
+                let init_symbol_table =
+                    self.get_dec_symbol_table(&init_class.details.declaration_path);
+                let init = self.lookup_on_class(&init_symbol_table, init_class, "__init__")?;
+                match self.bind_member(init, &PythonType::Class(c.clone())) {
+                    PythonType::Callable(init) if init.is_overloaded() => {
+                        let candidates = init.overloads.clone();
+                        Some(self.select_overload(candidates, call, symbol_table, scope_id))
                     }
+                    PythonType::Callable(init) => Some(*init),
+                    _ => None,
                 }
-                _ => continue,
             }
+            PythonType::Instance(i) => {
+                let call_method = self.get_call_method(&i, symbol_table)?;
+                if call_method.is_overloaded() {
+                    let candidates = call_method.overloads.clone();
+                    return Some(self.select_overload(candidates, call, symbol_table, scope_id));
+                }
+                Some(call_method)
+            }
+            _ => None,
         }
-        if values_matching_type_param.is_some() {
-            return values_matching_type_param
-                .take()
-                .expect("type parameter was not in passed args");
-        }
-
-        error!("cannot find the type var that function returns in the passed args. args: {args:?} type var: {target_name:?}");
-
-        ret_type
     }
 
-    fn lookup_on_class(
+    /// Whether the called expression is a function or an attribute of an object whose
+    /// type is known. Methods of other objects are not known well enough to check calls.
+    fn has_known_receiver(
         &self,
+        func: &Expression,
         symbol_table: &SymbolTable,
-        c: &ClassType,
-        method_name: &str,
-    ) -> Option<PythonType> {
-        let class_symbol_table = self.get_dec_symbol_table(&c.details.declaration_path);
-        let class_scope = c.details.class_scope_id;
-        // TODO: Probably should implement mro here
-        // Try to find on the class and it's base classes.
-        let bases = self.get_base_classes(c);
-        let symbol = class_symbol_table.lookup_attribute(method_name, class_scope);
-        if symbol.is_none() {
-            for base in bases {
-                let base_class = base.expect_class();
-                let class_symbol_table =
-                    self.get_dec_symbol_table(&base_class.details.declaration_path);
-                if let Some(attribute_on_base) = class_symbol_table
-                    .lookup_attribute(method_name, base_class.details.class_scope_id)
-                {
-                    return Some(self.get_symbol_type(
-                        attribute_on_base,
-                        &class_symbol_table,
-                        None,
-                    ));
+        scope_id: u32,
+    ) -> bool {
+        let Expression::Attribute(attribute) = func else {
+            return true;
+        };
+        matches!(
+            self.get_type(&attribute.value, symbol_table, scope_id),
+            Ok(PythonType::Class(_) | PythonType::Instance(_) | PythonType::Module(_))
+        )
+    }
+
+    /// The type of a class member that is accessed through the receiver, which is an
+    /// instance or a class. Methods are bound to the receiver and properties evaluate to
+    /// the type that their getter returns.
+    fn bind_member(&self, member: PythonType, receiver: &PythonType) -> PythonType {
+        match member {
+            PythonType::Callable(c) => {
+                let is_property = c.method_kind == MethodKind::Property;
+                let bound = self.bind_self_parameter(*c, receiver);
+                if is_property {
+                    bound.return_type
+                } else {
+                    PythonType::Callable(Box::new(bound))
                 }
             }
+            member => member,
         }
-
-        symbol.map(|node| self.get_symbol_type(node, symbol_table, None))
     }
 
-    fn get_function_signature(
-        &self,
-        arguments: &ast::Arguments,
-        symbol_table: &symbol_table::SymbolTable,
-        scope_id: u32,
-    ) -> Vec<CallableArgs> {
-        let mut signature = Vec::with_capacity(arguments.len());
-        for argument in arguments.posonlyargs.iter() {
-            if let Some(type_annotation) = &argument.annotation {
-                signature.push(CallableArgs::PositionalOnly(self.get_annotation_type(
-                    type_annotation,
-                    symbol_table,
-                    scope_id,
-                )));
-            } else {
-                signature.push(CallableArgs::PositionalOnly(self.get_name_type(
-                    &argument.arg,
-                    Some(argument.node.end),
-                    symbol_table,
-                    scope_id,
-                )));
+    /// The type of a class member that is accessed through the class object. Only class
+    /// methods are bound, other methods keep their self parameter and `Self` is solved
+    /// to the class.
+    fn bind_class_member(&self, member: PythonType, receiver: &PythonType) -> PythonType {
+        match member {
+            PythonType::Callable(c) if c.method_kind != MethodKind::Class => {
+                let mut solution = TypeVarSolution::new();
+                solution.insert(SELF_TYPE_VAR.to_string(), receiver.clone());
+                PythonType::Callable(Box::new(
+                    self.apply_type_var_solution_to_callable(&c, &solution),
+                ))
             }
+            member => self.bind_member(member, receiver),
         }
-        for positional in arguments.args.iter() {
-            if let Some(type_annotation) = &positional.annotation {
-                signature.push(CallableArgs::Positional(self.get_annotation_type(
-                    type_annotation,
-                    symbol_table,
-                    scope_id,
-                )));
-            } else {
-                signature.push(CallableArgs::Positional(self.get_name_type(
-                    &positional.arg,
-                    Some(positional.node.end),
-                    symbol_table,
-                    scope_id,
-                )));
-            }
+    }
+
+    /// Binds a method to the object it is accessed through. The self parameter, or the
+    /// cls parameter of a class method, is removed and the type vars in its annotation
+    /// and `Self` are solved to the type of the receiver. Static methods and functions
+    /// that are not declared in a class body keep their first parameter.
+    fn bind_self_parameter(&self, callable: CallableType, receiver: &PythonType) -> CallableType {
+        let binds_first = matches!(
+            callable.method_kind,
+            MethodKind::Instance | MethodKind::Class | MethodKind::Property
+        ) && callable.signature.first().is_some_and(|arg| {
+            matches!(
+                arg,
+                CallableArgs::PositionalOnly(_) | CallableArgs::Positional(_)
+            )
+        });
+        if !binds_first {
+            return callable;
         }
-        for argument in arguments.kwonlyargs.iter() {
-            if let Some(type_annotation) = &argument.annotation {
-                signature.push(CallableArgs::Keyword(self.get_annotation_type(
-                    type_annotation,
-                    symbol_table,
-                    scope_id,
-                )));
-            } else {
-                signature.push(CallableArgs::Keyword(PythonType::Unknown));
+        let overloads = callable
+            .overloads
+            .iter()
+            .map(|overload| self.bind_self_parameter(overload.clone(), receiver))
+            .collect();
+        let mut unbound = callable;
+        unbound.overloads = vec![];
+        let self_parameter = unbound.signature.remove(0);
+        let mut solution = TypeVarSolution::new();
+        solution.insert(SELF_TYPE_VAR.to_string(), receiver.clone());
+        match self_parameter.get_type() {
+            // `cls: type[T]`
+            PythonType::Class(c) if c.details.qual_name == "builtins.type" => {
+                if let Some(class_parameter) = c.specialized.first() {
+                    self.solve_type_vars(class_parameter, receiver, &mut solution);
+                }
             }
+            self_type => self.solve_type_vars(self_type, receiver, &mut solution),
         }
+        let mut bound = self.apply_type_var_solution_to_callable(&unbound, &solution);
+        // A bound method is called like a function
+        bound.method_kind = MethodKind::Function;
+        bound.overloads = overloads;
+        bound
+    }
 
-        if let Some(vararg) = &arguments.vararg {
-            if let Some(type_annotation) = &vararg.annotation {
-                signature.push(CallableArgs::Args(self.get_annotation_type(
-                    type_annotation,
-                    symbol_table,
-                    scope_id,
-                )));
-            } else {
-                signature.push(CallableArgs::Args(PythonType::Unknown));
-            }
-        }
-        if let Some(kwarg) = &arguments.kwarg {
-            if let Some(type_annotation) = &kwarg.annotation {
-                signature.push(CallableArgs::KwArgs(self.get_annotation_type(
-                    type_annotation,
-                    symbol_table,
-                    scope_id,
-                )));
-            } else {
-                signature.push(CallableArgs::KwArgs(PythonType::Unknown));
+    /// The first overload that accepts the arguments of the call, or the first candidate
+    /// with all the candidates as its overloads when none of them does.
+    fn select_overload(
+        &self,
+        candidates: Vec<CallableType>,
+        call: &ast::Call,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> CallableType {
+        match self.match_overloads(&candidates, call, symbol_table, scope_id) {
+            Some(matched) => candidates[matched[0]].clone(),
+            None => {
+                let mut signature = candidates[0].clone();
+                signature.overloads = candidates;
+                signature
             }
         }
-
-        signature
     }
 
-    // TODO(coroutine_annotation): These two are very similar. Maybe should be presented in another
-    // way. Async version only needs the return type to be a coroutine.
-    fn get_function_type(
+    /// Evaluates the overloads of a function against the arguments of a call in the
+    /// order of their declaration and returns the indices of the overloads that the call
+    /// evaluates to. That is the first overload that accepts the arguments, or every
+    /// accepting overload when an argument is unknown. Arguments of union types that no
+    /// overload accepts are expanded and each member must be accepted by an overload.
+    /// Returns None when no overload accepts the arguments.
+    /// https://typing.readthedocs.io/en/latest/spec/overload.html#overload-call-evaluation
+    fn match_overloads(
         &self,
-        symbol_table: &symbol_table::SymbolTable,
-        f: &symbol_table::Function,
-        arguments_scope_id: u32,
-    ) -> PythonType {
-        // TODO: handle default values
+        candidates: &[CallableType],
+        call: &ast::Call,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Option<Vec<usize>> {
+        let arguments: Vec<&Expression> = call
+            .args
+            .iter()
+            .chain(call.keywords.iter().map(|k| &k.value))
+            .collect();
+        let argument_types: Vec<PythonType> = arguments
+            .iter()
+            .map(|arg| self.get_argument_type(arg, symbol_table, scope_id))
+            .collect();
+        let accepting = |argument_types: &[PythonType]| -> Vec<usize> {
+            candidates
+                .iter()
+                .enumerate()
+                .filter(|(_, candidate)| {
+                    let Some(bound) = match_arguments(&candidate.signature, call) else {
+                        return false;
+                    };
+                    bound.iter().all(|(parameter, arg)| {
+                        let index = arguments
+                            .iter()
+                            .position(|a| std::ptr::eq(*a, *arg))
+                            .expect("bound arguments are arguments of the call");
+                        self.is_assignable(&argument_types[index], parameter.get_type())
+                    })
+                })
+                .map(|(index, _)| index)
+                .collect()
+        };
 
-        let name = f.function_node.name;
-        let signature =
-            self.get_function_signature(&f.function_node.args, symbol_table, arguments_scope_id);
-        let return_type =
-            f.function_node
-                .returns
-                .clone()
-                .map_or(PythonType::Unknown, |type_annotation| {
-                    self.get_annotation_type(&type_annotation, symbol_table, arguments_scope_id)
-                });
-        let file = self
-            .build_manager
-            .files
-            .get(&f.declaration_path.symbol_table_id)
-            .unwrap();
-        PythonType::Callable(Box::new(CallableType::new(
-            intern_lookup(name).to_string(),
-            signature,
-            return_type,
-            false,
-        )))
+        let matched = accepting(&argument_types);
+        if let Some(&first) = matched.first() {
+            let has_unknown_argument = argument_types.iter().any(|t| {
+                matches!(t, PythonType::Any | PythonType::Unknown) || self.is_any_class(t)
+            });
+            return Some(if has_unknown_argument {
+                matched
+            } else {
+                vec![first]
+            });
+        }
+        let (index, union) = argument_types
+            .iter()
+            .enumerate()
+            .find(|(_, t)| t.union_members().len() > 1)?;
+        let mut expanded = vec![];
+        for member in union.union_members() {
+            let mut member_types = argument_types.clone();
+            member_types[index] = member;
+            let first = *accepting(&member_types).first()?;
+            if !expanded.contains(&first) {
+                expanded.push(first);
+            }
+        }
+        Some(expanded)
     }
 
-    fn get_async_function_type(
+    /// The type of an argument of a call. Constants are typed as literals so that they
+    /// match overloads that take literal types.
+    fn get_argument_type(
         &self,
-        symbol_table: &symbol_table::SymbolTable,
-        f: &symbol_table::AsyncFunction,
+        arg: &Expression,
+        symbol_table: &SymbolTable,
         scope_id: u32,
     ) -> PythonType {
-        let arguments = f.function_node.args.clone();
-        let name = f.function_node.name;
-        let signature = self.get_function_signature(&f.function_node.args, symbol_table, scope_id);
-        let return_type = f
-            .function_node
-            .returns
-            .clone()
-            .map_or(PythonType::Unknown, |type_annotation| {
-                self.get_annotation_type(&type_annotation, symbol_table, scope_id)
-            });
-
-        let file = self
-            .build_manager
-            .files
-            .get(&f.declaration_path.symbol_table_id)
-            .unwrap();
-        PythonType::Callable(Box::new(CallableType::new(
-            intern_lookup(name).to_string(),
-            signature,
-            PythonType::Coroutine(Box::new(types::CoroutineType {
-                return_type,
-                send_type: PythonType::Any,
-                yield_type: PythonType::Any,
-            })),
-            true,
-        )))
-    }
-
-    fn get_base_classes(&self, c: &ClassType) -> Vec<PythonType> {
-        let mut super_classes = vec![];
-        super_classes.push(PythonType::Class(c.clone()));
-        for super_class in &c.base_classes {
-            super_classes.push(PythonType::Class(super_class.clone()));
+        match self.get_literal_type_of_constant(arg) {
+            Some(literal_type) => literal_type,
+            None => self
+                .get_type(arg, symbol_table, scope_id)
+                .unwrap_or(PythonType::Unknown),
         }
-        let object_class = self.get_builtin_type("object").expect("object not found");
-
-        super_classes.push(object_class.clone());
+    }
 
-        super_classes
+    /// Returns the literal type of a constant. Constants are inferred as their class, but
+    /// when they are assigned to a declared literal type their value is used instead.
+    pub fn get_literal_type_of_constant(&self, expr: &Expression) -> Option<PythonType> {
+        let Expression::Constant(c) = expr else {
+            return None;
+        };
+        let literal_value = match &c.value {
+            ast::ConstantValue::Bool(b) => LiteralValue::Bool(*b),
+            ast::ConstantValue::Int(i) => LiteralValue::Int(i.clone()),
+            ast::ConstantValue::Str(s) => LiteralValue::Str(s.clone()),
+            ast::ConstantValue::Bytes(b) => LiteralValue::Bytes(b.clone()),
+            _ => return None,
+        };
+        Some(PythonType::LiteralValue(KnownValue { literal_value }))
     }
 
-    // Resolving all type parameters in a python type based on the given type parameters dict
-    fn resolve_generics(
+    /// Write a function that takes in an expression which is a parameter to a
+    /// literal type and returns the LiteralValue of the parameter.
+    /// Literal values might contain a tuple, that's why the return type is a
+    /// vector.
+    pub fn get_literal_value_from_param(
         &self,
-        python_type: &PythonType,
-        type_parameters: &Vec<PythonType>,
-        specialized_types: &Vec<PythonType>,
-    ) -> PythonType {
-        match &python_type {
-            PythonType::None => todo!(),
-            PythonType::Unknown => todo!(),
-            PythonType::Any => todo!(),
-            PythonType::LiteralValue(known_value) => todo!(),
-            PythonType::Module(module_ref) => todo!(),
-            PythonType::MultiValue(vec) => todo!(),
-            PythonType::Callable(callable_type) => todo!(),
-            PythonType::Coroutine(coroutine_type) => todo!(),
-            PythonType::Class(class_type) => {
-                let mut resolved = vec![];
-                for tp in type_parameters.iter() {
-                    let specialized_type =
-                        self.resolve_generics(tp, type_parameters, specialized_types);
-                    resolved.push(specialized_type);
-                }
-
-                let mut new_class = class_type.clone();
-                new_class.specialized = resolved;
-                PythonType::Class(new_class)
-            }
-            PythonType::Instance(instance_type) => todo!(),
-            PythonType::Optional(python_type) => todo!(),
-            PythonType::TypeVar(type_var) => {
-                let name = type_var.name.as_str();
-                let mut index: Option<usize> = None;
-                for (i, tp) in type_parameters.iter().enumerate() {
-                    let PythonType::TypeVar(ref tp) = tp else {
-                        continue;
-                    };
-                    if tp.name == name {
-                        index = Some(i);
+        expr: &Expression,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Vec<LiteralValue> {
+        let val = match expr {
+            Expression::Tuple(t) => {
+                return t
+                    .elements
+                    .iter()
+                    .flat_map(|e| self.get_literal_value_from_param(e, symbol_table, scope_id))
+                    .collect();
+            }
+            Expression::Constant(c) => {
+                match c.value.clone() {
+                    ast::ConstantValue::Bool(b) => LiteralValue::Bool(b),
+                    ast::ConstantValue::Int(i) => LiteralValue::Int(i),
+                    ast::ConstantValue::Str(s) => LiteralValue::Str(s),
+                    ast::ConstantValue::Bytes(b) => LiteralValue::Bytes(b),
+                    ast::ConstantValue::None => LiteralValue::None,
+                    // Tuple is illegal if it has parentheses, otherwise it's allowed and the output
+                    // a multiValued type Currently even mypy does not support
+                    // this, who am I to do it? https://mypy-play.net/?mypy=latest&python=3.10&gist=0df0421d5c85f3b75f65a51cae8616ce
+                    // Floats, complex numbers and ellipsis are illegal parameters
+                    ast::ConstantValue::Tuple
+                    | ast::ConstantValue::Ellipsis
+                    | ast::ConstantValue::Float(_)
+                    | ast::ConstantValue::Complex { .. } => {
+                        error!("{}", LITERAL_TYPE_PARAMETER_MSG);
+                        return vec![];
                     }
                 }
-
-                if let Some(pos) = index {
-                    return specialized_types[pos].clone();
+            }
+            // Only can be enum values
+            Expression::Attribute(a) => match self.get_type(expr, symbol_table, scope_id) {
+                Ok(PythonType::LiteralValue(KnownValue {
+                    literal_value: literal_value @ LiteralValue::Enum(_),
+                })) => literal_value,
+                _ => {
+                    error!(
+                        "Literal type with attribute {} is not an enum member",
+                        a.attr
+                    );
+                    return vec![];
                 }
-
-                error!("Did not find type parameter {name}");
-                python_type.clone()
+            },
+            Expression::Subscript(s) => {
+                match &s.value {
+                    Expression::Name(n) => {
+                        if !self.is_literal(n.id.clone()) {
+                            panic!("{}", LITERAL_TYPE_PARAMETER_MSG)
+                        }
+                        // When there is a literal inside a literal we flatten it
+                        return self.get_literal_value_from_param(&s.slice, symbol_table, scope_id);
+                    }
+                    _ => panic!("{}", LITERAL_TYPE_PARAMETER_MSG),
+                };
             }
-        }
-    }
+            // Negative integers are written with a unary minus
+            Expression::UnaryOp(u) if matches!(u.op, ast::UnaryOperator::USub) => {
+                match &u.operand {
+                    Expression::Constant(c) => match &c.value {
+                        ast::ConstantValue::Int(i) => LiteralValue::Int(-i),
+                        _ => panic!(
+                        "Literal type with illegal parameter, can only be a constant value or enum"
+                    ),
+                    },
+                    _ => panic!(
+                        "Literal type with illegal parameter, can only be a constant value or enum"
+                    ),
+                }
+            }
+            // Illegal parameter
+            _ => {
+                panic!("Literal type with illegal parameter, can only be a constant value or enum")
+            }
+        };
 
-    fn get_dec_symbol_table(&self, decl_path: &DeclarationPath) -> Arc<SymbolTable> {
-        let table_id = decl_path.symbol_table_id;
-        return self.build_manager.get_symbol_table_by_id(&table_id);
+        vec![val]
     }
 
-    fn get_symbol_table(&self, id: &Id) -> Arc<SymbolTable> {
-        return self.build_manager.get_symbol_table_by_id(id);
-    }
+    /// Evaluates the type of a binary operation the way python does at runtime.
+    /// First `left.__op__(right)` is tried and if the left operand does not support
+    /// the right operand then `right.__rop__(left)` is tried.
+    /// If the right operand is a subclass of the left operand the reflected method
+    /// is tried first.
+    /// https://docs.python.org/3/reference/datamodel.html#object.__radd__
+    pub fn bin_op_result_type(
+        &self,
+        t1: &PythonType,
+        t2: &PythonType,
+        op: &ast::BinaryOperator,
+        symbol_table: &SymbolTable,
+    ) -> Result<PythonType> {
+        let (Some(left_class), Some(right_class)) =
+            (self.get_class_of_operand(t1), self.get_class_of_operand(t2))
+        else {
+            // TODO: Unions, optionals and None operands need narrowing to be
+            // checked without false positives.
+            return Ok(PythonType::Unknown);
+        };
+        // Concatenated tuples keep the types of their elements
+        if matches!(op, ast::BinaryOperator::Add) {
+            if let (Some(left), Some(right)) =
+                (self.get_tuple_elements(t1), self.get_tuple_elements(t2))
+            {
+                let elements: Vec<PythonType> = left.into_iter().chain(right).collect();
+                if elements.iter().filter(|e| e.is_unbounded()).count() <= 1 {
+                    if let Some(tuple) = self.create_tuple_instance(elements) {
+                        return Ok(tuple);
+                    }
+                }
+            }
+        }
+        let (method, reflected_method) = binary_operator_methods(op);
+
+        let reflected_first =
+            !left_class.type_equal(&right_class) && self.is_subclass(&right_class, &left_class);
+        let attempts = if reflected_first {
+            [
+                (&right_class, reflected_method, t1),
+                (&left_class, method, t2),
+            ]
+        } else {
+            [
+                (&left_class, method, t2),
+                (&right_class, reflected_method, t1),
+            ]
+        };
+        for (class, method_name, operand) in attempts {
+            if let Some(result) =
+                self.call_operator_method(symbol_table, class, method_name, Some(operand))
+            {
+                return Ok(result);
+            }
+        }
+
+        // Classes and their instances are represented with the same type, so `int | None`
+        // cannot be told apart from a bitwise or on values.
+        if matches!(op, ast::BinaryOperator::BitOr) {
+            return Ok(PythonType::Unknown);
+        }
+        bail!(TypeIssue(format!(
+            "Operator \"{op}\" not supported for types \"{t1}\" and \"{t2}\""
+        )))
+    }
+
+    /// Evaluates `-x`, `+x` and `~x` using `__neg__`, `__pos__` and `__invert__`.
+    pub fn unary_op_result_type(
+        &self,
+        operand: &PythonType,
+        op: &ast::UnaryOperator,
+        symbol_table: &SymbolTable,
+    ) -> Result<PythonType> {
+        let method_name = match op {
+            ast::UnaryOperator::UAdd => "__pos__",
+            ast::UnaryOperator::USub => "__neg__",
+            ast::UnaryOperator::Invert => "__invert__",
+            ast::UnaryOperator::Not => {
+                return Ok(self.get_builtin_type("bool").expect("typeshed"));
+            }
+        };
+        let Some(class) = self.get_class_of_operand(operand) else {
+            return Ok(PythonType::Unknown);
+        };
+        match self.call_operator_method(symbol_table, &class, method_name, None) {
+            Some(result) => Ok(result),
+            None => bail!(TypeIssue(format!(
+                "Operator \"{op}\" not supported for type \"{operand}\""
+            ))),
+        }
+    }
+
+    /// Evaluates a chain of comparisons like `a < b <= c`.
+    /// Each pair is compared with the rich comparison methods and the result of the
+    /// chain is the union of the results of every comparison.
+    pub fn compare_result_type(
+        &self,
+        compare: &ast::Compare,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Result<PythonType> {
+        let mut left = self
+            .get_type(&compare.left, symbol_table, scope_id)
+            .unwrap_or(PythonType::Unknown);
+        let mut results: Vec<PythonType> = vec![];
+        for (op, comparator) in compare.ops.iter().zip(compare.comparators.iter()) {
+            let right = self
+                .get_type(comparator, symbol_table, scope_id)
+                .unwrap_or(PythonType::Unknown);
+            let result = self.comparison_result_type(&left, &right, op, symbol_table)?;
+            if !results.contains(&result) {
+                results.push(result);
+            }
+            left = right;
+        }
+
+        Ok(match results.len() {
+            0 => PythonType::Unknown,
+            1 => results.remove(0),
+            _ => PythonType::MultiValue(results),
+        })
+    }
+
+    /// Evaluates a single comparison. Rich comparisons try the method on the left
+    /// operand first and then the reflected method on the right operand.
+    /// https://docs.python.org/3/reference/datamodel.html#object.__lt__
+    fn comparison_result_type(
+        &self,
+        left: &PythonType,
+        right: &PythonType,
+        op: &ast::ComparisonOperator,
+        symbol_table: &SymbolTable,
+    ) -> Result<PythonType> {
+        let bool_type = self.get_builtin_type("bool").expect("typeshed");
+        let (method, reflected_method) = match op {
+            ast::ComparisonOperator::Eq => ("__eq__", "__eq__"),
+            ast::ComparisonOperator::NotEq => ("__ne__", "__ne__"),
+            ast::ComparisonOperator::Lt => ("__lt__", "__gt__"),
+            ast::ComparisonOperator::LtE => ("__le__", "__ge__"),
+            ast::ComparisonOperator::Gt => ("__gt__", "__lt__"),
+            ast::ComparisonOperator::GtE => ("__ge__", "__le__"),
+            // Identity checks cannot be overridden
+            ast::ComparisonOperator::Is | ast::ComparisonOperator::IsNot => {
+                return Ok(bool_type);
+            }
+            // The result of `in` is always converted to bool. When the container does not
+            // define `__contains__` python falls back to iterating it.
+            ast::ComparisonOperator::In | ast::ComparisonOperator::NotIn => {
+                if let Some(container) = self.get_class_of_operand(right) {
+                    if self
+                        .lookup_method_overloads(&container, "__contains__")
+                        .is_empty()
+                    {
+                        return Ok(bool_type);
+                    }
+                    if self
+                        .call_operator_method(symbol_table, &container, "__contains__", Some(left))
+                        .is_none()
+                    {
+                        bail!(TypeIssue(format!(
+                            "Operator \"{op}\" not supported for types \"{left}\" and \"{right}\""
+                        )));
+                    }
+                }
+                return Ok(bool_type);
+            }
+        };
+
+        let (Some(left_class), Some(right_class)) = (
+            self.get_class_of_operand(left),
+            self.get_class_of_operand(right),
+        ) else {
+            return Ok(match op {
+                ast::ComparisonOperator::Eq | ast::ComparisonOperator::NotEq => bool_type,
+                _ => PythonType::Unknown,
+            });
+        };
+        if let Some(result) =
+            self.call_operator_method(symbol_table, &left_class, method, Some(right))
+        {
+            return Ok(result);
+        }
+        if let Some(result) =
+            self.call_operator_method(symbol_table, &right_class, reflected_method, Some(left))
+        {
+            return Ok(result);
+        }
+        // `==` and `!=` fall back to identity comparison
+        if matches!(
+            op,
+            ast::ComparisonOperator::Eq | ast::ComparisonOperator::NotEq
+        ) {
+            return Ok(bool_type);
+        }
+        bail!(TypeIssue(format!(
+            "Operator \"{op}\" not supported for types \"{left}\" and \"{right}\""
+        )))
+    }
+
+    /// Calls the operator method on the class with an optional operand and returns the
+    /// return type of the first overload that accepts the operand.
+    /// Returns None if the method does not exist or the operand is not accepted.
+    fn call_operator_method(
+        &self,
+        symbol_table: &SymbolTable,
+        class: &ClassType,
+        method_name: &str,
+        operand: Option<&PythonType>,
+    ) -> Option<PythonType> {
+        let receiver = PythonType::Class(class.clone());
+        for overload in self.lookup_method_overloads(class, method_name) {
+            // Overloads can restrict the type of self, e.g. `self: LiteralString`
+            let Some(self_parameter) = overload.signature.first() else {
+                continue;
+            };
+            if !self.is_assignable(&receiver, self_parameter.get_type()) {
+                continue;
+            }
+            // The type vars of the class are solved by its type arguments and the ones of
+            // the method by the operand, e.g. `list[_S | _T]` in `list.__add__`
+            let mut solution = TypeVarSolution::new();
+            solution.insert(SELF_TYPE_VAR.to_string(), receiver.clone());
+            self.add_class_type_arguments(class, &class.specialized, &mut solution);
+            if let Some(operand) = operand {
+                let Some(parameter) = overload.signature.get(1) else {
+                    continue;
+                };
+                let parameter_type = self.apply_type_var_solution(parameter.get_type(), &solution);
+                if !self.is_assignable(operand, &parameter_type) {
+                    continue;
+                }
+                self.solve_type_vars(&parameter_type, operand, &mut solution);
+            }
+            let mut type_vars = vec![];
+            collect_type_vars(&overload.return_type, &mut type_vars);
+            for type_var in type_vars {
+                solution
+                    .entry(type_var.name.clone())
+                    .or_insert(PythonType::Unknown);
+            }
+            return Some(self.apply_type_var_solution(&overload.return_type, &solution));
+        }
+        None
+    }
+
+    /// Returns the class that the methods of a value are looked up on.
+    fn get_class_of_operand(&self, operand: &PythonType) -> Option<ClassType> {
+        match operand {
+            PythonType::Class(c) => Some(c.clone()),
+            // The type arguments of the instance specialize the methods of its class
+            PythonType::Instance(i) if i.class_type.specialized.is_empty() => {
+                let mut class = i.class_type.clone();
+                class.specialized = i.specialized_type_parameters.clone();
+                Some(class)
+            }
+            PythonType::Instance(i) => Some(i.class_type.clone()),
+            PythonType::LiteralValue(value) => {
+                let class_name = match value.literal_value {
+                    LiteralValue::Bool(_) => "bool",
+                    LiteralValue::Int(_) => "int",
+                    LiteralValue::Str(_) => "str",
+                    LiteralValue::Bytes(_) => "bytes",
+                    LiteralValue::None => return None,
+                    LiteralValue::Enum(ref member) => return Some(*member.class.clone()),
+                };
+                self.get_builtin_type(class_name)?.class()
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks whether a value of type source can be used where target is expected.
+    /// Any and Unknown are assignable in both directions.
+    /// https://typing.readthedocs.io/en/latest/spec/concepts.html#assignable
+    pub fn is_assignable(&self, source: &PythonType, target: &PythonType) -> bool {
+        // typeshed declares `Any` as a class
+        let is_any_class = |t: &PythonType| {
+            self.get_class_of_operand(t)
+                .is_some_and(|c| c.details.qual_name == "typing.Any")
+        };
+        if is_any_class(source) || is_any_class(target) {
+            return true;
+        }
+        match (source, target) {
+            (PythonType::Unknown | PythonType::Any | PythonType::Never, _)
+            | (_, PythonType::Unknown | PythonType::Any) => true,
+            // A union is assignable when every member is assignable
+            (PythonType::MultiValue(sources), _) => {
+                sources.iter().all(|s| self.is_assignable(s, target))
+            }
+            (PythonType::Optional(s), _) => {
+                self.is_assignable(&PythonType::None, target) && self.is_assignable(s, target)
+            }
+            (_, PythonType::MultiValue(targets)) => {
+                targets.iter().any(|t| self.is_assignable(source, t))
+            }
+            (_, PythonType::Optional(t)) => {
+                self.is_assignable(source, &PythonType::None) || self.is_assignable(source, t)
+            }
+            (PythonType::TypeVar(s), PythonType::TypeVar(t)) if s.name == t.name => true,
+            (_, PythonType::TypeVar(t)) => {
+                if let Some(upper_bound) = &t.upper_bound {
+                    self.is_assignable(source, upper_bound)
+                } else {
+                    t.bounds.is_empty() || t.bounds.iter().any(|b| self.is_assignable(source, b))
+                }
+            }
+            // Without a bound a type var can only be used as object
+            (PythonType::TypeVar(s), _) => {
+                if let Some(upper_bound) = &s.upper_bound {
+                    self.is_assignable(upper_bound, target)
+                } else if s.bounds.is_empty() {
+                    self.get_class_of_operand(target)
+                        .is_some_and(|c| c.details.qual_name == "builtins.object")
+                } else {
+                    s.bounds.iter().any(|b| self.is_assignable(b, target))
+                }
+            }
+            (
+                PythonType::None
+                | PythonType::LiteralValue(KnownValue {
+                    literal_value: LiteralValue::None,
+                }),
+                _,
+            ) => match target {
+                PythonType::None => true,
+                PythonType::LiteralValue(value) => value.literal_value == LiteralValue::None,
+                _ => self.get_class_of_operand(target).is_some_and(|c| {
+                    c.details.qual_name == "builtins.object" || self.is_protocol(&c)
+                }),
+            },
+            (_, PythonType::None) => false,
+            // Literal types are only assignable to equal literals, but they widen to their class
+            (_, PythonType::LiteralValue(_)) => source == target,
+            (PythonType::Module(s), PythonType::Module(t)) => s == t,
+            (PythonType::Callable(s), PythonType::Callable(t)) => self.is_callable_assignable(s, t),
+            (PythonType::Coroutine(s), PythonType::Coroutine(t)) => {
+                self.is_assignable(&s.return_type, &t.return_type)
+            }
+            // Coroutines are awaitable objects that implement `typing.Coroutine`
+            (PythonType::Coroutine(s), PythonType::Class(_) | PythonType::Instance(_))
+                if self.get_class_of_operand(target).is_some_and(|c| {
+                    matches!(
+                        c.details.qual_name.as_str(),
+                        "typing.Coroutine" | "typing.Awaitable"
+                    )
+                }) =>
+            {
+                self.get_awaited_type(target)
+                    .map_or(true, |t| self.is_assignable(&s.return_type, &t))
+            }
+            (
+                PythonType::Class(_) | PythonType::Instance(_) | PythonType::LiteralValue(_),
+                PythonType::Class(_) | PythonType::Instance(_),
+            ) => {
+                let (Some(source_class), Some(target_class)) = (
+                    self.get_class_of_operand(source),
+                    self.get_class_of_operand(target),
+                ) else {
+                    return false;
+                };
+                self.is_class_assignable(source, &source_class, target, &target_class)
+            }
+            // Functions, modules and coroutines are objects too
+            (_, PythonType::Class(_) | PythonType::Instance(_)) => self
+                .get_class_of_operand(target)
+                .is_some_and(|c| c.details.qual_name == "builtins.object" || self.is_protocol(&c)),
+            // TODO: Instances of classes that define `__call__` are callable too. Classes are
+            // callable as well but they are not distinguished from their instances yet.
+            (PythonType::Class(_) | PythonType::Instance(_), PythonType::Callable(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn is_class_assignable(
+        &self,
+        source: &PythonType,
+        source_class: &ClassType,
+        target: &PythonType,
+        target_class: &ClassType,
+    ) -> bool {
+        // Only literal strings are assignable to `LiteralString`, and constants are not
+        // inferred as literals.
+        if target_class.details.name == "LiteralString" {
+            return matches!(
+                source,
+                PythonType::LiteralValue(KnownValue {
+                    literal_value: LiteralValue::Str(_),
+                })
+            );
+        }
+        // Special forms like `Callable` or `Any` are not modeled as classes yet
+        if source_class.details.special || target_class.details.special {
+            return true;
+        }
+        match target_class.details.qual_name.as_str() {
+            "builtins.object" => return true,
+            // Classes and their instances share the same type so any class can be a `type`
+            "builtins.type" => return matches!(source, PythonType::Class(_)),
+            _ => {}
+        }
+        if self.is_protocol(target_class) {
+            return self
+                .find_protocol_mismatch(source, source_class, target, target_class)
+                .is_none();
+        }
+        if !self.is_subclass(source_class, target_class) {
+            return false;
+        }
+        // TODO: Type arguments of a subclass need to be mapped to the type parameters of
+        // the base class before they can be compared.
+        if source_class.details.qual_name != target_class.details.qual_name {
+            return true;
+        }
+        let source_arguments = self.get_type_arguments(source);
+        let target_arguments = self.get_type_arguments(target);
+        if source_arguments.is_empty() || target_arguments.is_empty() {
+            return true;
+        }
+        if target_class.details.qual_name == "builtins.tuple" {
+            return self.is_tuple_assignable(source_arguments, target_arguments);
+        }
+        // Mutable containers are invariant in their type parameters
+        // TODO: Use the variance of the type parameters
+        source_arguments.len() == target_arguments.len()
+            && source_arguments
+                .iter()
+                .zip(target_arguments.iter())
+                .all(|(s, t)| {
+                    self.is_assignable(s, t) && (t.is_type_var() || self.is_assignable(t, s))
+                })
+    }
+
+    /// Tuples are immutable so their elements are compared covariantly. The elements
+    /// before and after an unbounded element of the target are matched with the elements
+    /// at the start and the end of the source and the rest of the source elements must be
+    /// assignable to the unbounded element.
+    /// https://typing.readthedocs.io/en/latest/spec/tuples.html#type-compatibility-rules
+    fn is_tuple_assignable(
+        &self,
+        source_arguments: &[PythonType],
+        target_arguments: &[PythonType],
+    ) -> bool {
+        let unbounded_position =
+            |arguments: &[PythonType]| arguments.iter().position(|a| a.is_unbounded());
+        let source_unbounded = unbounded_position(source_arguments);
+        let Some(target_unbounded) = unbounded_position(target_arguments) else {
+            return match source_unbounded {
+                // `tuple[Any, ...]` is compatible with tuples of any length
+                Some(i) => {
+                    source_arguments.len() == 1
+                        && source_arguments[i]
+                            .as_unbounded()
+                            .is_some_and(|e| e.is_any())
+                }
+                None => {
+                    source_arguments.len() == target_arguments.len()
+                        && source_arguments
+                            .iter()
+                            .zip(target_arguments.iter())
+                            .all(|(s, t)| self.is_assignable(s, t))
+                }
+            };
+        };
+        let prefix = &target_arguments[..target_unbounded];
+        let suffix = &target_arguments[target_unbounded + 1..];
+        let source_fixed = source_arguments.len() - usize::from(source_unbounded.is_some());
+        let source_prefix_len = source_unbounded.unwrap_or(source_arguments.len());
+        let source_suffix_len =
+            source_arguments.len() - source_prefix_len - usize::from(source_unbounded.is_some());
+        if source_fixed < prefix.len() + suffix.len()
+            || (source_unbounded.is_some()
+                && (source_prefix_len < prefix.len() || source_suffix_len < suffix.len()))
+        {
+            return false;
+        }
+        let middle = &source_arguments[prefix.len()..source_arguments.len() - suffix.len()];
+        let Some(target_element) = target_arguments[target_unbounded].as_unbounded() else {
+            return false;
+        };
+        prefix
+            .iter()
+            .zip(source_arguments.iter())
+            .chain(
+                suffix
+                    .iter()
+                    .zip(source_arguments[source_arguments.len() - suffix.len()..].iter()),
+            )
+            .all(|(t, s)| self.is_assignable(s, t))
+            && middle
+                .iter()
+                .all(|s| self.is_assignable(s.as_unbounded().map_or(s, |e| e), target_element))
+    }
+
+    fn get_type_arguments<'t>(&self, python_type: &'t PythonType) -> &'t [PythonType] {
+        match python_type {
+            PythonType::Class(c) => &c.specialized,
+            PythonType::Instance(i) => &i.specialized_type_parameters,
+            _ => &[],
+        }
+    }
+
+    /// A callable is assignable to another callable when it accepts all the arguments
+    /// that the target accepts and returns a type that is assignable to the target
+    /// return type. Parameters are compared contravariantly.
+    fn is_callable_assignable(&self, source: &CallableType, target: &CallableType) -> bool {
+        if !self.is_assignable(&source.return_type, &target.return_type) {
+            return false;
+        }
+        // `Callable[..., R]` and param specs accept any parameters
+        if matches!(
+            target.signature.as_slice(),
+            [CallableArgs::Args(_), CallableArgs::KwArgs(_)]
+        ) {
+            return true;
+        }
+        let positional = |c: &'_ CallableType| -> Vec<CallableParameter> {
+            c.signature
+                .iter()
+                .filter(|arg| {
+                    matches!(
+                        arg,
+                        CallableArgs::PositionalOnly(_) | CallableArgs::Positional(_)
+                    )
+                })
+                .map(|arg| arg.parameter().clone())
+                .collect()
+        };
+        let source_positional = positional(source);
+        let target_positional = positional(target);
+        let source_varargs = source.signature.iter().find_map(|arg| match arg {
+            CallableArgs::Args(p) => Some(&p.python_type),
+            _ => None,
+        });
+        // Extra parameters of the source must have default values
+        if source_positional
+            .iter()
+            .skip(target_positional.len())
+            .any(|p| !p.has_default)
+        {
+            return false;
+        }
+        if source_positional.len() < target_positional.len() && source_varargs.is_none() {
+            return false;
+        }
+        target_positional.iter().enumerate().all(|(i, t)| {
+            match source_positional
+                .get(i)
+                .map(|p| &p.python_type)
+                .or(source_varargs)
+            {
+                Some(s) => self.is_assignable(&t.python_type, s),
+                None => false,
+            }
+        })
+    }
+
+    /// Returns the first member of a protocol that the source does not have or has with
+    /// an incompatible type. Returns None when the target is not a protocol.
+    pub fn get_protocol_mismatch(
+        &self,
+        source: &PythonType,
+        target: &PythonType,
+    ) -> Option<ProtocolMismatch> {
+        let source_class = self.get_class_of_operand(source)?;
+        let target_class = self.get_class_of_operand(target)?;
+        if !self.is_protocol(&target_class) {
+            return None;
+        }
+        self.find_protocol_mismatch(source, &source_class, target, &target_class)
+    }
+
+    /// Checks structurally that the class has every member of the protocol. Methods are
+    /// compared by their signatures and properties by the type of their value.
+    /// https://typing.readthedocs.io/en/latest/spec/protocol.html#protocol-members
+    fn find_protocol_mismatch(
+        &self,
+        source: &PythonType,
+        source_class: &ClassType,
+        protocol: &PythonType,
+        protocol_class: &ClassType,
+    ) -> Option<ProtocolMismatch> {
+        let source_arguments = self.get_type_arguments(source);
+        let protocol_arguments = self.get_type_arguments(protocol);
+        if self.is_subclass(source_class, protocol_class) {
+            // Protocol type parameters are compared covariantly
+            let base_arguments =
+                self.get_type_arguments_of_base(source_class, source_arguments, protocol_class);
+            return base_arguments?
+                .into_iter()
+                .zip(protocol_arguments.iter())
+                .find(|(s, t)| !self.is_assignable(s, t))
+                .map(|(source, target)| ProtocolMismatch::TypeArgument {
+                    source: Box::new(source),
+                    target: Box::new(target.clone()),
+                });
+        }
+        // Protocols can refer to themselves in their members. Assume the protocol is
+        // satisfied while its members are being compared.
+        let key = (
+            source_class.details.qual_name.clone(),
+            protocol_class.details.qual_name.clone(),
+        );
+        if self.protocol_checks.borrow().contains(&key) {
+            return None;
+        }
+        self.protocol_checks.borrow_mut().push(key);
+        let mismatch = self
+            .protocol_members(protocol_class)
+            .into_iter()
+            .find_map(|member| {
+                let protocol_member =
+                    self.get_instance_member_type(protocol_class, protocol_arguments, &member)?;
+                let Some(source_member) =
+                    self.get_instance_member_type(source_class, source_arguments, &member)
+                else {
+                    return Some(ProtocolMismatch::Missing(member));
+                };
+                let compatible = match (source_member, protocol_member) {
+                    // Methods are compared without their self parameter
+                    (PythonType::Callable(mut s), PythonType::Callable(mut t)) => {
+                        if !s.signature.is_empty() {
+                            s.signature.remove(0);
+                        }
+                        if !t.signature.is_empty() {
+                            t.signature.remove(0);
+                        }
+                        self.is_callable_assignable(&s, &t)
+                    }
+                    (s, t) => self.is_assignable(&s, &t),
+                };
+                (!compatible).then_some(ProtocolMismatch::Incompatible(member))
+            });
+        self.protocol_checks.borrow_mut().pop();
+        mismatch
+    }
+
+    /// The type of a member when it is read from an instance of the class. Properties
+    /// have the type that their getter returns.
+    fn get_instance_member_type(
+        &self,
+        class: &ClassType,
+        arguments: &[PythonType],
+        member: &str,
+    ) -> Option<PythonType> {
+        let member_type = self.lookup_on_class(
+            &self.get_dec_symbol_table(&class.details.declaration_path),
+            class,
+            member,
+        )?;
+        let member_type = self.specialize_member_type(member_type, class, arguments);
+        match member_type {
+            PythonType::Callable(getter) if self.is_property(class, member) => {
+                Some(getter.return_type)
+            }
+            member_type => Some(member_type),
+        }
+    }
+
+    /// Whether the member that a class or its closest base class declares is a property.
+    fn is_property(&self, class: &ClassType, member: &str) -> bool {
+        self.get_base_classes(class)
+            .iter()
+            .filter_map(|base| base.as_class())
+            .find_map(|base| {
+                let symbol_table = self.get_dec_symbol_table(&base.details.declaration_path);
+                let symbol = symbol_table.lookup_attribute(member, base.details.class_scope_id)?;
+                Some(
+                    symbol
+                        .declarations
+                        .iter()
+                        .any(|d| d.as_function().is_some_and(|f| f.is_property())),
+                )
+            })
+            .unwrap_or(false)
+    }
+
+    /// The type of the values that can be assigned to a property of the instances of a
+    /// class, which is the type of the value parameter of its setter. None when the
+    /// member is not a property with a setter.
+    pub fn get_property_setter_type(
+        &self,
+        class: &ClassType,
+        arguments: &[PythonType],
+        member: &str,
+    ) -> Option<PythonType> {
+        let (symbol_table, setter) = self.get_mro(class).iter().find_map(|base| {
+            let symbol_table = self.get_dec_symbol_table(&base.details.declaration_path);
+            let symbol = symbol_table.lookup_attribute(member, base.details.class_scope_id)?;
+            let setter = symbol
+                .declarations
+                .iter()
+                .filter_map(|d| d.as_function())
+                .find(|f| f.is_property_setter())
+                .cloned();
+            Some((symbol_table, setter))
+        })?;
+        let setter = setter?;
+        let setter_type = self.get_function_type(
+            &symbol_table,
+            &setter,
+            symbol_table.get_scope(setter.function_node.node.start),
+        );
+        let PythonType::Callable(setter_type) =
+            self.specialize_member_type(setter_type, class, arguments)
+        else {
+            return None;
+        };
+        setter_type
+            .signature
+            .get(1)
+            .map(|value| value.parameter().python_type.clone())
+    }
+
+    /// Whether the values of a type are instances of a class. Protocols are matched
+    /// structurally, which `isinstance` only allows for runtime checkable protocols.
+    fn is_instance_of(&self, t: &PythonType, t_class: &ClassType, class: &ClassType) -> bool {
+        if self.is_protocol(class) {
+            let protocol = PythonType::Class(class.clone());
+            return self
+                .find_protocol_mismatch(t, t_class, &protocol, class)
+                .is_none();
+        }
+        self.is_subclass(t_class, class)
+    }
+
+    pub fn is_protocol_class(&self, t: &PythonType) -> bool {
+        matches!(t, PythonType::Class(c) if self.is_protocol(c))
+    }
+
+    /// Names of the members that are declared in the protocol and its protocol bases.
+    fn protocol_members(&self, protocol: &ClassType) -> Vec<String> {
+        let mut members = vec![];
+        let protocols = std::iter::once(protocol).chain(
+            protocol
+                .base_classes
+                .iter()
+                .filter(|base| self.is_protocol(base)),
+        );
+        for class in protocols {
+            let symbol_table = self.get_dec_symbol_table(&class.details.declaration_path);
+            let Some(scope) = symbol_table.get_scope_by_id(class.details.class_scope_id) else {
+                continue;
+            };
+            for symbol in scope.symbols() {
+                if !PROTOCOL_EXCLUDED_MEMBERS.contains(&symbol.name.as_str())
+                    && !members.contains(&symbol.name)
+                {
+                    members.push(symbol.name.clone());
+                }
+            }
+        }
+        members
+    }
+
+    /// Checks whether source is the same class as target or derives from it.
+    /// int is also accepted where float is expected and both are accepted where complex is.
+    /// https://typing.readthedocs.io/en/latest/spec/special-types.html#special-cases-for-float-and-complex
+    fn is_subclass(&self, source: &ClassType, target: &ClassType) -> bool {
+        let promoted = match target.details.qual_name.as_str() {
+            "builtins.float" => ["builtins.int"].as_slice(),
+            "builtins.complex" => ["builtins.int", "builtins.float"].as_slice(),
+            _ => [].as_slice(),
+        };
+        promoted.contains(&source.details.qual_name.as_str())
+            || self.derives_from(source, &target.details.qual_name)
+    }
+
+    fn is_protocol(&self, c: &ClassType) -> bool {
+        c.base_classes
+            .iter()
+            .any(|base| base.details.name == "Protocol")
+    }
+
+    pub fn is_literal(&self, name: String) -> bool {
+        name.as_str() == "Literal"
+    }
+
+    /// Returns the type that a call of the callable evaluates to. The type vars in the
+    /// return type are solved from the types of the arguments. Type vars that the
+    /// arguments do not solve are Unknown.
+    fn get_return_type_of_callable(
+        &self,
+        f_type: &CallableType,
+        call: &ast::Call,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> PythonType {
+        if f_type.is_overloaded() {
+            let Some(matched) =
+                self.match_overloads(&f_type.overloads, call, symbol_table, scope_id)
+            else {
+                return PythonType::Unknown;
+            };
+            return PythonType::union(
+                matched
+                    .into_iter()
+                    .map(|index| {
+                        self.get_return_type_of_callable(
+                            &f_type.overloads[index],
+                            call,
+                            symbol_table,
+                            scope_id,
+                        )
+                    })
+                    .collect(),
+            );
+        }
+        let ret_type = &f_type.return_type;
+        // `_replace` returns a copy of the named tuple
+        if f_type.name == "_replace"
+            && self
+                .get_named_tuple_receiver_fields(call, symbol_table, scope_id)
+                .is_some()
+        {
+            if let Expression::Attribute(a) = &call.func {
+                return self
+                    .get_type(&a.value, symbol_table, scope_id)
+                    .unwrap_or(PythonType::Unknown);
+            }
+        }
+        if self.get_type_guard(ret_type).is_some() {
+            return self.get_builtin_type("bool").unwrap_or(PythonType::Unknown);
+        }
+
+        let mut type_vars = vec![];
+        collect_type_vars(ret_type, &mut type_vars);
+        if type_vars.is_empty() {
+            return ret_type.clone();
+        }
+        let mut solution = self.solve_call_type_vars(f_type, call, symbol_table, scope_id);
+        for type_var in type_vars {
+            solution
+                .entry(type_var.name.clone())
+                .or_insert(PythonType::Unknown);
+        }
+        self.apply_type_var_solution(ret_type, &solution)
+    }
+
+    /// Calling a generic class solves its type parameters from the arguments that are
+    /// passed to `__init__`, e.g. `Box(1)` is `Box[int]` when `__init__` takes a `T`.
+    fn get_constructed_class_type(
+        &self,
+        c: &ClassType,
+        call: &ast::Call,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> PythonType {
+        if c.details.special || c.type_parameters.is_empty() || !c.specialized.is_empty() {
+            return PythonType::Class(c.clone());
+        }
+        let Some(init) = self.get_call_signature(call, symbol_table, scope_id) else {
+            return PythonType::Class(c.clone());
+        };
+        let solution = self.solve_call_type_vars(&init, call, symbol_table, scope_id);
+        let mut specialized_class = c.clone();
+        specialized_class.specialized = c
+            .type_parameters
+            .iter()
+            .map(|tp| {
+                tp.as_type_var()
+                    .and_then(|type_var| solution.get(&type_var.name))
+                    .cloned()
+                    .unwrap_or(PythonType::Unknown)
+            })
+            .collect();
+        // The length of a tuple that is created from an iterable is not known
+        if c.details.qual_name == "builtins.tuple" {
+            specialized_class.specialized = specialized_class
+                .specialized
+                .into_iter()
+                .map(|element| PythonType::Unbounded(Box::new(element)))
+                .collect();
+        }
+        PythonType::Class(specialized_class)
+    }
+
+    /// Checks whether the class is the class with the qualified name or derives from it,
+    /// which is whether the class is in its method resolution order.
+    fn derives_from(&self, c: &ClassType, qual_name: &str) -> bool {
+        self.get_mro(c)
+            .iter()
+            .any(|class| class.details.qual_name == qual_name)
+    }
+
+    /// Enum classes derive from `enum.Enum`, e.g. `IntEnum`, `StrEnum` and `Flag`.
+    pub fn is_enum_class(&self, c: &ClassType) -> bool {
+        self.derives_from(c, "enum.Enum")
+    }
+
+    /// The members of an enum class with the expressions of their values, in the order
+    /// of their declaration. Names that start with an underscore, nested functions and
+    /// annotated names without a value are not members.
+    /// https://typing.readthedocs.io/en/latest/spec/enums.html#defining-members
+    fn get_enum_members(&self, c: &ClassType) -> Vec<(String, Expression)> {
+        if !self.is_enum_class(c) {
+            return vec![];
+        }
+        let Some(class_node) = c.details.class_node.as_ref() else {
+            return vec![];
+        };
+        class_node
+            .body
+            .iter()
+            .filter_map(|statement| {
+                let (target, value) = match statement {
+                    ast::Statement::AssignStatement(a) if a.targets.len() == 1 => {
+                        (&a.targets[0], &a.value)
+                    }
+                    ast::Statement::AnnAssignStatement(a) => (&a.target, a.value.as_ref()?),
+                    _ => return None,
+                };
+                let Expression::Name(name) = target else {
+                    return None;
+                };
+                let is_nonmember = match value {
+                    Expression::Lambda(_) => true,
+                    Expression::Call(call) => {
+                        dataclass::name_of(&call.func).is_some_and(|f| f == "nonmember")
+                    }
+                    _ => false,
+                };
+                if name.id.starts_with('_') || is_nonmember {
+                    return None;
+                }
+                Some((name.id.clone(), value.clone()))
+            })
+            .collect()
+    }
+
+    /// The type of the value of an enum member. `auto()` creates ints, or strs in enums
+    /// that derive from `str`.
+    fn get_enum_value_type(&self, c: &ClassType, value: &Expression) -> PythonType {
+        if let Expression::Call(call) = value {
+            if dataclass::name_of(&call.func).is_some_and(|f| f == "auto") {
+                let class_name = if self.derives_from(c, "builtins.str") {
+                    "str"
+                } else {
+                    "int"
+                };
+                return self
+                    .get_builtin_type(class_name)
+                    .unwrap_or(PythonType::Unknown);
+            }
+        }
+        if let Some(literal) = self.get_literal_type_of_constant(value) {
+            return literal;
+        }
+        let symbol_table = self.get_dec_symbol_table(&c.details.declaration_path);
+        self.get_type(value, &symbol_table, c.details.class_scope_id)
+            .unwrap_or(PythonType::Unknown)
+    }
+
+    /// The type of an attribute of an enum class or an enum value, None when the
+    /// attribute is not specific to enums. Members of the class are literals, and the
+    /// `name` and `value` of an enum value come from the members it can be.
+    fn get_enum_attribute_type(
+        &self,
+        value_type: &PythonType,
+        attribute: &str,
+    ) -> Option<PythonType> {
+        let (class, members) = match value_type {
+            PythonType::LiteralValue(KnownValue {
+                literal_value: LiteralValue::Enum(member),
+            }) => {
+                let members = self
+                    .get_enum_members(&member.class)
+                    .into_iter()
+                    .filter(|(name, _)| *name == member.name)
+                    .collect();
+                (*member.class.clone(), members)
+            }
+            PythonType::Class(_) | PythonType::Instance(_) => {
+                let class = self.get_class_of_operand(value_type)?;
+                let members = self.get_enum_members(&class);
+                (class, members)
+            }
+            _ => return None,
+        };
+        if members.is_empty() {
+            return None;
+        }
+        match attribute {
+            "name" | "_name_" => Some(PythonType::union(
+                members
+                    .into_iter()
+                    .map(|(name, _)| {
+                        PythonType::LiteralValue(KnownValue {
+                            literal_value: LiteralValue::Str(name),
+                        })
+                    })
+                    .collect(),
+            )),
+            "value" | "_value_" => Some(PythonType::union(
+                members
+                    .iter()
+                    .map(|(_, value)| self.get_enum_value_type(&class, value))
+                    .collect(),
+            )),
+            _ if value_type.is_literal_value() => None,
+            _ => members
+                .iter()
+                .any(|(name, _)| name == attribute)
+                .then(|| enum_literal(&class, attribute)),
+        }
+    }
+
+    /// Enum classes and their values are the unions of the literals of their members,
+    /// except for flags whose values can also be combinations of members.
+    fn expand_enum_members(&self, t: PythonType, class: &ClassType) -> PythonType {
+        if self.derives_from(class, "enum.Flag") {
+            return t;
+        }
+        let members = t
+            .union_members()
+            .into_iter()
+            .flat_map(|member| {
+                let members = match self.get_class_of_operand(&member) {
+                    Some(c) if !member.is_literal_value() && c == *class => {
+                        self.get_enum_members(&c)
+                    }
+                    _ => vec![],
+                };
+                if members.is_empty() {
+                    vec![member]
+                } else {
+                    members
+                        .iter()
+                        .map(|(name, _)| enum_literal(class, name))
+                        .collect()
+                }
+            })
+            .collect();
+        PythonType::union(members)
+    }
+
+    /// The fields of a named tuple in the order of their positions, or None when the
+    /// class is not a named tuple. Subclasses of named tuples have the same fields.
+    /// https://typing.readthedocs.io/en/latest/spec/namedtuples.html
+    pub fn get_named_tuple_fields(&self, c: &ClassType) -> Option<Vec<NamedTupleField>> {
+        if let Some(ClassFields::NamedTuple(fields)) = &c.fields {
+            return Some(fields.clone());
+        }
+        if !c
+            .base_classes
+            .iter()
+            .any(|base| base.details.qual_name == "typing.NamedTuple")
+        {
+            return c
+                .base_classes
+                .iter()
+                .find_map(|base| self.get_named_tuple_fields(base));
+        }
+        let class_node = c.details.class_node.as_ref()?;
+        let symbol_table = self.get_dec_symbol_table(&c.details.declaration_path);
+        let fields = class_node
+            .body
+            .iter()
+            .filter_map(|statement| {
+                let ast::Statement::AnnAssignStatement(a) = statement else {
+                    return None;
+                };
+                let Expression::Name(target) = &a.target else {
+                    return None;
+                };
+                Some(NamedTupleField {
+                    name: target.id.clone(),
+                    python_type: self.get_annotation_type(
+                        &a.annotation,
+                        &symbol_table,
+                        c.details.class_scope_id,
+                    ),
+                    has_default: a.value.is_some(),
+                })
+            })
+            .collect();
+        Some(fields)
+    }
+
+    /// The items of a typed dict, or None when the class is not a typed dict. Items of
+    /// base typed dicts come first. Items are required when the class that declares them
+    /// is total, unless they are marked `Required` or `NotRequired`.
+    /// https://typing.readthedocs.io/en/latest/spec/typeddict.html
+    pub fn get_typed_dict_fields(&self, c: &ClassType) -> Option<Vec<TypedDictField>> {
+        if let Some(ClassFields::TypedDict(fields)) = &c.fields {
+            return Some(fields.clone());
+        }
+        let mut fields: Vec<TypedDictField> = vec![];
+        let mut is_typed_dict = false;
+        for base in &c.base_classes {
+            if base.details.special && base.details.name == "TypedDict" {
+                is_typed_dict = true;
+            } else if let Some(base_fields) = self.get_typed_dict_fields(base) {
+                is_typed_dict = true;
+                for field in base_fields {
+                    add_typed_dict_field(&mut fields, field);
+                }
+            }
+        }
+        if !is_typed_dict {
+            return None;
+        }
+        let Some(class_node) = c.details.class_node.as_ref() else {
+            return Some(fields);
+        };
+        let total = !class_node
+            .keywords
+            .iter()
+            .any(|k| k.arg.as_deref() == Some("total") && is_false(&k.value));
+        let symbol_table = self.get_dec_symbol_table(&c.details.declaration_path);
+        for statement in &class_node.body {
+            let ast::Statement::AnnAssignStatement(a) = statement else {
+                continue;
+            };
+            let Expression::Name(target) = &a.target else {
+                continue;
+            };
+            let (annotation, required, read_only) = typed_dict_item_qualifiers(&a.annotation);
+            let field = TypedDictField {
+                name: target.id.clone(),
+                python_type: self.get_annotation_type(
+                    annotation,
+                    &symbol_table,
+                    c.details.class_scope_id,
+                ),
+                required: required.unwrap_or(total),
+                read_only,
+            };
+            add_typed_dict_field(&mut fields, field);
+        }
+        Some(fields)
+    }
+
+    /// Creates the class of a named tuple or a typed dict that is declared by calling
+    /// `NamedTuple` or `TypedDict`, e.g. `Point = NamedTuple("Point", [("x", int)])`.
+    fn create_class_with_fields(
+        &self,
+        c: &ClassType,
+        call: &ast::Call,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Option<ClassType> {
+        let is_named_tuple = c.details.qual_name == "typing.NamedTuple";
+        let is_typed_dict = c.details.special && c.details.name == "TypedDict";
+        if !is_named_tuple && !is_typed_dict {
+            return None;
+        }
+        let Some(Expression::Constant(name)) = call.args.first() else {
+            return None;
+        };
+        let ast::ConstantValue::Str(name) = &name.value else {
+            return None;
+        };
+        let annotation_type =
+            |annotation| self.get_annotation_type(annotation, symbol_table, scope_id);
+        let fields = match call.args.get(1) {
+            Some(Expression::List(l)) if is_named_tuple => ClassFields::NamedTuple(
+                l.elements
+                    .iter()
+                    .filter_map(|element| {
+                        let Expression::Tuple(pair) = element else {
+                            return None;
+                        };
+                        let [field_name, annotation] = pair.elements.as_slice() else {
+                            return None;
+                        };
+                        Some(NamedTupleField {
+                            name: string_constant(field_name)?,
+                            python_type: annotation_type(annotation),
+                            has_default: false,
+                        })
+                    })
+                    .collect(),
+            ),
+            Some(Expression::Dict(d)) if is_typed_dict => {
+                let total = !call
+                    .keywords
+                    .iter()
+                    .any(|k| k.arg.as_deref() == Some("total") && is_false(&k.value));
+                ClassFields::TypedDict(
+                    d.keys
+                        .iter()
+                        .zip(d.values.iter())
+                        .filter_map(|(key, value)| {
+                            let (annotation, required, read_only) =
+                                typed_dict_item_qualifiers(value);
+                            Some(TypedDictField {
+                                name: string_constant(key)?,
+                                python_type: annotation_type(annotation),
+                                required: required.unwrap_or(total),
+                                read_only,
+                            })
+                        })
+                        .collect(),
+                )
+            }
+            _ => return None,
+        };
+        let module_name = symbol_table.file_path.file_stem()?.to_str()?;
+        let mut class = c.clone();
+        class.details.name = name.clone();
+        class.details.qual_name = format!("{module_name}.{name}");
+        class.details.special = false;
+        class.base_classes = vec![c.clone()];
+        class.fields = Some(fields);
+        Some(class)
+    }
+
+    /// The type of an item of a named tuple that is indexed by an integer literal, or of a
+    /// typed dict that is indexed by a string literal.
+    fn get_item_type_of_fields(
+        &self,
+        value_type: &PythonType,
+        index: &Expression,
+    ) -> Result<Option<PythonType>> {
+        let class = match value_type {
+            PythonType::Class(c) => c,
+            PythonType::Instance(i) => &i.class_type,
+            _ => return Ok(None),
+        };
+        if let Some(elements) = self.get_tuple_elements(value_type) {
+            return self.get_tuple_item_type(&class.details.name, elements, index);
+        }
+        if let Some(fields) = self.get_typed_dict_fields(class) {
+            let Some(key) = string_constant(index) else {
+                return Ok(None);
+            };
+            return match fields.iter().find(|field| field.name == key) {
+                Some(field) => Ok(Some(field.python_type.clone())),
+                None => bail!(TypeIssue(format!(
+                    "\"{key}\" is not a defined key in \"{}\"",
+                    class.details.name
+                ))),
+            };
+        }
+        Ok(None)
+    }
+
+    /// The type of the element of a tuple at a literal index or the tuple of the elements
+    /// in a literal slice. None when the index does not tell which elements they are.
+    fn get_tuple_item_type(
+        &self,
+        name: &str,
+        elements: Vec<PythonType>,
+        index: &Expression,
+    ) -> Result<Option<PythonType>> {
+        let unbounded = elements.iter().position(|e| e.is_unbounded());
+        if let Expression::Slice(slice) = index {
+            if unbounded.is_some() {
+                return Ok(None);
+            }
+            let bound = |bound: &Option<Expression>| match bound {
+                Some(bound) => int_constant(bound).map(Some),
+                None => Some(None),
+            };
+            let (Some(lower), Some(upper), Some(step)) =
+                (bound(&slice.lower), bound(&slice.upper), bound(&slice.step))
+            else {
+                return Ok(None);
+            };
+            let Some(indices) = slice_indices(elements.len(), lower, upper, step.unwrap_or(1))
+            else {
+                return Ok(None);
+            };
+            return Ok(self.create_tuple_instance(
+                indices.into_iter().map(|i| elements[i].clone()).collect(),
+            ));
+        }
+        let Some(index) = int_constant(index) else {
+            return Ok(None);
+        };
+        let len = elements.len() as i64;
+        let position = if index < 0 { len + index } else { index };
+        match unbounded {
+            None => match usize::try_from(position).ok().and_then(|i| elements.get(i)) {
+                Some(element) => Ok(Some(element.clone())),
+                None => bail!(TypeIssue(format!(
+                    "Index {index} is out of range for type \"{name}\""
+                ))),
+            },
+            // Only the elements before and after the unbounded element have known positions
+            Some(u)
+                if (index >= 0 && position < u as i64) || (index < 0 && position > u as i64) =>
+            {
+                Ok(Some(elements[position as usize].clone()))
+            }
+            Some(_) => Ok(None),
+        }
+    }
+
+    /// The type of the part of a value that a target in tuple targets is assigned.
+    fn get_unpacked_type(
+        &self,
+        value_type: PythonType,
+        unpack_path: &[UnpackTarget],
+    ) -> PythonType {
+        let mut unpacked_type = value_type;
+        for target in unpack_path {
+            match self.get_unpacked_target_types(&unpacked_type, target.count, target.star) {
+                Some(mut targets) => unpacked_type = targets.swap_remove(target.index),
+                None => return PythonType::Unknown,
+            }
+        }
+        unpacked_type
+    }
+
+    /// The types of the targets of a tuple target that a value is unpacked to. Tuples give
+    /// their elements in their positions and other iterables give their items to each
+    /// target. The star target gets a list of the items that the other targets do not take.
+    /// None when the value is a tuple that does not have enough elements for the targets.
+    pub fn get_unpacked_target_types(
+        &self,
+        value_type: &PythonType,
+        count: usize,
+        star: Option<usize>,
+    ) -> Option<Vec<PythonType>> {
+        let mut types = match self.get_tuple_elements(value_type) {
+            Some(elements) => unpack_tuple_elements(&elements, count, star)?,
+            None => vec![self.get_iterated_type(value_type); count],
+        };
+        if let Some(star) = star {
+            let item_type = std::mem::replace(&mut types[star], PythonType::Unknown);
+            types[star] = self.get_builtin_instance(builtins::LIST_TYPE, vec![item_type]);
+        }
+        Some(types)
+    }
+
+    /// The fields of the named tuple that a method is called on, e.g. `p` in `p._replace()`.
+    fn get_named_tuple_receiver_fields(
+        &self,
+        call: &ast::Call,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Option<Vec<NamedTupleField>> {
+        let Expression::Attribute(a) = &call.func else {
+            return None;
+        };
+        match self.get_type(&a.value, symbol_table, scope_id).ok()? {
+            PythonType::Class(c) => self.get_named_tuple_fields(&c),
+            PythonType::Instance(i) => self.get_named_tuple_fields(&i.class_type),
+            _ => None,
+        }
+    }
+
+    /// Returns the options of a dataclass, or None when the class is not a dataclass.
+    /// Classes are dataclasses when they are decorated with `dataclass` or a decorator
+    /// marked with `dataclass_transform`, or when they derive from a class or use a
+    /// metaclass that is marked with `dataclass_transform`.
+    /// https://typing.readthedocs.io/en/latest/spec/dataclasses.html#the-dataclass-transform-decorator
+    pub fn get_dataclass_options(&self, c: &ClassType) -> Option<DataclassOptions> {
+        let class_node = c.details.class_node.as_ref()?;
+        let symbol_table = self.get_dec_symbol_table(&c.details.declaration_path);
+        let class_decl_scope = symbol_table
+            .get_scope_by_id(c.details.class_scope_id)
+            .and_then(|scope| symbol_table.parent_scope(scope))
+            .map_or(0, |scope| scope.id);
+        for decorator in &class_node.decorator_list {
+            let (callee, keywords) = dataclass::decorator_call(decorator);
+            let mut options = if dataclass::is_dataclass_decorator(decorator) {
+                DataclassOptions::default()
+            } else {
+                let Expression::Name(callee) = callee else {
+                    continue;
+                };
+                let Some(Declaration::Function(f)) =
+                    self.get_declaration_of(&callee.id, &symbol_table, class_decl_scope)
+                else {
+                    continue;
+                };
+                let Some(transform) =
+                    dataclass::find_dataclass_transform(&f.function_node.decorator_list)
+                else {
+                    continue;
+                };
+                DataclassOptions::from_transform(transform)
+            };
+            options.apply_keywords(keywords);
+            return Some(options);
+        }
+        let mut options = self.get_inherited_transform_options(c)?;
+        options.apply_keywords(&class_node.keywords);
+        Some(options)
+    }
+
+    /// The default options of the transform that a base class or a metaclass of the
+    /// class is marked with.
+    fn get_inherited_transform_options(&self, c: &ClassType) -> Option<DataclassOptions> {
+        c.base_classes
+            .iter()
+            .chain(c.metaclass.as_deref())
+            .find_map(|base| {
+                let transform = base.details.class_node.as_ref().and_then(|class_node| {
+                    dataclass::find_dataclass_transform(&class_node.decorator_list)
+                });
+                match transform {
+                    Some(transform) => Some(DataclassOptions::from_transform(transform)),
+                    None => self.get_inherited_transform_options(base),
+                }
+            })
+    }
+
+    /// Whether instances of the class are dataclass instances, either because the class
+    /// is a dataclass or because it derives from one.
+    pub fn is_dataclass(&self, c: &ClassType) -> bool {
+        self.get_dataclass_options(c).is_some()
+            || c.base_classes.iter().any(|base| self.is_dataclass(base))
+    }
+
+    /// Whether the attributes of the instances of the class cannot be assigned because the
+    /// class or the dataclass it derives from is frozen.
+    pub fn is_frozen_dataclass(&self, c: &ClassType) -> bool {
+        match self.get_dataclass_options(c) {
+            Some(options) => options.frozen,
+            None => c
+                .base_classes
+                .iter()
+                .any(|base| self.is_frozen_dataclass(base)),
+        }
+    }
+
+    /// The fields of a dataclass in the order of the parameters of its `__init__`.
+    /// Fields of base classes come first and keep their position when a subclass
+    /// declares them again.
+    pub fn get_dataclass_fields(&self, c: &ClassType) -> Vec<DataclassField> {
+        let mut fields: Vec<DataclassField> = vec![];
+        let add_field = |fields: &mut Vec<DataclassField>, field: DataclassField| match fields
+            .iter_mut()
+            .find(|f| f.name == field.name)
+        {
+            Some(existing) => *existing = field,
+            None => fields.push(field),
+        };
+        for base in c.base_classes.iter().rev() {
+            for field in self.get_dataclass_fields(base) {
+                add_field(&mut fields, field);
+            }
+        }
+        let (Some(options), Some(class_node)) =
+            (self.get_dataclass_options(c), c.details.class_node.as_ref())
+        else {
+            return fields;
+        };
+        let symbol_table = self.get_dec_symbol_table(&c.details.declaration_path);
+        for declaration in dataclass::field_declarations(&class_node.body, &options) {
+            let python_type = self.get_annotation_type(
+                declaration.annotation,
+                &symbol_table,
+                c.details.class_scope_id,
+            );
+            let field = DataclassField {
+                name: declaration.name.to_string(),
+                python_type,
+                has_default: declaration.has_default,
+                init: declaration.init,
+                kw_only: declaration.kw_only.unwrap_or(options.kw_only),
+                init_var: declaration.init_var,
+                declared_in: c.details.qual_name.clone(),
+                node: declaration.node,
+            };
+            add_field(&mut fields, field);
+        }
+        fields
+    }
+
+    /// The type of a method or attribute that a dataclass synthesizes. Returns None when
+    /// the class is not a dataclass or does not synthesize the member.
+    fn get_synthesized_dataclass_member(&self, c: &ClassType, name: &str) -> Option<PythonType> {
+        if !dataclass::SYNTHESIZED_MEMBERS.contains(&name) {
+            return None;
+        }
+        let options = self.get_dataclass_options(c)?;
+        let bool_type = self.get_builtin_type("bool")?;
+        let self_parameter = CallableArgs::Positional(CallableParameter::new(
+            "self".to_string(),
+            PythonType::Class(c.clone()),
+            false,
+        ));
+        let method = |parameters: Vec<CallableArgs>, return_type: PythonType| {
+            let signature = std::iter::once(self_parameter.clone())
+                .chain(parameters)
+                .collect();
+            let mut method = CallableType::new(name.to_string(), signature, return_type, false);
+            method.method_kind = MethodKind::Instance;
+            PythonType::Callable(Box::new(method))
+        };
+        let fields = self.get_dataclass_fields(c);
+        let init_fields = fields.iter().filter(|field| field.init);
+        match name {
+            "__init__" if options.init => {
+                let (keyword, positional): (Vec<_>, Vec<_>) =
+                    init_fields.partition(|field| field.kw_only);
+                let parameter = |field: &DataclassField| {
+                    CallableParameter::new(
+                        field.name.clone(),
+                        field.python_type.clone(),
+                        field.has_default,
+                    )
+                };
+                let parameters = positional
+                    .into_iter()
+                    .map(|field| CallableArgs::Positional(parameter(field)))
+                    .chain(
+                        keyword
+                            .into_iter()
+                            .map(|field| CallableArgs::Keyword(parameter(field))),
+                    )
+                    .collect();
+                Some(method(parameters, PythonType::None))
+            }
+            "__eq__" if options.eq => {
+                let other = CallableArgs::Positional(CallableParameter::new(
+                    "other".to_string(),
+                    self.get_builtin_type("object")?,
+                    false,
+                ));
+                Some(method(vec![other], bool_type))
+            }
+            "__lt__" | "__le__" | "__gt__" | "__ge__" if options.order => {
+                let other = CallableArgs::Positional(CallableParameter::new(
+                    "other".to_string(),
+                    PythonType::Class(c.clone()),
+                    false,
+                ));
+                Some(method(vec![other], bool_type))
+            }
+            // Instances that compare by value but can change are not hashable
+            "__hash__" if options.eq && !options.frozen && !options.unsafe_hash => {
+                Some(PythonType::None)
+            }
+            "__match_args__" if options.match_args => {
+                let names = init_fields
+                    .filter(|field| !field.kw_only)
+                    .map(|field| {
+                        PythonType::LiteralValue(KnownValue {
+                            literal_value: LiteralValue::Str(field.name.clone()),
+                        })
+                    })
+                    .collect();
+                self.create_tuple_instance(names)
+            }
+            "__slots__" if options.slots => {
+                self.create_tuple_instance(vec![PythonType::Unbounded(Box::new(
+                    self.get_builtin_type("str")?,
+                ))])
+            }
+            "__dataclass_fields__" => {
+                let dict_class = self.get_builtin_type(builtins::DICT_TYPE)?.class()?;
+                Some(PythonType::Instance(InstanceType::new(
+                    dict_class,
+                    vec![self.get_builtin_type("str")?, PythonType::Any],
+                )))
+            }
+            "__replace__" => Some(method(
+                self.get_replace_parameters(&fields),
+                PythonType::Class(c.clone()),
+            )),
+            _ => None,
+        }
+    }
+
+    /// The parameters that set the fields of a copy of a dataclass instance, as they are
+    /// passed to `dataclasses.replace` or `__replace__`. Fields that are not initialized
+    /// by `__init__` cannot be replaced.
+    fn get_replace_parameters(&self, fields: &[DataclassField]) -> Vec<CallableArgs> {
+        fields
+            .iter()
+            .filter(|field| field.init)
+            .map(|field| {
+                CallableArgs::Keyword(CallableParameter::new(
+                    field.name.clone(),
+                    field.python_type.clone(),
+                    !field.init_var || field.has_default,
+                ))
+            })
+            .collect()
+    }
+
+    /// `dataclasses.replace` takes the fields of the dataclass instance that is passed to
+    /// it as keyword arguments.
+    fn get_replace_signature(
+        &self,
+        callable: &CallableType,
+        call: &ast::Call,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Option<CallableType> {
+        let obj = callable.signature.first()?.parameter().clone();
+        let obj_type = self
+            .get_type(call.args.first()?, symbol_table, scope_id)
+            .ok()?;
+        let class = match obj_type {
+            PythonType::Class(c) => c,
+            PythonType::Instance(i) => i.class_type,
+            _ => return None,
+        };
+        if !self.is_dataclass(&class) {
+            return None;
+        }
+        let mut signature = vec![CallableArgs::PositionalOnly(obj)];
+        signature.extend(self.get_replace_parameters(&self.get_dataclass_fields(&class)));
+        Some(CallableType::new(
+            callable.name.clone(),
+            signature,
+            callable.return_type.clone(),
+            false,
+        ))
+    }
+
+    /// Creates a tuple with elements of the types, an unbounded element stands for any
+    /// number of elements.
+    fn create_tuple_instance(&self, elements: Vec<PythonType>) -> Option<PythonType> {
+        let tuple_class = self.get_builtin_type(builtins::TUPLE_TYPE)?.class()?;
+        Some(PythonType::Instance(InstanceType::new(
+            tuple_class,
+            elements,
+        )))
+    }
+
+    /// The types of the elements of a tuple or a named tuple in their positions. None when
+    /// the value is not a tuple or its elements are not known.
+    pub fn get_tuple_elements(&self, value_type: &PythonType) -> Option<Vec<PythonType>> {
+        let (class, arguments) = match value_type {
+            PythonType::Class(c) => (c, &c.specialized),
+            PythonType::Instance(i) => (&i.class_type, &i.specialized_type_parameters),
+            _ => return None,
+        };
+        if let Some(fields) = self.get_named_tuple_fields(class) {
+            return Some(fields.into_iter().map(|field| field.python_type).collect());
+        }
+        (class.details.qual_name == "builtins.tuple" && !arguments.is_empty())
+            .then(|| arguments.clone())
+    }
+
+    /// The element types of a tuple expression. Unpacking a tuple in it adds the elements
+    /// of the tuple and unpacking other iterables adds any number of their items.
+    fn get_tuple_expression_elements(
+        &self,
+        elements: &[Expression],
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Vec<PythonType> {
+        let mut element_types = vec![];
+        for element in elements {
+            let Expression::Starred(starred) = element else {
+                element_types.push(
+                    self.get_type(element, symbol_table, scope_id)
+                        .unwrap_or(PythonType::Unknown),
+                );
+                continue;
+            };
+            let unpacked = self
+                .get_type(&starred.value, symbol_table, scope_id)
+                .unwrap_or(PythonType::Unknown);
+            match self.get_tuple_elements(&unpacked) {
+                Some(unpacked_elements) => element_types.extend(unpacked_elements),
+                None => element_types.push(PythonType::Unbounded(Box::new(
+                    self.get_iterated_type(&unpacked),
+                ))),
+            }
+        }
+        join_unbounded_elements(element_types)
+    }
+
+    /// The type arguments of a tuple type, e.g. `int, ...` in `tuple[int, ...]`. Unpacking
+    /// a tuple type in them adds its elements, e.g. `*tuple[int, ...]`.
+    /// https://typing.readthedocs.io/en/latest/spec/tuples.html
+    fn get_tuple_type_arguments(
+        &self,
+        slice: &Expression,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Vec<PythonType> {
+        let elements = match slice {
+            Expression::Tuple(t) => t.elements.iter().collect(),
+            slice => vec![slice],
+        };
+        let mut arguments = vec![];
+        for element in elements {
+            match element {
+                Expression::Constant(c) if matches!(c.value, ast::ConstantValue::Ellipsis) => {
+                    if let Some(last) = arguments.pop() {
+                        arguments.push(PythonType::Unbounded(Box::new(last)));
+                    }
+                }
+                Expression::Starred(starred) => {
+                    let unpacked = self.get_annotation_type(&starred.value, symbol_table, scope_id);
+                    match self.get_tuple_elements(&unpacked) {
+                        Some(unpacked_elements) => arguments.extend(unpacked_elements),
+                        // TODO: Type var tuples
+                        None => {
+                            arguments.push(PythonType::Unbounded(Box::new(PythonType::Unknown)))
+                        }
+                    }
+                }
+                element => {
+                    arguments.push(self.get_annotation_type(element, symbol_table, scope_id))
+                }
+            }
+        }
+        join_unbounded_elements(arguments)
+    }
+
+    /// Finds the declaration that a name refers to. Imported names are followed to the
+    /// module that declares them.
+    fn get_declaration_of(
+        &self,
+        name: &str,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Option<Declaration> {
+        let declaration = symbol_table
+            .lookup_in_scope(name, scope_id)?
+            .last_declaration()
+            .clone();
+        let Declaration::Alias(alias) = &declaration else {
+            return Some(declaration);
+        };
+        let symbol_name = alias.symbol_name.as_ref()?;
+        alias
+            .import_result
+            .as_ref()?
+            .resolved_ids
+            .iter()
+            .filter(|id| **id != symbol_table.id)
+            .find_map(|id| self.get_declaration_of(symbol_name, &self.get_symbol_table(id), 0))
+    }
+
+    /// Solves the type vars of a signature from the types of the arguments of a call.
+    fn solve_call_type_vars(
+        &self,
+        signature: &CallableType,
+        call: &ast::Call,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> TypeVarSolution {
+        let mut solution = TypeVarSolution::new();
+        for (parameter, arg) in bind_arguments(&signature.signature, call) {
+            let arg_type = self
+                .get_type(arg, symbol_table, scope_id)
+                .unwrap_or(PythonType::Unknown);
+            self.solve_type_vars(parameter.get_type(), &arg_type, &mut solution);
+        }
+        solution
+    }
+
+    /// Matches the structure of a parameter type against the type of the argument that is
+    /// passed for it and records the types that the type vars in the parameter stand for.
+    /// https://typing.readthedocs.io/en/latest/spec/generics.html
+    fn solve_type_vars(
+        &self,
+        parameter: &PythonType,
+        argument: &PythonType,
+        solution: &mut TypeVarSolution,
+    ) {
+        match parameter {
+            PythonType::TypeVar(type_var) => {
+                self.add_type_var_solution(type_var, argument, solution)
+            }
+            PythonType::Unbounded(element) => {
+                let argument = argument.as_unbounded().map_or(argument, |e| e);
+                self.solve_type_vars(element, argument, solution);
+            }
+            PythonType::Optional(inner) => {
+                for member in argument.union_members() {
+                    if member != PythonType::None {
+                        self.solve_type_vars(inner, &member, solution);
+                    }
+                }
+            }
+            PythonType::MultiValue(members) => {
+                // The members of the argument that other members of the union accept are
+                // not matched against the type vars, e.g. `int` for `Union[int, T]`
+                let (generic, concrete): (Vec<&PythonType>, Vec<&PythonType>) =
+                    members.iter().partition(|m| {
+                        let mut type_vars = vec![];
+                        collect_type_vars(m, &mut type_vars);
+                        !type_vars.is_empty()
+                    });
+                for member in argument.union_members() {
+                    if concrete.iter().any(|c| self.is_assignable(&member, c)) {
+                        continue;
+                    }
+                    for g in generic.iter() {
+                        self.solve_type_vars(g, &member, solution);
+                    }
+                }
+            }
+            PythonType::Callable(parameter_callable) => {
+                let PythonType::Callable(argument_callable) = argument else {
+                    return;
+                };
+                // A param spec stands for the whole signature of the argument
+                if let Some(name) = param_spec_name(parameter_callable) {
+                    let mut parameters = (**argument_callable).clone();
+                    parameters.return_type = PythonType::Unknown;
+                    parameters.overloads = vec![];
+                    solution
+                        .entry(name.to_string())
+                        .or_insert(PythonType::Callable(Box::new(parameters)));
+                    self.solve_type_vars(
+                        &parameter_callable.return_type,
+                        &argument_callable.return_type,
+                        solution,
+                    );
+                    return;
+                }
+                for (p, a) in parameter_callable
+                    .signature
+                    .iter()
+                    .zip(argument_callable.signature.iter())
+                {
+                    self.solve_type_vars(p.get_type(), a.get_type(), solution);
+                }
+                self.solve_type_vars(
+                    &parameter_callable.return_type,
+                    &argument_callable.return_type,
+                    solution,
+                );
+            }
+            PythonType::Class(_) | PythonType::Instance(_) => {
+                let parameter_arguments = self.get_type_arguments(parameter);
+                if parameter_arguments.is_empty() {
+                    return;
+                }
+                let Some(parameter_class) = self.get_class_of_operand(parameter) else {
+                    return;
+                };
+                let Some(argument_class) = self.get_class_of_operand(argument) else {
+                    return;
+                };
+                let argument_arguments = self.get_type_arguments(argument);
+                if let Some(base_arguments) = self.get_type_arguments_of_base(
+                    &argument_class,
+                    argument_arguments,
+                    &parameter_class,
+                ) {
+                    // Any element of the tuple can be an element of `tuple[T, ...]`
+                    if let [PythonType::Unbounded(element)] = parameter_arguments {
+                        if parameter_class.details.qual_name == "builtins.tuple" {
+                            self.solve_type_vars(
+                                element,
+                                &tuple_element_type(&base_arguments),
+                                solution,
+                            );
+                            return;
+                        }
+                    }
+                    for (p, a) in parameter_arguments.iter().zip(base_arguments.iter()) {
+                        self.solve_type_vars(p, a, solution);
+                    }
+                } else if self.is_protocol(&parameter_class) {
+                    self.solve_protocol_type_vars(
+                        &parameter_class,
+                        parameter_arguments,
+                        &argument_class,
+                        argument_arguments,
+                        solution,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// A class that is passed for a protocol solves the type vars of the protocol
+    /// through the types of its members, e.g. `__next__` for `SupportsNext[T]`.
+    fn solve_protocol_type_vars(
+        &self,
+        protocol: &ClassType,
+        protocol_arguments: &[PythonType],
+        class: &ClassType,
+        class_arguments: &[PythonType],
+        solution: &mut TypeVarSolution,
+    ) {
+        let key = (
+            class.details.qual_name.clone(),
+            protocol.details.qual_name.clone(),
+        );
+        if self.protocol_checks.borrow().contains(&key) {
+            return;
+        }
+        self.protocol_checks.borrow_mut().push(key);
+        for member in self.protocol_members(protocol) {
+            let Some(class_member) = self.lookup_on_class(
+                &self.get_dec_symbol_table(&class.details.declaration_path),
+                class,
+                &member,
+            ) else {
+                continue;
+            };
+            let Some(protocol_member) = self.lookup_on_class(
+                &self.get_dec_symbol_table(&protocol.details.declaration_path),
+                protocol,
+                &member,
+            ) else {
+                continue;
+            };
+            self.solve_type_vars(
+                &self.specialize_member_type(protocol_member, protocol, protocol_arguments),
+                &self.specialize_member_type(class_member, class, class_arguments),
+                solution,
+            );
+        }
+        self.protocol_checks.borrow_mut().pop();
+    }
+
+    /// Widens the solution of a type var so that it accepts the argument.
+    fn add_type_var_solution(
+        &self,
+        type_var: &TypeVar,
+        argument: &PythonType,
+        solution: &mut TypeVarSolution,
+    ) {
+        if !type_var.bounds.is_empty() {
+            self.add_constrained_type_var_solution(type_var, argument, solution);
+            return;
+        }
+        let argument = argument.clone();
+        let solved = match solution.remove(&type_var.name) {
+            None | Some(PythonType::Unknown) => argument,
+            Some(existing) if argument == PythonType::Unknown => existing,
+            Some(existing) if self.is_assignable(&argument, &existing) => existing,
+            Some(existing) if self.is_assignable(&existing, &argument) => argument,
+            Some(existing) => PythonType::union(vec![existing, argument]),
+        };
+        solution.insert(type_var.name.clone(), solved);
+    }
+
+    /// A constrained type var is solved to the first constraint that accepts every
+    /// argument. The solution is Unknown when the arguments need different constraints.
+    fn add_constrained_type_var_solution(
+        &self,
+        type_var: &TypeVar,
+        argument: &PythonType,
+        solution: &mut TypeVarSolution,
+    ) {
+        // Any does not decide which constraint is used
+        if matches!(argument, PythonType::Any | PythonType::Unknown) {
+            return;
+        }
+        let previous = solution.get(&type_var.name);
+        let solved = match argument {
+            // A type var that is passed through keeps its own constraints
+            PythonType::TypeVar(_) => match previous {
+                None => argument.clone(),
+                Some(previous) if previous == argument => argument.clone(),
+                Some(_) => PythonType::Unknown,
+            },
+            _ => match type_var.bounds.iter().find(|constraint| {
+                self.is_assignable(argument, constraint)
+                    && previous.map_or(true, |p| self.is_assignable(p, constraint))
+            }) {
+                Some(constraint) if argument.is_instance() => to_instance(constraint.clone()),
+                Some(constraint) => constraint.clone(),
+                None => PythonType::Unknown,
+            },
+        };
+        solution.insert(type_var.name.clone(), solved);
+    }
+
+    /// Returns the type arguments that a class passes to one of its base classes,
+    /// e.g. `[int]` for `Sequence` when the class is `list[int]`.
+    fn get_type_arguments_of_base(
+        &self,
+        class: &ClassType,
+        arguments: &[PythonType],
+        base: &ClassType,
+    ) -> Option<Vec<PythonType>> {
+        if class.details.qual_name == base.details.qual_name {
+            return (!arguments.is_empty()).then(|| arguments.to_vec());
+        }
+        let solution = type_var_solution(
+            &class.type_parameters,
+            &class_type_arguments(class, arguments),
+        );
+        class.base_classes.iter().find_map(|class_base| {
+            let base_arguments: Vec<PythonType> = class_base
+                .specialized
+                .iter()
+                .map(|t| self.apply_type_var_solution(t, &solution))
+                .collect();
+            self.get_type_arguments_of_base(class_base, &base_arguments, base)
+        })
+    }
+
+    /// Replaces the type parameters of a class in the type of one of its members with
+    /// the type arguments of the class. The type parameters of the base classes are
+    /// replaced with the type arguments that the class passes to them.
+    fn specialize_member_type(
+        &self,
+        member: PythonType,
+        class: &ClassType,
+        arguments: &[PythonType],
+    ) -> PythonType {
+        let mut solution = TypeVarSolution::new();
+        self.add_class_type_arguments(class, arguments, &mut solution);
+        if solution.is_empty() {
+            return member;
+        }
+        self.apply_type_var_solution(&member, &solution)
+    }
+
+    fn add_class_type_arguments(
+        &self,
+        class: &ClassType,
+        arguments: &[PythonType],
+        solution: &mut TypeVarSolution,
+    ) {
+        for (name, argument) in type_var_solution(
+            &class.type_parameters,
+            &class_type_arguments(class, arguments),
+        ) {
+            solution.entry(name).or_insert(argument);
+        }
+        for base in &class.base_classes {
+            let base_arguments: Vec<PythonType> = base
+                .specialized
+                .iter()
+                .map(|t| self.apply_type_var_solution(t, solution))
+                .collect();
+            self.add_class_type_arguments(base, &base_arguments, solution);
+        }
+    }
+
+    fn apply_type_var_solution_to_callable(
+        &self,
+        c: &CallableType,
+        solution: &TypeVarSolution,
+    ) -> CallableType {
+        let mut callable = c.clone();
+        if let Some(PythonType::Callable(parameters)) =
+            param_spec_name(c).and_then(|name| solution.get(name))
+        {
+            callable.signature = parameters.signature.clone();
+            callable.method_kind = parameters.method_kind;
+        }
+        for arg in callable.signature.iter_mut() {
+            let parameter = arg.parameter_mut();
+            parameter.python_type = self.apply_type_var_solution(&parameter.python_type, solution);
+        }
+        callable.return_type = self.apply_type_var_solution(&c.return_type, solution);
+        callable.overloads = c
+            .overloads
+            .iter()
+            .map(|overload| self.apply_type_var_solution_to_callable(overload, solution))
+            .collect();
+        callable
+    }
+
+    /// Replaces the type vars in a type with their solutions. Type vars without a
+    /// solution are kept.
+    fn apply_type_var_solution(
+        &self,
+        python_type: &PythonType,
+        solution: &TypeVarSolution,
+    ) -> PythonType {
+        let apply = |types: &[PythonType]| -> Vec<PythonType> {
+            types
+                .iter()
+                .map(|t| self.apply_type_var_solution(t, solution))
+                .collect()
+        };
+        match python_type {
+            PythonType::TypeVar(type_var) => solution
+                .get(&type_var.name)
+                .cloned()
+                .unwrap_or_else(|| python_type.clone()),
+            PythonType::Class(c) if !c.specialized.is_empty() => {
+                let mut specialized_class = c.clone();
+                specialized_class.specialized = apply(&c.specialized);
+                PythonType::Class(specialized_class)
+            }
+            PythonType::Instance(i) => PythonType::Instance(InstanceType::new(
+                i.class_type.clone(),
+                apply(&i.specialized_type_parameters),
+            )),
+            PythonType::MultiValue(members) => PythonType::union(apply(members)),
+            PythonType::Optional(inner) => {
+                PythonType::Optional(Box::new(self.apply_type_var_solution(inner, solution)))
+            }
+            PythonType::Unbounded(element) => {
+                PythonType::Unbounded(Box::new(self.apply_type_var_solution(element, solution)))
+            }
+            PythonType::Callable(c) => PythonType::Callable(Box::new(
+                self.apply_type_var_solution_to_callable(c, solution),
+            )),
+            PythonType::Coroutine(c) => {
+                let mut coroutine = c.clone();
+                coroutine.return_type = self.apply_type_var_solution(&c.return_type, solution);
+                coroutine.send_type = self.apply_type_var_solution(&c.send_type, solution);
+                coroutine.yield_type = self.apply_type_var_solution(&c.yield_type, solution);
+                PythonType::Coroutine(coroutine)
+            }
+            _ => python_type.clone(),
+        }
+    }
+
+    fn lookup_on_class(
+        &self,
+        symbol_table: &SymbolTable,
+        c: &ClassType,
+        method_name: &str,
+    ) -> Option<PythonType> {
+        let class_symbol_table = self.get_dec_symbol_table(&c.details.declaration_path);
+        let class_scope = c.details.class_scope_id;
+        // TODO: Probably should implement mro here
+        // Try to find on the class and it's base classes.
+        let bases = self.get_base_classes(c);
+        let symbol = class_symbol_table.lookup_attribute(method_name, class_scope);
+        if symbol.is_none() {
+            for base in bases {
+                let base_class = base.expect_class();
+                let class_symbol_table =
+                    self.get_dec_symbol_table(&base_class.details.declaration_path);
+                if let Some(attribute_on_base) = class_symbol_table
+                    .lookup_attribute(method_name, base_class.details.class_scope_id)
+                {
+                    return Some(self.get_symbol_type(
+                        attribute_on_base,
+                        &class_symbol_table,
+                        None,
+                    ));
+                }
+                if let Some(synthesized) =
+                    self.get_synthesized_dataclass_member(&base_class, method_name)
+                {
+                    return Some(synthesized);
+                }
+                if let Some(ClassFields::NamedTuple(fields)) = &base_class.fields {
+                    if let Some(field) = fields.iter().find(|field| field.name == method_name) {
+                        return Some(field.python_type.clone());
+                    }
+                }
+            }
+        }
+
+        symbol.map(|node| self.get_symbol_type(node, symbol_table, None))
+    }
+
+    /// Returns the type of every declaration of a method, so all the overloads of a method
+    /// can be matched against the arguments.
+    fn lookup_method_overloads(&self, c: &ClassType, method_name: &str) -> Vec<CallableType> {
+        for base in self.get_base_classes(c) {
+            let base_class = base.expect_class();
+            let class_symbol_table =
+                self.get_dec_symbol_table(&base_class.details.declaration_path);
+            let Some(symbol) =
+                class_symbol_table.lookup_attribute(method_name, base_class.details.class_scope_id)
+            else {
+                match self.get_synthesized_dataclass_member(&base_class, method_name) {
+                    Some(PythonType::Callable(method)) => return vec![*method],
+                    Some(_) => return vec![],
+                    None => continue,
+                }
+            };
+            return symbol
+                .declarations
+                .iter()
+                .filter_map(|decl| {
+                    let Declaration::Function(f) = decl else {
+                        return None;
+                    };
+                    let scope_id = class_symbol_table.get_scope(f.function_node.node.start);
+                    match self.get_function_type(&class_symbol_table, f, scope_id) {
+                        PythonType::Callable(callable) => Some(*callable),
+                        _ => None,
+                    }
+                })
+                .collect();
+        }
+        vec![]
+    }
+
+    fn get_function_signature(
+        &self,
+        arguments: &ast::Arguments,
+        symbol_table: &symbol_table::SymbolTable,
+        scope_id: u32,
+    ) -> Vec<CallableArgs> {
+        let mut signature = Vec::with_capacity(arguments.len());
+        let annotation_scope_id = self.get_annotation_scope_id(symbol_table, scope_id);
+        let parameter = |argument: &ast::Arg, has_default: bool| -> CallableParameter {
+            let python_type = match &argument.annotation {
+                Some(type_annotation) => {
+                    self.get_annotation_type(type_annotation, symbol_table, annotation_scope_id)
+                }
+                None => self.get_name_type(
+                    &argument.arg,
+                    Some(argument.node.end),
+                    symbol_table,
+                    scope_id,
+                ),
+            };
+            CallableParameter::new(argument.arg.clone(), python_type, has_default)
+        };
+        // Default values belong to the last positional parameters
+        let positional_count = arguments.posonlyargs.len() + arguments.args.len();
+        let first_default = positional_count.saturating_sub(arguments.defaults.len());
+        for (index, argument) in arguments.posonlyargs.iter().enumerate() {
+            signature.push(CallableArgs::PositionalOnly(parameter(
+                argument,
+                index >= first_default,
+            )));
+        }
+        for (index, positional) in arguments.args.iter().enumerate() {
+            signature.push(CallableArgs::Positional(parameter(
+                positional,
+                index + arguments.posonlyargs.len() >= first_default,
+            )));
+        }
+        for (index, argument) in arguments.kwonlyargs.iter().enumerate() {
+            let has_default = arguments
+                .kw_defaults
+                .get(index)
+                .is_some_and(|default| default.is_some());
+            if argument.annotation.is_some() {
+                signature.push(CallableArgs::Keyword(parameter(argument, has_default)));
+            } else {
+                signature.push(CallableArgs::Keyword(CallableParameter::new(
+                    argument.arg.clone(),
+                    PythonType::Unknown,
+                    has_default,
+                )));
+            }
+        }
+
+        if let Some(vararg) = &arguments.vararg {
+            let python_type = match &vararg.annotation {
+                Some(type_annotation) => {
+                    self.get_annotation_type(type_annotation, symbol_table, annotation_scope_id)
+                }
+                None => PythonType::Unknown,
+            };
+            signature.push(CallableArgs::Args(CallableParameter::new(
+                vararg.arg.clone(),
+                python_type,
+                false,
+            )));
+        }
+        if let Some(kwarg) = &arguments.kwarg {
+            // `**kwargs: Unpack[TD]` takes the items of the typed dict as keyword arguments
+            // https://typing.readthedocs.io/en/latest/spec/callables.html#unpack-for-keyword-arguments
+            if let Some(Expression::Subscript(s)) = &kwarg.annotation {
+                let typed_dict_fields = self
+                    .get_annotation_type(&s.slice, symbol_table, annotation_scope_id)
+                    .as_class()
+                    .filter(|_| is_special_form_name(&s.value, "Unpack"))
+                    .and_then(|c| self.get_typed_dict_fields(c));
+                if let Some(fields) = typed_dict_fields {
+                    signature.extend(fields.into_iter().map(|field| {
+                        CallableArgs::Keyword(CallableParameter::new(
+                            field.name,
+                            field.python_type,
+                            !field.required,
+                        ))
+                    }));
+                    return signature;
+                }
+            }
+            let python_type = match &kwarg.annotation {
+                Some(type_annotation) => {
+                    self.get_annotation_type(type_annotation, symbol_table, annotation_scope_id)
+                }
+                None => PythonType::Unknown,
+            };
+            signature.push(CallableArgs::KwArgs(CallableParameter::new(
+                kwarg.arg.clone(),
+                python_type,
+                false,
+            )));
+        }
+
+        signature
+    }
+
+    /// The type of a function symbol. An overloaded function is typed as its first
+    /// overload and carries the signatures of all overloads.
+    fn get_function_symbol_type(
+        &self,
+        symbol: &SymbolTableNode,
+        f: &symbol_table::Function,
+        symbol_table: &SymbolTable,
+    ) -> PythonType {
+        // to be able to get the function signature correctly we use the scope id of the
+        // function. Since parameters are defined in that scope.
+        let function_type = |f: &symbol_table::Function| {
+            self.get_function_type(
+                symbol_table,
+                f,
+                symbol_table.get_scope(f.function_node.node.start),
+            )
+        };
+        let overloads = symbol.overloads();
+        if overloads.is_empty() {
+            return function_type(symbol.first_property_accessor().unwrap_or(f));
+        }
+        let overloads: Vec<CallableType> = overloads
+            .into_iter()
+            .filter_map(|f| function_type(f).callable())
+            .map(|callable| *callable)
+            .collect();
+        let Some(first) = overloads.first() else {
+            return PythonType::Unknown;
+        };
+        let mut callable = first.clone();
+        callable.overloads = overloads;
+        PythonType::Callable(Box::new(callable))
+    }
+
+    /// Annotations of parameters and return types are evaluated in the scope that
+    /// contains the function, so a parameter cannot shadow the type in its own annotation.
+    fn get_annotation_scope_id(&self, symbol_table: &SymbolTable, function_scope_id: u32) -> u32 {
+        symbol_table
+            .get_parent_scope_of(function_scope_id)
+            .map_or(function_scope_id, |scope| scope.id)
+    }
+
+    // TODO(coroutine_annotation): These two are very similar. Maybe should be presented in another
+    // way. Async version only needs the return type to be a coroutine.
+    fn get_function_type(
+        &self,
+        symbol_table: &symbol_table::SymbolTable,
+        f: &symbol_table::Function,
+        arguments_scope_id: u32,
+    ) -> PythonType {
+        // TODO: handle default values
+
+        let name = f.function_node.name;
+        let signature =
+            self.get_function_signature(&f.function_node.args, symbol_table, arguments_scope_id);
+        let return_type = match &f.function_node.returns {
+            Some(type_annotation) => self.get_annotation_type(
+                type_annotation,
+                symbol_table,
+                self.get_annotation_scope_id(symbol_table, arguments_scope_id),
+            ),
+            None if f.is_generator => self.infer_generator_type(
+                symbol_table,
+                arguments_scope_id,
+                &f.yield_statements,
+                &f.yield_from_statements,
+                &f.return_statements,
+                false,
+            ),
+            None => PythonType::Unknown,
+        };
+        let file = self
+            .build_manager
+            .files
+            .get(&f.declaration_path.symbol_table_id)
+            .unwrap();
+        let mut callable = CallableType::new(
+            intern_lookup(name).to_string(),
+            signature,
+            return_type,
+            false,
+        );
+        if f.is_method {
+            callable.method_kind = method_kind(&f.function_node.decorator_list);
+        }
+        self.apply_decorators(
+            PythonType::Callable(Box::new(callable)),
+            &f.function_node.decorator_list,
+            symbol_table,
+            f.declaration_path.scope_id,
+        )
+    }
+
+    fn get_async_function_type(
+        &self,
+        symbol_table: &symbol_table::SymbolTable,
+        f: &symbol_table::AsyncFunction,
+        scope_id: u32,
+    ) -> PythonType {
+        let arguments = f.function_node.args.clone();
+        let name = f.function_node.name;
+        let signature = self.get_function_signature(&f.function_node.args, symbol_table, scope_id);
+        let return_type =
+            f.function_node
+                .returns
+                .clone()
+                .map_or(PythonType::Unknown, |type_annotation| {
+                    self.get_annotation_type(
+                        &type_annotation,
+                        symbol_table,
+                        self.get_annotation_scope_id(symbol_table, scope_id),
+                    )
+                });
+
+        let file = self
+            .build_manager
+            .files
+            .get(&f.declaration_path.symbol_table_id)
+            .unwrap();
+        // Calling an async generator function returns the generator without awaiting
+        let return_type = if !f.is_generator {
+            PythonType::Coroutine(Box::new(types::CoroutineType {
+                return_type,
+                send_type: PythonType::Any,
+                yield_type: PythonType::Any,
+            }))
+        } else if f.function_node.returns.is_some() {
+            return_type
+        } else {
+            self.infer_generator_type(
+                symbol_table,
+                scope_id,
+                &f.yield_statements,
+                &f.yield_from_statements,
+                &f.return_statements,
+                true,
+            )
+        };
+        let mut callable = CallableType::new(
+            intern_lookup(name).to_string(),
+            signature,
+            return_type,
+            true,
+        );
+        if f.is_method {
+            callable.method_kind = method_kind(&f.function_node.decorator_list);
+        }
+        self.apply_decorators(
+            PythonType::Callable(Box::new(callable)),
+            &f.function_node.decorator_list,
+            symbol_table,
+            f.declaration_path.scope_id,
+        )
+    }
+
+    /// Generator functions without a declared return type return a `Generator` of the
+    /// values that they yield and return, async ones return an `AsyncGenerator`.
+    fn infer_generator_type(
+        &self,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+        yield_statements: &[ast::Yield],
+        yield_from_statements: &[ast::YieldFrom],
+        return_statements: &[ast::Return],
+        is_async: bool,
+    ) -> PythonType {
+        // Recursive generators yield from themselves
+        let reference = (symbol_table.id, scope_id);
+        if self.generator_references.borrow().contains(&reference) {
+            return PythonType::Unknown;
+        }
+        self.generator_references.borrow_mut().push(reference);
+        let get_type = |expr: &Expression| {
+            self.get_type(expr, symbol_table, scope_id)
+                .unwrap_or(PythonType::Unknown)
+        };
+        let mut yield_types: Vec<PythonType> = yield_statements
+            .iter()
+            .map(|y| y.value.as_ref().map_or(PythonType::None, get_type))
+            .collect();
+        yield_types.extend(
+            yield_from_statements
+                .iter()
+                .map(|y| self.get_iterated_type(&get_type(&y.value))),
+        );
+        let return_types: Vec<PythonType> = return_statements
+            .iter()
+            .map(|r| r.value.as_ref().map_or(PythonType::None, get_type))
+            .collect();
+        self.generator_references.borrow_mut().pop();
+
+        let yield_type = PythonType::union(yield_types);
+        let return_type = if return_types.is_empty() {
+            PythonType::None
+        } else {
+            PythonType::union(return_types)
+        };
+        let (name, arguments) = if is_async {
+            ("AsyncGenerator", vec![yield_type, PythonType::Any])
+        } else {
+            ("Generator", vec![yield_type, PythonType::Any, return_type])
+        };
+        self.get_typing_class(name)
+            .map_or(PythonType::Unknown, |class| {
+                PythonType::Instance(InstanceType::new(class, arguments))
+            })
+    }
+
+    /// The yield, send and return types of the generators that are declared to return
+    /// the type. None when a generator cannot be declared to return it.
+    pub fn get_declared_generator_types(
+        &self,
+        declared: &PythonType,
+    ) -> Option<(PythonType, PythonType, PythonType)> {
+        let (class, arguments) = match declared {
+            PythonType::Any | PythonType::Unknown => {
+                return Some((PythonType::Any, PythonType::Any, PythonType::Any))
+            }
+            PythonType::Class(c) => (c, &c.specialized),
+            PythonType::Instance(i) => (&i.class_type, &i.specialized_type_parameters),
+            _ => return None,
+        };
+        let argument = |index: usize| arguments.get(index).cloned().unwrap_or(PythonType::Any);
+        match class.details.qual_name.as_str() {
+            "typing.Generator" => Some((argument(0), argument(1), argument(2))),
+            "typing.AsyncGenerator" => Some((argument(0), argument(1), PythonType::None)),
+            // Iterating over a generator sends None to it
+            "typing.Iterator"
+            | "typing.Iterable"
+            | "typing.AsyncIterator"
+            | "typing.AsyncIterable" => Some((argument(0), PythonType::None, PythonType::None)),
+            "builtins.object" => Some((PythonType::Any, PythonType::Any, PythonType::Any)),
+            _ => None,
+        }
+    }
+
+    /// The type of the values sent to the generator function of the scope, which is the
+    /// value of its yield expressions.
+    fn get_send_type(&self, symbol_table: &SymbolTable, scope_id: u32) -> PythonType {
+        let Some(SymbolTableType::Function(function_def)) = symbol_table
+            .get_scope_by_id(scope_id)
+            .map(|scope| &scope.kind)
+        else {
+            return PythonType::Unknown;
+        };
+        let Some(returns) = &function_def.returns else {
+            return PythonType::Any;
+        };
+        let declared = self.get_annotation_type(
+            returns,
+            symbol_table,
+            self.get_annotation_scope_id(symbol_table, scope_id),
+        );
+        self.get_declared_generator_types(&declared)
+            .map_or(PythonType::Unknown, |(_, send_type, _)| send_type)
+    }
+
+    /// The value that a generator returns to `yield from`. Other iterables return None.
+    fn get_generator_return_type(&self, delegate: &PythonType) -> PythonType {
+        match delegate {
+            PythonType::Instance(i) if i.class_type.details.qual_name == "typing.Generator" => i
+                .specialized_type_parameters
+                .get(2)
+                .cloned()
+                .unwrap_or(PythonType::Unknown),
+            PythonType::Any | PythonType::Unknown => delegate.clone(),
+            _ => PythonType::None,
+        }
+    }
+
+    /// The type of the items that iterating over a value gives, which is what the
+    /// `__next__` method of the iterator that its `__iter__` method returns returns.
+    pub fn get_iterated_type(&self, iterable: &PythonType) -> PythonType {
+        match iterable {
+            // Iterating over an enum class gives its members
+            PythonType::Class(c) if self.is_enum_class(c) => {
+                PythonType::Instance(InstanceType::new(c.clone(), vec![]))
+            }
+            PythonType::Any => PythonType::Any,
+            _ => {
+                let iterator = self.get_method_return_type(iterable, "__iter__");
+                self.get_method_return_type(&iterator, "__next__")
+            }
+        }
+    }
+
+    /// The type of the items that `async for` gives, which is what awaiting the result of
+    /// the `__anext__` method of the iterator that its `__aiter__` method returns gives.
+    pub fn get_async_iterated_type(&self, iterable: &PythonType) -> PythonType {
+        match iterable {
+            PythonType::Any | PythonType::Unknown => iterable.clone(),
+            _ => {
+                let iterator = self.get_method_return_type(iterable, "__aiter__");
+                let next = self.get_method_return_type(&iterator, "__anext__");
+                self.get_awaited_type(&next).unwrap_or(PythonType::Unknown)
+            }
+        }
+    }
+
+    /// The type of the value that `with` binds to its target, which is what the
+    /// `__enter__` method of the context manager returns. `async with` binds what awaiting
+    /// the result of its `__aenter__` method gives.
+    pub fn get_entered_type(&self, context_manager: &PythonType, is_async: bool) -> PythonType {
+        if !is_async {
+            return self.get_method_return_type(context_manager, "__enter__");
+        }
+        let entered = self.get_method_return_type(context_manager, "__aenter__");
+        self.get_awaited_type(&entered)
+            .unwrap_or(PythonType::Unknown)
+    }
+
+    /// The type of the value that awaiting a value gives. Besides coroutines, objects are
+    /// awaitable when their `__await__` method returns a generator, and awaiting them gives
+    /// the return value of that generator. None when the value is not awaitable.
+    pub fn get_awaited_type(&self, awaitable: &PythonType) -> Option<PythonType> {
+        match awaitable {
+            PythonType::Coroutine(coroutine) => Some(coroutine.return_type.clone()),
+            PythonType::Any | PythonType::Unknown => Some(awaitable.clone()),
+            PythonType::Class(_) | PythonType::Instance(_) => {
+                match self.has_attribute(awaitable, "__await__") {
+                    Some(false) => return None,
+                    None => return Some(PythonType::Unknown),
+                    Some(true) => (),
+                }
+                let generator = self.get_method_return_type(awaitable, "__await__");
+                Some(
+                    self.get_declared_generator_types(&generator)
+                        .map_or(PythonType::Unknown, |(_, _, return_type)| return_type),
+                )
+            }
+            PythonType::MultiValue(members) => members
+                .iter()
+                .map(|member| self.get_awaited_type(member))
+                .collect::<Option<Vec<_>>>()
+                .map(PythonType::union),
+            _ => None,
+        }
+    }
+
+    /// The return type of calling a method of a value without arguments.
+    fn get_method_return_type(&self, receiver: &PythonType, name: &str) -> PythonType {
+        let (class, arguments) = match receiver {
+            PythonType::Class(c) => (c, &c.specialized),
+            PythonType::Instance(i) => (&i.class_type, &i.specialized_type_parameters),
+            _ => return PythonType::Unknown,
+        };
+        match self.get_attribute_of_class(receiver, class, arguments, name) {
+            Some(PythonType::Callable(method)) => method.return_type,
+            _ => PythonType::Unknown,
+        }
+    }
+
+    /// Looks up a class that the `typing` module declares.
+    fn get_typing_class(&self, name: &str) -> Option<ClassType> {
+        let typing_path = self
+            .build_manager
+            .settings
+            .typeshed_path
+            .join("stdlib/typing.pyi");
+        let id = *self.build_manager.paths.get(&typing_path)?;
+        let symbol_table = self.get_symbol_table(&id);
+        let symbol = symbol_table.lookup_in_scope(name, 0)?;
+        self.get_symbol_type(symbol, &symbol_table, None).class()
+    }
+
+    /// Applies the decorators of a function to its type from the innermost one outwards.
+    /// A decorator turns the function into the type that calling the decorator with the
+    /// function returns. Decorators that decide how a method is bound are already part
+    /// of the function type.
+    fn apply_decorators(
+        &self,
+        function_type: PythonType,
+        decorators: &[Expression],
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> PythonType {
+        let kind = function_type
+            .as_callable()
+            .map_or(MethodKind::Function, |c| c.method_kind);
+        let mut decorated = function_type;
+        for decorator in decorators.iter().rev() {
+            if decorator_name(decorator)
+                .is_some_and(|name| name == "overload" || method_kind_of_decorator(name).is_some())
+            {
+                continue;
+            }
+            decorated = self.apply_decorator(decorator, decorated, symbol_table, scope_id);
+        }
+        // A function that a decorator returns is bound like the method it replaces
+        if let PythonType::Callable(c) = &mut decorated {
+            if c.method_kind == MethodKind::Function {
+                c.method_kind = kind;
+            }
+        }
+        decorated
+    }
+
+    /// The type that calling the decorator with the function returns. The function type
+    /// is kept when the decorator does not accept it or its result is not known, e.g.
+    /// decorators without annotations.
+    fn apply_decorator(
+        &self,
+        decorator: &Expression,
+        function_type: PythonType,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> PythonType {
+        let decorator_type = match self.get_type(decorator, symbol_table, scope_id) {
+            Ok(PythonType::Callable(c)) => *c,
+            Ok(PythonType::Instance(i)) => match self.get_call_method(&i, symbol_table) {
+                Some(c) => c,
+                None => return function_type,
+            },
+            _ => return function_type,
+        };
+        let candidates = if decorator_type.is_overloaded() {
+            decorator_type.overloads.clone()
+        } else {
+            vec![decorator_type]
+        };
+        let decorated = candidates.iter().find_map(|candidate| {
+            let parameter = candidate.signature.first()?;
+            if !matches!(
+                parameter,
+                CallableArgs::PositionalOnly(_) | CallableArgs::Positional(_)
+            ) || !self.is_assignable(&function_type, parameter.get_type())
+            {
+                return None;
+            }
+            let mut solution = TypeVarSolution::new();
+            self.solve_type_vars(parameter.get_type(), &function_type, &mut solution);
+            Some(self.apply_type_var_solution(&candidate.return_type, &solution))
+        });
+        match decorated {
+            None | Some(PythonType::Unknown) => function_type,
+            Some(decorated) => to_instance(decorated),
+        }
+    }
+
+    /// The bound `__call__` method of an instance, which is called when the instance is.
+    fn get_call_method(
+        &self,
+        instance: &InstanceType,
+        symbol_table: &SymbolTable,
+    ) -> Option<CallableType> {
+        let call_method = self.lookup_on_class(symbol_table, &instance.class_type, "__call__")?;
+        let call_method = self.specialize_member_type(
+            call_method,
+            &instance.class_type,
+            &instance.specialized_type_parameters,
+        );
+        self.bind_member(call_method, &PythonType::Instance(instance.clone()))
+            .callable()
+            .map(|c| *c)
+    }
+
+    /// The class and its base classes in their method resolution order
+    fn get_base_classes(&self, c: &ClassType) -> Vec<PythonType> {
+        self.get_mro(c).into_iter().map(PythonType::Class).collect()
+    }
+
+    /// The method resolution order of a class, which starts with the class itself and
+    /// ends with `object`. Classes whose hierarchy is inconsistent use the order in which
+    /// a depth first search visits their bases.
+    pub fn get_mro(&self, c: &ClassType) -> Vec<ClassType> {
+        match self.compute_mro(c) {
+            Ok(mro) | Err(mro) => mro,
+        }
+    }
+
+    /// Whether the bases of the class can be linearized into a method resolution order
+    pub fn has_consistent_mro(&self, c: &ClassType) -> bool {
+        self.compute_mro(c).is_ok()
+    }
+
+    /// Computes the method resolution order of a class with the C3 linearization, the
+    /// class followed by the merge of the orders of its bases and the bases themselves.
+    /// Special forms like `Generic` and `Protocol` are not part of the order.
+    /// https://docs.python.org/3/howto/mro.html
+    fn compute_mro(&self, c: &ClassType) -> Result<Vec<ClassType>, Vec<ClassType>> {
+        // Classes that are created by calling `NamedTuple` or `TypedDict` share their
+        // declaration with other classes
+        let key = (
+            c.details.declaration_path.symbol_table_id,
+            c.details.class_scope_id,
+            c.details.qual_name.clone(),
+        );
+        if c.fields.is_none() {
+            if let Some(cached) = self.mro_cache.borrow().get(&key) {
+                // The cached order starts with the class as it was first evaluated
+                let with_class = |mro: &Vec<ClassType>| {
+                    std::iter::once(c.clone())
+                        .chain(mro.iter().skip(1).cloned())
+                        .collect()
+                };
+                return cached.as_ref().map(with_class).map_err(with_class);
+            }
+        }
+        let object_class = self
+            .get_builtin_type("object")
+            .and_then(|object| object.class())
+            .expect("object is a builtin class");
+        if same_class(c, &object_class) {
+            return Ok(vec![c.clone()]);
+        }
+        let bases: Vec<ClassType> = c
+            .base_classes
+            .iter()
+            .filter(|base| !base.details.special)
+            .cloned()
+            .collect();
+        let mut sequences: Vec<Vec<ClassType>> =
+            bases.iter().map(|base| self.get_mro(base)).collect();
+        if bases.is_empty() {
+            sequences.push(vec![object_class.clone()]);
+        }
+        sequences.push(bases);
+        let mro = match c3_merge(sequences.clone()) {
+            Some(merged) => Ok(std::iter::once(c.clone()).chain(merged).collect()),
+            None => {
+                let mut fallback = vec![c.clone()];
+                for class in sequences.into_iter().flatten() {
+                    if !same_class(&class, &object_class)
+                        && !fallback.iter().any(|visited| same_class(visited, &class))
+                    {
+                        fallback.push(class);
+                    }
+                }
+                fallback.push(object_class);
+                Err(fallback)
+            }
+        };
+        if c.fields.is_none() {
+            self.mro_cache.borrow_mut().insert(key, mro.clone());
+        }
+        mro
+    }
+
+    /// The type of an attribute that is accessed through an instance of a class. The
+    /// lookup follows the descriptor protocol: data descriptors like properties come
+    /// before the attributes that are assigned on the instance, which come before
+    /// methods and other class attributes. Attributes that are not found are provided by
+    /// `__getattr__` or looked up on the metaclass.
+    /// https://docs.python.org/3/howto/descriptor.html#invocation-from-an-instance
+    // TODO: Classes and their instances are not distinguished yet so classes use the
+    // same lookup.
+    fn get_attribute_of_class(
+        &self,
+        receiver: &PythonType,
+        class: &ClassType,
+        arguments: &[PythonType],
+        name: &str,
+    ) -> Option<PythonType> {
+        let specialize = |member| self.specialize_member_type(member, class, arguments);
+        let class_attribute = self
+            .lookup_declared_attribute(class, name, AttributeKind::Class)
+            .map(specialize);
+        if let Some(class_attribute) = &class_attribute {
+            if self.is_data_descriptor(class_attribute) {
+                return Some(self.get_descriptor_value(class_attribute.clone(), receiver));
+            }
+        }
+        if let Some(instance_attribute) =
+            self.lookup_declared_attribute(class, name, AttributeKind::Instance)
+        {
+            return Some(specialize(instance_attribute));
+        }
+        if let Some(class_attribute) = class_attribute {
+            return Some(self.get_descriptor_value(class_attribute, receiver));
+        }
+        if let Some(getattr) =
+            self.lookup_declared_attribute(class, "__getattr__", AttributeKind::Class)
+        {
+            if let PythonType::Callable(getattr) = self.bind_member(specialize(getattr), receiver) {
+                return Some(getattr.return_type);
+            }
+        }
+        let metaclass = self.get_metaclass(class)?;
+        let metaclass_attribute =
+            self.lookup_declared_attribute(&metaclass, name, AttributeKind::Class)?;
+        Some(self.get_descriptor_value(metaclass_attribute, &PythonType::Class(class.clone())))
+    }
+
+    /// The type of an attribute that is accessed through the class object rather than
+    /// through an instance of it.
+    fn get_attribute_of_class_object(
+        &self,
+        receiver: &PythonType,
+        class: &ClassType,
+        name: &str,
+    ) -> Option<PythonType> {
+        match self.lookup_declared_attribute(class, name, AttributeKind::Class) {
+            Some(member @ PythonType::Callable(_)) => Some(self.bind_class_member(
+                self.specialize_member_type(member, class, &class.specialized),
+                receiver,
+            )),
+            _ => self.get_attribute_of_class(receiver, class, &class.specialized, name),
+        }
+    }
+
+    /// Whether the expression names a class, like `C`, `module.C` or `cls`, so the value
+    /// is the class object. Instances of classes evaluate to class types as well.
+    fn refers_to_class(
+        &self,
+        expr: &Expression,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> bool {
+        let declaration = match expr {
+            Expression::Name(name) => self
+                .get_declaration_of(&name.id, symbol_table, scope_id)
+                .or_else(|| self.get_declaration_of(&name.id, &self.get_symbol_table(&Id(0)), 0)),
+            Expression::Attribute(attribute) => {
+                let Ok(PythonType::Module(module)) =
+                    self.get_type(&attribute.value, symbol_table, scope_id)
+                else {
+                    return false;
+                };
+                self.get_declaration_of(
+                    &attribute.attr,
+                    &self.get_symbol_table(&module.module_id),
+                    0,
+                )
+            }
+            _ => None,
+        };
+        match declaration {
+            Some(Declaration::Class(_)) => true,
+            // `cls` in class methods
+            Some(Declaration::Parameter(p)) if p.is_first => symbol_table
+                .get_scope_by_id(p.declaration_path.scope_id)
+                .and_then(|scope| scope.kind.as_function())
+                .is_some_and(|f| method_kind(&f.decorator_list) == MethodKind::Class),
+            _ => false,
+        }
+    }
+
+    /// Whether an attribute can be accessed on a value of the type. `None` when that is
+    /// not known, for example when a base class is not resolved or the attributes are
+    /// looked up dynamically.
+    pub fn has_attribute(&self, receiver: &PythonType, name: &str) -> Option<bool> {
+        if self.get_enum_attribute_type(receiver, name).is_some() {
+            return Some(true);
+        }
+        let (class, arguments) = match receiver {
+            PythonType::Module(module) => return self.module_has_attribute(module, name),
+            PythonType::Class(c) => (c, c.specialized.as_slice()),
+            PythonType::Instance(i) => (&i.class_type, i.specialized_type_parameters.as_slice()),
+            PythonType::LiteralValue(KnownValue {
+                literal_value: LiteralValue::Enum(member),
+            }) => (member.class.as_ref(), [].as_slice()),
+            _ => return None,
+        };
+        if !self.has_known_attributes(class) {
+            return None;
+        }
+        if self
+            .get_attribute_of_class(receiver, class, arguments, name)
+            .is_some()
+        {
+            return Some(true);
+        }
+        // Classes are instances of `type` when they do not declare a metaclass
+        if let PythonType::Class(_) = receiver {
+            let type_class = self.get_builtin_type("type")?.class()?;
+            return Some(
+                self.lookup_declared_attribute(&type_class, name, AttributeKind::Class)
+                    .is_some(),
+            );
+        }
+        Some(false)
+    }
+
+    /// The attributes that a class or module declares, used to suggest names for
+    /// attributes that are not found.
+    pub fn get_attribute_names(&self, receiver: &PythonType) -> Vec<String> {
+        let class = match receiver {
+            PythonType::Module(module) => {
+                let symbol_table = self.get_symbol_table(&module.module_id);
+                return sorted_symbol_names(symbol_table.global_scope());
+            }
+            PythonType::Class(c) => c,
+            PythonType::Instance(i) => &i.class_type,
+            PythonType::LiteralValue(KnownValue {
+                literal_value: LiteralValue::Enum(member),
+            }) => member.class.as_ref(),
+            _ => return vec![],
+        };
+        let mut names = vec![];
+        for base in self.get_mro(class) {
+            let symbol_table = self.get_dec_symbol_table(&base.details.declaration_path);
+            if let Some(scope) = symbol_table.get_scope_by_id(base.details.class_scope_id) {
+                names.extend(sorted_symbol_names(scope));
+            }
+        }
+        names
+    }
+
+    /// The attributes of a class are known when all of its base classes are resolved and
+    /// none of them looks up attributes with `__getattribute__`.
+    fn has_known_attributes(&self, class: &ClassType) -> bool {
+        // `type[C]` and classes created by calling `NamedTuple` or `TypedDict` are not
+        // declared by a class body
+        if class.details.qual_name == "builtins.type" || class.fields.is_some() {
+            return false;
+        }
+        self.get_mro(class).iter().all(|base| {
+            let declared_bases = base
+                .details
+                .class_node
+                .as_ref()
+                .map_or(0, |class_node| class_node.bases.len());
+            let has_special_base = base.base_classes.iter().any(|b| {
+                b.details.special && !matches!(b.details.name.as_str(), "Generic" | "Protocol")
+            });
+            let symbol_table = self.get_dec_symbol_table(&base.details.declaration_path);
+            let overrides_getattribute = base.details.qual_name != "builtins.object"
+                && symbol_table
+                    .lookup_attribute("__getattribute__", base.details.class_scope_id)
+                    .is_some();
+            base.base_classes.len() >= declared_bases
+                && !has_special_base
+                && !overrides_getattribute
+        })
+    }
+
+    /// Modules have the names that they declare or import with `import *`, and their
+    /// submodules. Modules that define `__getattr__` can have any attribute.
+    fn module_has_attribute(&self, module: &ModuleRef, name: &str) -> Option<bool> {
+        let symbol_table = self.get_symbol_table(&module.module_id);
+        if symbol_table.lookup_in_scope("__getattr__", 0).is_some() {
+            return None;
+        }
+        if symbol_table.lookup_in_scope(name, 0).is_some()
+            || MODULE_ATTRIBUTES.contains(&name)
+            || self.is_star_imported(name, &symbol_table)
+        {
+            return Some(true);
+        }
+        let is_package = symbol_table
+            .file_path
+            .file_stem()
+            .is_some_and(|stem| stem == "__init__");
+        let Some(package_dir) = symbol_table.file_path.parent().filter(|_| is_package) else {
+            return Some(false);
+        };
+        let is_submodule = package_dir.join(name).is_dir()
+            || package_dir.join(format!("{name}.py")).is_file()
+            || package_dir.join(format!("{name}.pyi")).is_file();
+        Some(is_submodule)
+    }
+
+    /// The metaclass of a class is the one it declares or the one of its first base
+    /// class that has a metaclass.
+    fn get_metaclass(&self, class: &ClassType) -> Option<ClassType> {
+        self.get_mro(class)
+            .into_iter()
+            .find_map(|c| c.metaclass.map(|metaclass| *metaclass))
+    }
+
+    /// Looks up an attribute in the method resolution order of a class. Class attributes
+    /// are declared in the class body, instance attributes are assigned in its methods.
+    fn lookup_declared_attribute(
+        &self,
+        class: &ClassType,
+        name: &str,
+        kind: AttributeKind,
+    ) -> Option<PythonType> {
+        for base in self.get_mro(class) {
+            let symbol_table = self.get_dec_symbol_table(&base.details.declaration_path);
+            if let Some(symbol) = symbol_table.lookup_attribute(name, base.details.class_scope_id) {
+                let declaration = symbol.declarations.iter().rev().find(|declaration| {
+                    let in_class_body =
+                        declaration.declaration_path().scope_id == base.details.class_scope_id;
+                    in_class_body == (kind == AttributeKind::Class)
+                });
+                if let Some(declaration) = declaration {
+                    return Some(self.get_declaration_type(symbol, declaration, None));
+                }
+            }
+            if kind == AttributeKind::Instance {
+                continue;
+            }
+            if let Some(synthesized) = self.get_synthesized_dataclass_member(&base, name) {
+                return Some(synthesized);
+            }
+            if let Some(ClassFields::NamedTuple(fields)) = &base.fields {
+                if let Some(field) = fields.iter().find(|field| field.name == name) {
+                    return Some(field.python_type.clone());
+                }
+            }
+        }
+        None
+    }
+
+    /// Data descriptors define `__set__` or `__delete__` besides `__get__`, properties
+    /// are data descriptors too.
+    fn is_data_descriptor(&self, member: &PythonType) -> bool {
+        if let PythonType::Callable(c) = member {
+            return c.method_kind == MethodKind::Property;
+        }
+        let Some((class, _)) = descriptor_class(member) else {
+            return false;
+        };
+        let defines = |name| {
+            self.lookup_declared_attribute(class, name, AttributeKind::Class)
+                .is_some()
+        };
+        defines("__get__") && (defines("__set__") || defines("__delete__"))
+    }
+
+    /// The value of a class attribute that is accessed through the receiver. Descriptors
+    /// evaluate to what their `__get__` method returns for the receiver, methods are
+    /// bound to it.
+    fn get_descriptor_value(&self, member: PythonType, receiver: &PythonType) -> PythonType {
+        let Some((class, arguments)) = descriptor_class(&member) else {
+            return self.bind_member(member, receiver);
+        };
+        let Some(get) = self.lookup_declared_attribute(class, "__get__", AttributeKind::Class)
+        else {
+            return member;
+        };
+        let get = self.specialize_member_type(get, class, arguments);
+        let PythonType::Callable(get) = self.bind_member(get, &member) else {
+            return PythonType::Unknown;
+        };
+        let candidates = if get.is_overloaded() {
+            get.overloads.clone()
+        } else {
+            vec![*get]
+        };
+        // The overload for accessing the descriptor through an instance, not through
+        // the class where the instance is None
+        candidates
+            .iter()
+            .find_map(|candidate| {
+                let instance_type = candidate.signature.first()?.get_type();
+                if *instance_type == PythonType::None
+                    || !self.is_assignable(receiver, instance_type)
+                {
+                    return None;
+                }
+                let mut solution = TypeVarSolution::new();
+                self.solve_type_vars(instance_type, receiver, &mut solution);
+                Some(self.apply_type_var_solution(&candidate.return_type, &solution))
+            })
+            .unwrap_or(PythonType::Unknown)
+    }
+
+    // Resolving all type parameters in a python type based on the given type parameters dict
+    fn resolve_generics(
+        &self,
+        python_type: &PythonType,
+        type_parameters: &[PythonType],
+        specialized_types: &[PythonType],
+    ) -> PythonType {
+        let solution = type_var_solution(type_parameters, specialized_types);
+        self.apply_type_var_solution(python_type, &solution)
+    }
+
+    /// Returns whether an expression statement calls a function that never returns,
+    /// e.g. `sys.exit()`.
+    pub fn is_no_return_call(
+        &self,
+        expr: &Expression,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> bool {
+        let Expression::Call(call) = expr else {
+            return false;
+        };
+        let key = (symbol_table.id, call.node.start);
+        if let Some(is_no_return) = self.no_return_calls.borrow().get(&key) {
+            return *is_no_return;
+        }
+        let is_no_return = match self.get_type(&call.func, symbol_table, scope_id) {
+            Ok(PythonType::Callable(callable)) => matches!(
+                &callable.return_type,
+                PythonType::Class(c)
+                    if c.details.special && matches!(c.details.name.as_str(), "NoReturn" | "Never")
+            ),
+            _ => false,
+        };
+        self.no_return_calls.borrow_mut().insert(key, is_no_return);
+        is_no_return
+    }
+
+    /// Evaluates the type of a name at a reference by following the control flow of the
+    /// scope that contains the reference. The type comes from the assignments that reach
+    /// the reference and is narrowed by the conditions of the branches that lead to it.
+    /// Returns None when the flow is not analyzed, e.g. in class bodies, or the name is
+    /// unbound. The declarations of the symbol decide the type then.
+    fn get_flow_type(
+        &self,
+        name: &str,
+        position: u32,
+        symbol: &SymbolTableNode,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> Option<PythonType> {
+        if symbol_table.is_pyi_file()
+            || self
+                .flags
+                .get()
+                .intersects(GetTypeFlags::DEFERRED | GetTypeFlags::NO_FLOW_ANALYSIS)
+        {
+            return None;
+        }
+        // Assignment targets have the type of their own declaration
+        if let Some(decl) = symbol.declarations.iter().find(|d| {
+            d.declaration_path().symbol_table_id == symbol_table.id
+                && d.declaration_path().node.start == position
+        }) {
+            return Some(self.get_declaration_type(symbol, decl, None));
+        }
+
+        let scope = symbol_table.get_scope_by_id(scope_id)?;
+        let file;
+        let body = match &scope.kind {
+            SymbolTableType::Function(f) => f.body.as_slice(),
+            SymbolTableType::Module => {
+                file = self.build_manager.files.get(&symbol_table.id)?;
+                file.tree.body.as_slice()
+            }
+            _ => return None,
+        };
+
+        let key = (symbol_table.id, position);
+        if self.flow_references.borrow().contains(&key) {
+            return None;
+        }
+        let flags = self.flags.get();
+        self.flags.set(flags | GetTypeFlags::NO_FLOW_ANALYSIS);
+        let cfg = ControlFlowGraph::build(body, &|expr| {
+            self.is_no_return_call(expr, symbol_table, scope_id)
+        });
+        self.flags.set(flags);
+
+        let nodes = cfg.find_nodes_at(position);
+        if nodes.is_empty() {
+            return None;
+        }
+        let reference =
+            self.create_flow_reference(&cfg, name, position, symbol, symbol_table, scope_id);
+
+        self.flow_references.borrow_mut().push(key);
+        let flow_type = self.get_flow_type_at_nodes(&cfg, &reference, &nodes);
+        self.flow_references.borrow_mut().pop();
+        flow_type
+    }
+
+    /// Checks whether a name that is narrowed by the conditions of a scope has no
+    /// possible type when execution falls off the end of the scope, e.g. when the
+    /// branches of an if statement handle all members of an enum. The end of the scope
+    /// cannot be reached then.
+    pub fn is_end_narrowed_to_never(
+        &self,
+        cfg: &ControlFlowGraph,
+        position: u32,
+        symbol_table: &SymbolTable,
+        scope_id: u32,
+    ) -> bool {
+        let mut names: Vec<&str> = vec![];
+        for block in &cfg.blocks {
+            for condition in block.successors.iter().filter_map(|edge| edge.condition) {
+                for name in narrowed_names(condition.test) {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+        }
+        let incoming = cfg.incoming_edges();
+        names.into_iter().any(|name| {
+            let Some(symbol) = symbol_table.lookup_in_scope(name, scope_id) else {
+                return false;
+            };
+            let key = (symbol_table.id, position);
+            if self.flow_references.borrow().contains(&key) {
+                return false;
+            }
+            let reference =
+                self.create_flow_reference(cfg, name, position, symbol, symbol_table, scope_id);
+            self.flow_references.borrow_mut().push(key);
+            let block_types = self.get_block_flow_types(cfg, &reference, &incoming, &[cfg.end]);
+            let end_type =
+                self.get_flow_type_at_block_start(&reference, &incoming, &block_types, cfg.end);
+            self.flow_references.borrow_mut().pop();
+            end_type == Some(PythonType::Never)
+        })
+    }
+
+    fn create_flow_reference<'r>(
+        &self,
+        cfg: &ControlFlowGraph,
+        name: &'r str,
+        position: u32,
+        symbol: &'r SymbolTableNode,
+        symbol_table: &'r SymbolTable,
+        scope_id: u32,
+    ) -> FlowReference<'r> {
+        // Names that are assigned in the scope are unbound until the assignment runs,
+        // except for parameters. Other names keep the type they have outside the scope.
+        let entry_type = if cfg.local_names().contains(name) {
+            symbol
+                .declarations
+                .iter()
+                .find(|d| {
+                    matches!(d, Declaration::Parameter(_))
+                        && d.declaration_path().scope_id == scope_id
+                })
+                .map(|d| self.get_declaration_type(symbol, d, None))
+        } else {
+            Some(self.get_symbol_type(symbol, symbol_table, Some(position)))
+        };
+        FlowReference {
+            name,
+            position,
+            symbol,
+            symbol_table,
+            scope_id,
+            entry_type,
+        }
+    }
+
+    fn get_flow_type_at_nodes(
+        &self,
+        cfg: &ControlFlowGraph,
+        reference: &FlowReference,
+        nodes: &[(BlockId, usize)],
+    ) -> Option<PythonType> {
+        let blocks: Vec<BlockId> = nodes.iter().map(|(block, _)| *block).collect();
+        let incoming = cfg.incoming_edges();
+        let block_types = self.get_block_flow_types(cfg, reference, &incoming, &blocks);
+
+        let mut types = vec![];
+        for (block, index) in nodes {
+            let block_nodes = &cfg.blocks[*block].nodes;
+            let input =
+                self.get_flow_type_at_block_start(reference, &incoming, &block_types, *block);
+            if let Some(t) =
+                self.get_flow_type_after_nodes(reference, &block_nodes[..*index], input)
+            {
+                types.push(self.narrow_for_reference_in_node(t, &block_nodes[*index], reference));
+            }
+        }
+        if types.is_empty() {
+            None
+        } else {
+            Some(PythonType::union(types))
+        }
+    }
+
+    /// The types at the end of the blocks from which one of the given blocks can be
+    /// reached, None when the name is unbound.
+    fn get_block_flow_types(
+        &self,
+        cfg: &ControlFlowGraph,
+        reference: &FlowReference,
+        incoming: &[Vec<(BlockId, Edge)>],
+        blocks: &[BlockId],
+    ) -> HashMap<BlockId, Option<PythonType>> {
+        let ancestors = cfg.ancestors(blocks);
+
+        // Types at the end of the blocks, None when the name is unbound. A block is
+        // visited again when the type at the end of one of its predecessors changes.
+        let mut block_types: HashMap<BlockId, Option<PythonType>> = HashMap::new();
+        let mut visits: HashMap<BlockId, usize> = HashMap::new();
+        let mut worklist: BTreeSet<BlockId> = ancestors.iter().copied().collect();
+        while let Some(block) = worklist.pop_first() {
+            let input = self.get_flow_type_at_block_start(reference, incoming, &block_types, block);
+            let output = self.get_flow_type_after_nodes(reference, &cfg.blocks[block].nodes, input);
+            if block_types.get(&block) == Some(&output) {
+                continue;
+            }
+            block_types.insert(block, output);
+            let count = visits.entry(block).or_default();
+            *count += 1;
+            if *count > MAX_FLOW_VISITS {
+                continue;
+            }
+            for edge in &cfg.blocks[block].successors {
+                if ancestors.contains(&edge.target) {
+                    worklist.insert(edge.target);
+                }
+            }
+        }
+        block_types
+    }
+
+    fn get_flow_type_at_block_start(
+        &self,
+        reference: &FlowReference,
+        incoming: &[Vec<(BlockId, Edge)>],
+        block_types: &HashMap<BlockId, Option<PythonType>>,
+        block: BlockId,
+    ) -> Option<PythonType> {
+        if block == ENTRY {
+            return reference.entry_type.clone();
+        }
+        let mut types = vec![];
+        for (predecessor, edge) in &incoming[block] {
+            let Some(Some(t)) = block_types.get(predecessor) else {
+                continue;
+            };
+            types.push(match edge.condition {
+                Some(condition) => {
+                    self.narrow_type(t.clone(), condition.test, condition.value, reference)
+                }
+                None => t.clone(),
+            });
+        }
+        if types.is_empty() {
+            None
+        } else {
+            Some(PythonType::union(types))
+        }
+    }
+
+    /// The type after the nodes run is the type of the last assignment to the name in
+    /// the nodes. Without assignments the type does not change.
+    fn get_flow_type_after_nodes(
+        &self,
+        reference: &FlowReference,
+        nodes: &[FlowNode],
+        input: Option<PythonType>,
+    ) -> Option<PythonType> {
+        for node in nodes.iter().rev() {
+            match node.binding_of(reference.name) {
+                Some(Binding::Assigned) => return Some(self.get_assigned_type(node, reference)),
+                Some(Binding::Deleted) => return None,
+                None => {}
+            }
+        }
+        input
+    }
+
+    fn get_assigned_type(&self, node: &FlowNode, reference: &FlowReference) -> PythonType {
+        let Some(range) = node.range() else {
+            return self.get_symbol_type(reference.symbol, reference.symbol_table, None);
+        };
+        let decl = reference.symbol.declarations.iter().rfind(|d| {
+            let path = d.declaration_path();
+            path.symbol_table_id == reference.symbol_table.id
+                && range.start <= path.node.start
+                && path.node.start < range.end
+        });
+        match decl {
+            Some(decl) => {
+                let declared_type = self.get_declaration_type(reference.symbol, decl, None);
+                match decl {
+                    Declaration::Variable(symbol_table::Variable {
+                        type_annotation: Some(type_annotation),
+                        inferred_type_source: Some(source),
+                        ..
+                    }) if declared_type.union_members().len() > 1
+                        && !is_special_form_name(type_annotation, "TypeAlias") =>
+                    {
+                        let symbol_table = self.get_dec_symbol_table(decl.declaration_path());
+                        let assigned_type = self
+                            .get_type(source, &symbol_table, decl.declaration_path().scope_id)
+                            .unwrap_or(PythonType::Unknown);
+                        self.narrow_to_assigned_type(declared_type, &assigned_type)
+                    }
+                    _ => declared_type,
+                }
+            }
+            None => self.get_symbol_type(reference.symbol, reference.symbol_table, Some(range.end)),
+        }
+    }
+
+    /// The members of the declared type of a name that the assigned value can have, e.g.
+    /// `None` after `x: Optional[int] = None`.
+    fn narrow_to_assigned_type(
+        &self,
+        declared_type: PythonType,
+        assigned_type: &PythonType,
+    ) -> PythonType {
+        let assigned_members = assigned_type.union_members();
+        let members: Vec<PythonType> = declared_type
+            .union_members()
+            .into_iter()
+            .filter(|member| {
+                assigned_members
+                    .iter()
+                    .any(|assigned| self.is_assignable(assigned, member))
+            })
+            .collect();
+        if members.is_empty() {
+            declared_type
+        } else {
+            PythonType::union(members)
+        }
+    }
+
+    /// Narrows the type with the conditions that are evaluated before the reference
+    /// inside the node, e.g. the left operand of `x is not None and x.y`.
+    fn narrow_for_reference_in_node(
+        &self,
+        t: PythonType,
+        node: &FlowNode,
+        reference: &FlowReference,
+    ) -> PythonType {
+        let position = reference.position;
+        let roots: Vec<&Expression> = match node {
+            FlowNode::Expression(e) | FlowNode::Assignment(e) => vec![e],
+            FlowNode::Statement(stmt) => match stmt {
+                ast::Statement::AssignStatement(a) => {
+                    std::iter::once(&a.value).chain(a.targets.iter()).collect()
+                }
+                ast::Statement::AnnAssignStatement(a) => a.value.iter().collect(),
+                ast::Statement::AugAssignStatement(a) => vec![&a.target, &a.value],
+                ast::Statement::ExpressionStatement(e) => vec![e],
+                ast::Statement::ReturnStmt(r) => r.value.iter().collect(),
+                ast::Statement::Assert(a) => {
+                    // The message is only evaluated when the test is false
+                    if let Some(msg) = a.msg.as_ref().filter(|m| contains_position(m, position)) {
+                        let t = self.narrow_type(t, &a.test, false, reference);
+                        return self.narrow_for_reference_in_expression(t, msg, reference);
+                    }
+                    vec![&a.test]
+                }
+                _ => vec![],
+            },
+            _ => vec![],
+        };
+        match roots.into_iter().find(|e| contains_position(e, position)) {
+            Some(root) => self.narrow_for_reference_in_expression(t, root, reference),
+            None => t,
+        }
+    }
+
+    fn narrow_for_reference_in_expression(
+        &self,
+        t: PythonType,
+        expr: &Expression,
+        reference: &FlowReference,
+    ) -> PythonType {
+        let position = reference.position;
+        match expr {
+            Expression::BoolOp(b) => {
+                // An operand is only evaluated when the operands before it are true
+                // for `and` and false for `or`
+                let is_and = matches!(b.op, ast::BooleanOperator::And);
+                let mut t = t;
+                for value in &b.values {
+                    if contains_position(value, position) {
+                        return self.narrow_for_reference_in_expression(t, value, reference);
+                    }
+                    t = self.narrow_type(t, value, is_and, reference);
+                }
+                t
+            }
+            Expression::IfExp(i) => {
+                if contains_position(&i.body, position) {
+                    let t = self.narrow_type(t, &i.test, true, reference);
+                    self.narrow_for_reference_in_expression(t, &i.body, reference)
+                } else if contains_position(&i.orelse, position) {
+                    let t = self.narrow_type(t, &i.test, false, reference);
+                    self.narrow_for_reference_in_expression(t, &i.orelse, reference)
+                } else {
+                    self.narrow_for_reference_in_expression(t, &i.test, reference)
+                }
+            }
+            _ => match child_expressions(expr)
+                .into_iter()
+                .find(|e| contains_position(e, position))
+            {
+                Some(child) => self.narrow_for_reference_in_expression(t, child, reference),
+                None => t,
+            },
+        }
+    }
+
+    /// Narrows the type of the reference to the values for which the test has the
+    /// given truthiness.
+    /// https://typing.readthedocs.io/en/latest/spec/narrowing.html
+    fn narrow_type(
+        &self,
+        t: PythonType,
+        test: &Expression,
+        positive: bool,
+        reference: &FlowReference,
+    ) -> PythonType {
+        match test {
+            Expression::Name(n) if n.id == reference.name => self.narrow_truthiness(t, positive),
+            Expression::NamedExpr(n) => match &n.target {
+                Expression::Name(target) if target.id == reference.name => {
+                    self.narrow_truthiness(t, positive)
+                }
+                _ => self.narrow_type(t, &n.value, positive, reference),
+            },
+            Expression::UnaryOp(u) if matches!(u.op, ast::UnaryOperator::Not) => {
+                self.narrow_type(t, &u.operand, !positive, reference)
+            }
+            Expression::BoolOp(b) => {
+                let is_and = matches!(b.op, ast::BooleanOperator::And);
+                if is_and == positive {
+                    // Every operand has the truthiness of the whole expression
+                    b.values.iter().fold(t, |t, value| {
+                        self.narrow_type(t, value, positive, reference)
+                    })
+                } else {
+                    // One operand decides the result after the operands before it did not
+                    let mut types = vec![];
+                    let mut remaining = t;
+                    for value in &b.values {
+                        types.push(self.narrow_type(remaining.clone(), value, positive, reference));
+                        remaining = self.narrow_type(remaining, value, !positive, reference);
+                    }
+                    PythonType::union(types)
+                }
+            }
+            Expression::Compare(c) if c.ops.len() == 1 => self.narrow_for_comparison(
+                t,
+                &c.left,
+                &c.ops[0],
+                &c.comparators[0],
+                positive,
+                reference,
+            ),
+            Expression::Call(c) => self.narrow_for_call(t, c, positive, reference),
+            _ => t,
+        }
+    }
+
+    fn narrow_for_comparison(
+        &self,
+        t: PythonType,
+        left: &Expression,
+        op: &ast::ComparisonOperator,
+        right: &Expression,
+        positive: bool,
+        reference: &FlowReference,
+    ) -> PythonType {
+        use ast::ComparisonOperator as Op;
+        let is_reference = |e: &Expression| e.as_name().is_some_and(|n| n.id == reference.name);
+        match op {
+            Op::Is | Op::IsNot | Op::Eq | Op::NotEq => {
+                let equal = positive == matches!(op, Op::Is | Op::Eq);
+                let (subject, other) = if is_reference(right) && !is_reference(left) {
+                    (right, left)
+                } else {
+                    (left, right)
+                };
+                let is_none = matches!(
+                    other,
+                    Expression::Constant(c) if matches!(c.value, ast::ConstantValue::None)
+                );
+                let literal = match self.get_literal_type_of_constant(other) {
+                    Some(literal) => literal,
+                    None if is_none => PythonType::None,
+                    None => match other {
+                        Expression::Attribute(_) => {
+                            match self.get_type(other, reference.symbol_table, reference.scope_id) {
+                                Ok(
+                                    literal @ PythonType::LiteralValue(KnownValue {
+                                        literal_value: LiteralValue::Enum(_),
+                                    }),
+                                ) => literal,
+                                _ => return t,
+                            }
+                        }
+                        _ => return t,
+                    },
+                };
+                if is_reference(subject) {
+                    if is_none {
+                        self.narrow_to_none(t, equal)
+                    } else {
+                        self.narrow_to_literal(t, &literal, equal)
+                    }
+                } else {
+                    // Discriminated unions are narrowed by a literal attribute
+                    match subject {
+                        Expression::Attribute(a) if is_reference(&a.value) && !is_none => self
+                            .narrow_for_literal_attribute(t, &a.attr, &literal, equal, reference),
+                        _ => t,
+                    }
+                }
+            }
+            Op::In | Op::NotIn if is_reference(left) => {
+                let contained = positive == matches!(op, Op::In);
+                let Some(element_types) = self.get_container_element_types(right, reference) else {
+                    return t;
+                };
+                self.narrow_for_containment(t, &element_types, contained)
+            }
+            _ => t,
+        }
+    }
+
+    fn narrow_for_call(
+        &self,
+        t: PythonType,
+        call: &ast::Call,
+        positive: bool,
+        reference: &FlowReference,
+    ) -> PythonType {
+        let Some(Expression::Name(argument)) = call.args.first() else {
+            return t;
+        };
+        if argument.id != reference.name {
+            return t;
+        }
+        match &call.func {
+            Expression::Name(f) if f.id == "isinstance" && call.args.len() == 2 => {
+                match self.get_isinstance_classes(&call.args[1], reference) {
+                    Some(classes) => self.narrow_for_isinstance(t, &classes, positive),
+                    None => t,
+                }
+            }
+            Expression::Name(f) if f.id == "callable" => {
+                self.narrow_for_callable(t, positive, reference)
+            }
+            _ => {
+                let Ok(PythonType::Callable(callable)) =
+                    self.get_type(&call.func, reference.symbol_table, reference.scope_id)
+                else {
+                    return t;
+                };
+                match self.get_type_guard(&callable.return_type) {
+                    // A TypeGuard function only narrows when it returns True
+                    Some(TypeGuard::TypeGuard(guarded)) if positive => to_instance(guarded),
+                    Some(TypeGuard::TypeIs(guarded)) => {
+                        self.narrow_for_type_is(t, &to_instance(guarded), positive)
+                    }
+                    _ => t,
+                }
+            }
+        }
+    }
+
+    /// Values that are always truthy or always falsy are removed from the other branch.
+    fn narrow_truthiness(&self, t: PythonType, positive: bool) -> PythonType {
+        self.filter_union_members(t, |member| match member {
+            PythonType::None => !positive,
+            PythonType::LiteralValue(value) => {
+                is_literal_truthy(&value.literal_value) != Some(!positive)
+            }
+            // Functions and modules do not define __bool__ or __len__
+            PythonType::Callable(_) | PythonType::Module(_) => positive,
+            _ => true,
+        })
+    }
+
+    fn narrow_to_none(&self, t: PythonType, is_none: bool) -> PythonType {
+        let mut narrowed = vec![];
+        for member in t.union_members() {
+            match &member {
+                PythonType::None => {
+                    if is_none {
+                        narrowed.push(member);
+                    }
+                }
+                PythonType::LiteralValue(value) if value.literal_value == LiteralValue::None => {
+                    if is_none {
+                        narrowed.push(member);
+                    }
+                }
+                // These types can hold None
+                PythonType::Any | PythonType::Unknown | PythonType::TypeVar(_) => {
+                    narrowed.push(if is_none { PythonType::None } else { member });
+                }
+                _ if self.is_object_class(&member) => {
+                    narrowed.push(if is_none { PythonType::None } else { member });
+                }
+                _ => {
+                    if !is_none {
+                        narrowed.push(member);
+                    }
+                }
+            }
+        }
+        self.union_of_narrowed(t, narrowed)
+    }
+
+    fn narrow_to_literal(&self, t: PythonType, literal: &PythonType, equal: bool) -> PythonType {
+        let t = match literal {
+            PythonType::LiteralValue(KnownValue {
+                literal_value: LiteralValue::Enum(member),
+            }) => self.expand_enum_members(t, &member.class),
+            _ => t,
+        };
+        self.filter_union_members(t, |member| match member {
+            PythonType::LiteralValue(_) => (member == literal) == equal,
+            _ => true,
+        })
+    }
+
+    /// Members of a union whose attribute is a literal that cannot be equal to the
+    /// compared literal are removed.
+    fn narrow_for_literal_attribute(
+        &self,
+        t: PythonType,
+        attribute: &str,
+        literal: &PythonType,
+        equal: bool,
+        reference: &FlowReference,
+    ) -> PythonType {
+        self.filter_union_members(t, |member| {
+            let Some(class) = self.get_class_of_operand(member) else {
+                return true;
+            };
+            let Some(attribute_type) =
+                self.lookup_on_class(reference.symbol_table, &class, attribute)
+            else {
+                return true;
+            };
+            let values = attribute_type.union_members();
+            if !values.iter().all(|v| v.is_literal_value()) {
+                return true;
+            }
+            if equal {
+                values.contains(literal)
+            } else {
+                values.iter().any(|v| v != literal)
+            }
+        })
+    }
+
+    fn narrow_for_containment(
+        &self,
+        t: PythonType,
+        element_types: &[PythonType],
+        contained: bool,
+    ) -> PythonType {
+        let elements = PythonType::union(element_types.to_vec());
+        let mut narrowed = vec![];
+        for member in t.union_members() {
+            if !contained {
+                // Only a literal that is the only value of its type can be excluded
+                if !(member.is_literal_value() && element_types.contains(&member)) {
+                    narrowed.push(member);
+                }
+            } else if self.is_assignable(&member, &elements) {
+                narrowed.push(member);
+            } else {
+                narrowed.extend(
+                    element_types
+                        .iter()
+                        .filter(|e| self.is_assignable(e, &member))
+                        .cloned(),
+                );
+            }
+        }
+        self.union_of_narrowed(t, narrowed)
+    }
+
+    /// The types of the values of a container that a reference is compared against
+    /// with `in`. Only tuple, list and set displays and homogeneous containers are known.
+    fn get_container_element_types(
+        &self,
+        container: &Expression,
+        reference: &FlowReference,
+    ) -> Option<Vec<PythonType>> {
+        let elements = match container {
+            Expression::Tuple(t) => &t.elements,
+            Expression::List(l) => &l.elements,
+            Expression::Set(s) => &s.elements,
+            _ => {
+                let container_type = self
+                    .get_type(container, reference.symbol_table, reference.scope_id)
+                    .ok()?;
+                let type_arguments = self.get_type_arguments(&container_type);
+                return match type_arguments {
+                    [element] => Some(vec![element.clone()]),
+                    _ => None,
+                };
+            }
+        };
+        elements
+            .iter()
+            .map(|e| match self.get_literal_type_of_constant(e) {
+                Some(literal) => Some(literal),
+                None => self
+                    .get_type(e, reference.symbol_table, reference.scope_id)
+                    .ok(),
+            })
+            .collect()
+    }
+
+    fn get_isinstance_classes(
+        &self,
+        expr: &Expression,
+        reference: &FlowReference,
+    ) -> Option<Vec<ClassType>> {
+        match expr {
+            Expression::Tuple(t) => {
+                let mut classes = vec![];
+                for element in &t.elements {
+                    classes.extend(self.get_isinstance_classes(element, reference)?);
+                }
+                Some(classes)
+            }
+            _ => match self.get_type(expr, reference.symbol_table, reference.scope_id) {
+                Ok(PythonType::Class(c)) if !c.details.special => Some(vec![c]),
+                _ => None,
+            },
+        }
+    }
+
+    /// https://docs.python.org/3/library/functions.html#isinstance
+    fn narrow_for_isinstance(
+        &self,
+        t: PythonType,
+        classes: &[ClassType],
+        positive: bool,
+    ) -> PythonType {
+        let instances = || {
+            classes
+                .iter()
+                .map(|c| to_instance(PythonType::Class(c.clone())))
+        };
+        let mut narrowed = vec![];
+        for member in t.union_members() {
+            if matches!(member, PythonType::Any | PythonType::Unknown) || self.is_any_class(&member)
+            {
+                if positive {
+                    narrowed.extend(instances());
+                } else {
+                    narrowed.push(member);
+                }
+                continue;
+            }
+            if member == PythonType::None {
+                let matches = classes
+                    .iter()
+                    .any(|c| c.details.qual_name == "builtins.object");
+                if matches == positive {
+                    narrowed.push(member);
+                }
+                continue;
+            }
+            let Some(member_class) = self.get_class_of_operand(&member) else {
+                narrowed.push(member);
+                continue;
+            };
+            if classes
+                .iter()
+                .any(|c| self.is_instance_of(&member, &member_class, c))
+            {
+                if positive {
+                    narrowed.push(member);
+                }
+            } else if positive {
+                // The value can be an instance of a subclass of the member
+                narrowed.extend(
+                    classes
+                        .iter()
+                        .map(|c| to_instance(PythonType::Class(c.clone())))
+                        .filter(|c| {
+                            self.get_class_of_operand(c)
+                                .is_some_and(|class| self.is_instance_of(c, &class, &member_class))
+                        }),
+                );
+            } else {
+                narrowed.push(member);
+            }
+        }
+        self.union_of_narrowed(t, narrowed)
+    }
+
+    /// https://docs.python.org/3/library/functions.html#callable
+    fn narrow_for_callable(
+        &self,
+        t: PythonType,
+        positive: bool,
+        reference: &FlowReference,
+    ) -> PythonType {
+        self.filter_union_members(t, |member| match member {
+            PythonType::Callable(_) => positive,
+            PythonType::Any | PythonType::Unknown | PythonType::TypeVar(_) => true,
+            PythonType::None | PythonType::LiteralValue(_) | PythonType::Module(_) => !positive,
+            _ => match self.get_class_of_operand(member) {
+                Some(class) => {
+                    let is_callable = self
+                        .lookup_on_class(reference.symbol_table, &class, "__call__")
+                        .is_some();
+                    is_callable == positive || self.is_object_class(member)
+                }
+                None => true,
+            },
+        })
+    }
+
+    /// https://typing.readthedocs.io/en/latest/spec/narrowing.html#typeis
+    fn narrow_for_type_is(
+        &self,
+        t: PythonType,
+        guarded: &PythonType,
+        positive: bool,
+    ) -> PythonType {
+        let mut narrowed = vec![];
+        for member in t.union_members() {
+            let is_guarded = !matches!(member, PythonType::Any | PythonType::Unknown)
+                && self.is_assignable(&member, guarded);
+            if positive {
+                if is_guarded {
+                    narrowed.push(member);
+                } else {
+                    narrowed.extend(
+                        guarded
+                            .union_members()
+                            .into_iter()
+                            .filter(|g| self.is_assignable(g, &member)),
+                    );
+                }
+            } else if !is_guarded {
+                narrowed.push(member);
+            }
+        }
+        self.union_of_narrowed(t, narrowed)
+    }
+
+    /// Returns the narrowed type of functions that return `TypeGuard[T]` or `TypeIs[T]`.
+    fn get_type_guard(&self, return_type: &PythonType) -> Option<TypeGuard> {
+        let PythonType::Class(c) = return_type else {
+            return None;
+        };
+        if !c.details.special {
+            return None;
+        }
+        let guarded = c.specialized.first()?.clone();
+        match c.details.name.as_str() {
+            "TypeGuard" => Some(TypeGuard::TypeGuard(guarded)),
+            "TypeIs" => Some(TypeGuard::TypeIs(guarded)),
+            _ => None,
+        }
+    }
+
+    /// Keeps the members of a union for which the predicate holds. The type is returned
+    /// unchanged when every member is kept.
+    fn filter_union_members(
+        &self,
+        t: PythonType,
+        predicate: impl Fn(&PythonType) -> bool,
+    ) -> PythonType {
+        let narrowed = t
+            .union_members()
+            .into_iter()
+            .filter(|m| predicate(m))
+            .collect();
+        self.union_of_narrowed(t, narrowed)
+    }
+
+    fn union_of_narrowed(&self, t: PythonType, narrowed: Vec<PythonType>) -> PythonType {
+        if narrowed == t.union_members() {
+            t
+        } else {
+            PythonType::union(narrowed)
+        }
+    }
+
+    fn is_object_class(&self, t: &PythonType) -> bool {
+        self.get_class_of_operand(t)
+            .is_some_and(|c| c.details.qual_name == "builtins.object")
+    }
+
+    /// typeshed declares `Any` as a class
+    fn is_any_class(&self, t: &PythonType) -> bool {
+        self.get_class_of_operand(t)
+            .is_some_and(|c| c.details.qual_name == "typing.Any")
+    }
+
+    fn get_dec_symbol_table(&self, decl_path: &DeclarationPath) -> Arc<SymbolTable> {
+        let table_id = decl_path.symbol_table_id;
+        return self.build_manager.get_symbol_table_by_id(&table_id);
+    }
+
+    fn get_symbol_table(&self, id: &Id) -> Arc<SymbolTable> {
+        return self.build_manager.get_symbol_table_by_id(id);
+    }
+}
+
+/// The names of the symbols of a scope in a stable order.
+fn sorted_symbol_names(scope: &symbol_table::SymbolTableScope) -> Vec<String> {
+    let mut names: Vec<String> = scope.symbols().map(|symbol| symbol.name.clone()).collect();
+    names.sort();
+    names
+}
+
+/// Returns the method and the reflected method that implement a binary operator.
+/// https://docs.python.org/3/reference/datamodel.html#emulating-numeric-types
+fn binary_operator_methods(op: &ast::BinaryOperator) -> (&'static str, &'static str) {
+    match op {
+        ast::BinaryOperator::Add => ("__add__", "__radd__"),
+        ast::BinaryOperator::Sub => ("__sub__", "__rsub__"),
+        ast::BinaryOperator::Mult => ("__mul__", "__rmul__"),
+        ast::BinaryOperator::MatMult => ("__matmul__", "__rmatmul__"),
+        ast::BinaryOperator::Div => ("__truediv__", "__rtruediv__"),
+        ast::BinaryOperator::Mod => ("__mod__", "__rmod__"),
+        ast::BinaryOperator::Pow => ("__pow__", "__rpow__"),
+        ast::BinaryOperator::LShift => ("__lshift__", "__rlshift__"),
+        ast::BinaryOperator::RShift => ("__rshift__", "__rrshift__"),
+        ast::BinaryOperator::BitOr => ("__or__", "__ror__"),
+        ast::BinaryOperator::BitXor => ("__xor__", "__rxor__"),
+        ast::BinaryOperator::BitAnd => ("__and__", "__rand__"),
+        ast::BinaryOperator::FloorDiv => ("__floordiv__", "__rfloordiv__"),
+    }
+}
+
+/// Each block is visited at most this many times while evaluating the type of a name
+/// along the flow graph. Loops that keep changing the type stop at the last visit.
+const MAX_FLOW_VISITS: usize = 8;
+
+/// A reference to a name whose type is evaluated along the flow graph of its scope.
+struct FlowReference<'r> {
+    name: &'r str,
+    position: u32,
+    symbol: &'r SymbolTableNode,
+    symbol_table: &'r SymbolTable,
+    scope_id: u32,
+    /// The type of the name when the scope starts, None when the name is unbound.
+    entry_type: Option<PythonType>,
+}
+
+enum TypeGuard {
+    TypeGuard(PythonType),
+    TypeIs(PythonType),
+}
+
+/// Annotations evaluate to classes but values of the annotated type are instances.
+/// The signature of a callable that takes the parameters of a param spec. `...` is the
+/// param spec that takes any arguments.
+fn param_spec_signature(param_spec: PythonType) -> Vec<CallableArgs> {
+    vec![
+        CallableArgs::Args(CallableParameter::new(
+            "args".to_string(),
+            param_spec.clone(),
+            false,
+        )),
+        CallableArgs::KwArgs(CallableParameter::new(
+            "kwargs".to_string(),
+            param_spec,
+            false,
+        )),
+    ]
+}
+
+/// The name of the param spec that the parameters of a callable are, e.g. `P` for
+/// `Callable[P, int]`.
+fn param_spec_name(callable: &CallableType) -> Option<&str> {
+    match callable.signature.as_slice() {
+        [CallableArgs::Args(args), CallableArgs::KwArgs(kwargs)] => {
+            match (&args.python_type, &kwargs.python_type) {
+                (PythonType::TypeVar(a), PythonType::TypeVar(k)) if a.name == k.name => {
+                    Some(&a.name)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The name a decorator is referred to by, e.g. `cache` for `functools.cache` and
+/// `setter` for `value.setter`.
+fn decorator_name(decorator: &Expression) -> Option<&str> {
+    match decorator {
+        Expression::Name(n) => Some(&n.id),
+        Expression::Attribute(a) => Some(&a.attr),
+        _ => None,
+    }
+}
+
+fn method_kind_of_decorator(name: &str) -> Option<MethodKind> {
+    match name {
+        "staticmethod" => Some(MethodKind::Static),
+        "classmethod" => Some(MethodKind::Class),
+        "property" | "cached_property" | "getter" | "setter" | "deleter" => {
+            Some(MethodKind::Property)
+        }
+        _ => None,
+    }
+}
+
+/// How a function that is declared in a class body is bound, decided by its decorators.
+fn method_kind(decorators: &[Expression]) -> MethodKind {
+    decorators
+        .iter()
+        .filter_map(decorator_name)
+        .find_map(method_kind_of_decorator)
+        .unwrap_or(MethodKind::Instance)
+}
+
+/// Whether an attribute is declared on the class or assigned on its instances
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttributeKind {
+    Class,
+    Instance,
+}
+
+/// The class and the type arguments of an object that can be a descriptor. Objects that
+/// are created by calling a class have the class type too.
+fn descriptor_class(member: &PythonType) -> Option<(&ClassType, &[PythonType])> {
+    match member {
+        PythonType::Instance(i) => Some((&i.class_type, &i.specialized_type_parameters)),
+        PythonType::Class(c) => Some((c, &c.specialized)),
+        _ => None,
+    }
+}
+
+fn same_class(a: &ClassType, b: &ClassType) -> bool {
+    a.details.qual_name == b.details.qual_name
+}
+
+/// Merges the method resolution orders of the bases of a class. The next class is the
+/// first head of a sequence that is not in the tail of any sequence. Returns None when
+/// there is no such class, which means the hierarchy is inconsistent.
+fn c3_merge(mut sequences: Vec<Vec<ClassType>>) -> Option<Vec<ClassType>> {
+    let mut merged = vec![];
+    loop {
+        sequences.retain(|sequence| !sequence.is_empty());
+        if sequences.is_empty() {
+            return Some(merged);
+        }
+        let next = sequences
+            .iter()
+            .map(|sequence| &sequence[0])
+            .find(|candidate| {
+                !sequences
+                    .iter()
+                    .any(|sequence| sequence[1..].iter().any(|c| same_class(c, candidate)))
+            })?
+            .clone();
+        for sequence in sequences.iter_mut() {
+            if same_class(&sequence[0], &next) {
+                sequence.remove(0);
+            }
+        }
+        merged.push(next);
+    }
+}
+
+fn to_instance(t: PythonType) -> PythonType {
+    match t {
+        PythonType::Class(c) => {
+            let specialized = c.specialized.clone();
+            PythonType::Instance(InstanceType::new(c, specialized))
+        }
+        t => t,
+    }
+}
+
+fn enum_literal(class: &ClassType, name: &str) -> PythonType {
+    PythonType::LiteralValue(KnownValue {
+        literal_value: LiteralValue::Enum(EnumMember {
+            class: Box::new(class.clone()),
+            name: name.to_string(),
+        }),
+    })
+}
+
+/// The truthiness of a literal, None when it depends on the value of an enum member.
+fn is_literal_truthy(value: &LiteralValue) -> Option<bool> {
+    Some(match value {
+        LiteralValue::Bool(b) => *b,
+        LiteralValue::Int(i) => i.bits() != 0,
+        LiteralValue::Str(s) => !s.is_empty(),
+        LiteralValue::Bytes(b) => !b.is_empty(),
+        LiteralValue::None => false,
+        LiteralValue::Enum(_) => return None,
+    })
+}
+
+/// The names that a condition can narrow.
+fn narrowed_names(test: &Expression) -> Vec<&str> {
+    match test {
+        Expression::Name(n) => vec![n.id.as_str()],
+        Expression::NamedExpr(n) => narrowed_names(&n.target),
+        Expression::UnaryOp(u) => narrowed_names(&u.operand),
+        Expression::BoolOp(b) => b.values.iter().flat_map(narrowed_names).collect(),
+        Expression::Compare(c) => std::iter::once(&c.left)
+            .chain(c.comparators.iter())
+            .flat_map(|e| match e {
+                Expression::Attribute(a) => narrowed_names(&a.value),
+                e => narrowed_names(e),
+            })
+            .collect(),
+        Expression::Call(c) => c.args.first().map(narrowed_names).unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+fn contains_position(expr: &Expression, position: u32) -> bool {
+    let node = expr.get_node();
+    node.start <= position && position < node.end
+}
+
+fn comprehension_expressions(generators: &[ast::Comprehension]) -> Vec<&Expression> {
+    generators
+        .iter()
+        .flat_map(|g| std::iter::once(&g.iter).chain(g.ifs.iter()))
+        .collect()
+}
+
+/// The expressions that are direct children of an expression.
+fn child_expressions(expr: &Expression) -> Vec<&Expression> {
+    match expr {
+        Expression::Constant(_) | Expression::Name(_) => vec![],
+        Expression::List(l) => l.elements.iter().collect(),
+        Expression::Tuple(t) => t.elements.iter().collect(),
+        Expression::Set(s) => s.elements.iter().collect(),
+        Expression::Dict(d) => d.keys.iter().chain(d.values.iter()).collect(),
+        Expression::BoolOp(b) => b.values.iter().collect(),
+        Expression::UnaryOp(u) => vec![&u.operand],
+        Expression::BinOp(b) => vec![&b.left, &b.right],
+        Expression::NamedExpr(n) => vec![&n.value],
+        Expression::Yield(y) => y.value.iter().collect(),
+        Expression::YieldFrom(y) => vec![&y.value],
+        Expression::Starred(s) => vec![&s.value],
+        Expression::Generator(g) => {
+            let mut children = comprehension_expressions(&g.generators);
+            children.push(&g.element);
+            children
+        }
+        Expression::ListComp(l) => {
+            let mut children = comprehension_expressions(&l.generators);
+            children.push(&l.element);
+            children
+        }
+        Expression::SetComp(s) => {
+            let mut children = comprehension_expressions(&s.generators);
+            children.push(&s.element);
+            children
+        }
+        Expression::DictComp(d) => {
+            let mut children = comprehension_expressions(&d.generators);
+            children.push(&d.key);
+            children.push(&d.value);
+            children
+        }
+        Expression::Attribute(a) => vec![&a.value],
+        Expression::Subscript(s) => vec![&s.value, &s.slice],
+        Expression::Slice(s) => [&s.lower, &s.upper, &s.step]
+            .into_iter()
+            .flatten()
+            .collect(),
+        Expression::Call(c) => std::iter::once(&c.func)
+            .chain(c.args.iter())
+            .chain(c.keywords.iter().map(|k| &k.value))
+            .chain(c.starargs.iter())
+            .chain(c.kwargs.iter())
+            .collect(),
+        Expression::Await(a) => vec![&a.value],
+        Expression::Compare(c) => std::iter::once(&c.left)
+            .chain(c.comparators.iter())
+            .collect(),
+        Expression::Lambda(l) => vec![&l.body],
+        Expression::IfExp(i) => vec![&i.test, &i.body, &i.orelse],
+        Expression::JoinedStr(j) => j.values.iter().collect(),
+        Expression::FormattedValue(f) => std::iter::once(&f.value)
+            .chain(f.format_spec.iter())
+            .collect(),
+    }
+}
+
+/// Pairs the type parameters of a generic class with its type arguments.
+fn type_var_solution(type_parameters: &[PythonType], arguments: &[PythonType]) -> TypeVarSolution {
+    type_parameters
+        .iter()
+        .zip(arguments.iter())
+        .filter_map(|(parameter, argument)| {
+            Some((parameter.as_type_var()?.name.clone(), argument.clone()))
+        })
+        .collect()
+}
+
+/// The type arguments of a class for its type parameters. Tuples have a type argument
+/// for each element but one type parameter for the type of all their elements.
+fn class_type_arguments(class: &ClassType, arguments: &[PythonType]) -> Vec<PythonType> {
+    if class.details.qual_name == "builtins.tuple" && !arguments.is_empty() {
+        vec![tuple_element_type(arguments)]
+    } else {
+        arguments.to_vec()
+    }
+}
+
+/// The type of any element of a tuple with the type arguments.
+fn tuple_element_type(arguments: &[PythonType]) -> PythonType {
+    PythonType::union(
+        arguments
+            .iter()
+            .map(|argument| match argument {
+                PythonType::Unbounded(element) => (**element).clone(),
+                argument => argument.clone(),
+            })
+            .collect(),
+    )
+}
+
+/// Joins the elements of a tuple from its first to its last unbounded element into one
+/// unbounded element, because a tuple can have only one of them.
+fn join_unbounded_elements(elements: Vec<PythonType>) -> Vec<PythonType> {
+    let is_unbounded = |element: &PythonType| element.is_unbounded();
+    let (Some(first), Some(last)) = (
+        elements.iter().position(is_unbounded),
+        elements.iter().rposition(is_unbounded),
+    ) else {
+        return elements;
+    };
+    if first == last {
+        return elements;
+    }
+    let mut joined = elements[..first].to_vec();
+    joined.push(PythonType::Unbounded(Box::new(tuple_element_type(
+        &elements[first..=last],
+    ))));
+    joined.extend_from_slice(&elements[last + 1..]);
+    joined
+}
+
+fn collect_type_vars<'t>(python_type: &'t PythonType, type_vars: &mut Vec<&'t TypeVar>) {
+    match python_type {
+        PythonType::TypeVar(type_var) => type_vars.push(type_var),
+        PythonType::Class(c) => c
+            .specialized
+            .iter()
+            .for_each(|t| collect_type_vars(t, type_vars)),
+        PythonType::Instance(i) => i
+            .specialized_type_parameters
+            .iter()
+            .for_each(|t| collect_type_vars(t, type_vars)),
+        PythonType::MultiValue(members) => {
+            members.iter().for_each(|t| collect_type_vars(t, type_vars))
+        }
+        PythonType::Optional(inner) | PythonType::Unbounded(inner) => {
+            collect_type_vars(inner, type_vars)
+        }
+        PythonType::Callable(c) => {
+            c.signature
+                .iter()
+                .for_each(|arg| collect_type_vars(arg.get_type(), type_vars));
+            collect_type_vars(&c.return_type, type_vars);
+        }
+        PythonType::Coroutine(c) => collect_type_vars(&c.return_type, type_vars),
+        _ => {}
+    }
+}
+
+/// Pairs the arguments of a call with the parameters that they are passed to. Arguments
+/// that cannot be bound are left out, the checker reports them.
+fn bind_arguments<'c>(
+    signature: &'c [CallableArgs],
+    call: &'c ast::Call,
+) -> Vec<(&'c CallableArgs, &'c Expression)> {
+    let mut bound = vec![];
+    let mut positional = signature.iter().filter(|p| {
+        matches!(
+            p,
+            CallableArgs::PositionalOnly(_) | CallableArgs::Positional(_)
+        )
+    });
+    let var_args = signature
+        .iter()
+        .find(|p| matches!(p, CallableArgs::Args(_)));
+    for arg in call.args.iter() {
+        if matches!(arg, Expression::Starred(_)) {
+            break;
+        }
+        match positional.next().or(var_args) {
+            Some(parameter) => bound.push((parameter, arg)),
+            None => break,
+        }
+    }
+    for keyword in call.keywords.iter() {
+        let Some(name) = &keyword.arg else {
+            continue;
+        };
+        let parameter = signature
+            .iter()
+            .find(|p| {
+                matches!(p, CallableArgs::Positional(_) | CallableArgs::Keyword(_))
+                    && p.parameter().name == *name
+            })
+            .or_else(|| {
+                signature
+                    .iter()
+                    .find(|p| matches!(p, CallableArgs::KwArgs(_)))
+            });
+        if let Some(parameter) = parameter {
+            bound.push((parameter, &keyword.value));
+        }
+    }
+    bound
+}
+
+/// Binds the arguments of a call to the parameters of a signature like the interpreter
+/// does. Returns None when an argument has no parameter to bind to or a parameter without
+/// a default gets no argument.
+fn match_arguments<'c>(
+    signature: &'c [CallableArgs],
+    call: &'c ast::Call,
+) -> Option<Vec<(&'c CallableArgs, &'c Expression)>> {
+    let mut bound = vec![];
+    let mut assigned = vec![false; signature.len()];
+    let mut positional = signature
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| {
+            matches!(
+                p,
+                CallableArgs::PositionalOnly(_) | CallableArgs::Positional(_)
+            )
+        })
+        .map(|(index, _)| index);
+    let var_args = signature
+        .iter()
+        .position(|p| matches!(p, CallableArgs::Args(_)));
+    let var_kwargs = signature
+        .iter()
+        .position(|p| matches!(p, CallableArgs::KwArgs(_)));
+    // Unpacked arguments can fill any number of parameters
+    let mut unpacked_positional = false;
+    let mut unpacked_keywords = false;
+    for arg in call.args.iter() {
+        if matches!(arg, Expression::Starred(_)) {
+            unpacked_positional = true;
+            break;
+        }
+        let index = positional.next().or(var_args)?;
+        assigned[index] = true;
+        bound.push((&signature[index], arg));
+    }
+    for keyword in call.keywords.iter() {
+        let Some(name) = &keyword.arg else {
+            unpacked_keywords = true;
+            continue;
+        };
+        let index = signature
+            .iter()
+            .position(|p| {
+                matches!(p, CallableArgs::Positional(_) | CallableArgs::Keyword(_))
+                    && p.parameter().name == *name
+            })
+            .or(var_kwargs)?;
+        if assigned[index] && Some(index) != var_kwargs {
+            return None;
+        }
+        assigned[index] = true;
+        bound.push((&signature[index], &keyword.value));
+    }
+    let missing = signature.iter().enumerate().any(|(index, p)| {
+        let filled_by_unpacking = match p {
+            CallableArgs::PositionalOnly(_) => unpacked_positional,
+            CallableArgs::Positional(_) => unpacked_positional || unpacked_keywords,
+            CallableArgs::Keyword(_) => unpacked_keywords,
+            CallableArgs::Args(_) | CallableArgs::KwArgs(_) => true,
+        };
+        !assigned[index] && !p.parameter().has_default && !filled_by_unpacking
+    });
+    (!missing).then_some(bound)
+}
+
+/// Items that a typed dict class declares again replace the items of its bases.
+fn add_typed_dict_field(fields: &mut Vec<TypedDictField>, field: TypedDictField) {
+    match fields.iter_mut().find(|f| f.name == field.name) {
+        Some(existing) => *existing = field,
+        None => fields.push(field),
+    }
+}
+
+/// Removes the `Required`, `NotRequired` and `ReadOnly` qualifiers from the annotation of
+/// a typed dict item. Returns the annotation of the value, whether the item is marked
+/// required or not required and whether it is read only.
+fn typed_dict_item_qualifiers(annotation: &Expression) -> (&Expression, Option<bool>, bool) {
+    let mut annotation = annotation;
+    let mut required = None;
+    let mut read_only = false;
+    while let Expression::Subscript(s) = annotation {
+        if is_special_form_name(&s.value, "Required") {
+            required = Some(true);
+        } else if is_special_form_name(&s.value, "NotRequired") {
+            required = Some(false);
+        } else if is_special_form_name(&s.value, "ReadOnly") {
+            read_only = true;
+        } else {
+            break;
+        }
+        annotation = &s.slice;
+    }
+    (annotation, required, read_only)
+}
+
+/// Whether the expression refers to a special form by its name, e.g. `typing.Unpack`.
+fn is_special_form_name(expr: &Expression, name: &str) -> bool {
+    match expr {
+        Expression::Name(n) => n.id == name,
+        Expression::Attribute(a) => a.attr == name,
+        _ => false,
+    }
+}
+
+fn string_constant(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::Constant(c) => match &c.value {
+            ast::ConstantValue::Str(s) => Some(s.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn int_constant(expr: &Expression) -> Option<i64> {
+    match expr {
+        Expression::Constant(c) => match &c.value {
+            ast::ConstantValue::Int(i) => i64::try_from(i).ok(),
+            _ => None,
+        },
+        Expression::UnaryOp(u) if matches!(u.op, ast::UnaryOperator::USub) => {
+            int_constant(&u.operand).map(|i| -i)
+        }
+        _ => None,
+    }
+}
+
+/// The types of the targets that a tuple with the elements is unpacked to. The star
+/// target gets the type of the elements between the targets before and after it. None
+/// when the number of targets does not match the number of elements.
+fn unpack_tuple_elements(
+    elements: &[PythonType],
+    count: usize,
+    star: Option<usize>,
+) -> Option<Vec<PythonType>> {
+    let unbounded = elements.iter().position(|e| e.is_unbounded());
+    let Some(star) = star else {
+        let Some(unbounded) = unbounded else {
+            return (elements.len() == count).then(|| elements.to_vec());
+        };
+        let element = elements[unbounded].as_unbounded()?;
+        let repeated = count.checked_sub(elements.len() - 1)?;
+        return Some(
+            elements[..unbounded]
+                .iter()
+                .cloned()
+                .chain(std::iter::repeat((**element).clone()).take(repeated))
+                .chain(elements[unbounded + 1..].iter().cloned())
+                .collect(),
+        );
+    };
+    let after = count - star - 1;
+    match unbounded {
+        None if elements.len() < count - 1 => None,
+        // The targets can take elements from any part of the tuple
+        Some(u) if u < star || elements.len() - u - 1 < after => {
+            Some(vec![tuple_element_type(elements); count])
+        }
+        _ => {
+            let end = elements.len() - after;
+            let mut types = elements[..star].to_vec();
+            types.push(tuple_element_type(&elements[star..end]));
+            types.extend_from_slice(&elements[end..]);
+            Some(types)
+        }
+    }
+}
+
+/// The indices of the elements of a sequence of the length that a slice selects.
+/// None when the step is zero because the slice raises an error then.
+fn slice_indices(
+    len: usize,
+    lower: Option<i64>,
+    upper: Option<i64>,
+    step: i64,
+) -> Option<Vec<usize>> {
+    let len = len as i64;
+    let clamp = |bound: i64, min: i64, max: i64| {
+        let bound = if bound < 0 { bound + len } else { bound };
+        bound.clamp(min, max)
+    };
+    let (mut index, end) = match step {
+        0 => return None,
+        step if step > 0 => (
+            lower.map_or(0, |l| clamp(l, 0, len)),
+            upper.map_or(len, |u| clamp(u, 0, len)),
+        ),
+        _ => (
+            lower.map_or(len - 1, |l| clamp(l, -1, len - 1)),
+            upper.map_or(-1, |u| clamp(u, -1, len - 1)),
+        ),
+    };
+    let mut indices = vec![];
+    while (step > 0 && index < end) || (step < 0 && index > end) {
+        indices.push(index as usize);
+        index += step;
+    }
+    Some(indices)
+}
+
+fn is_false(expr: &Expression) -> bool {
+    matches!(expr, Expression::Constant(c) if matches!(c.value, ast::ConstantValue::Bool(false)))
 }
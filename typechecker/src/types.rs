@@ -1,5 +1,6 @@
 use crate::symbol_table::{self, Id};
 use is_macro::Is;
+use num_bigint::BigInt;
 use std::fmt::{self, Display};
 
 #[derive(Debug, Clone, PartialEq, Eq, Is)]
@@ -13,6 +14,10 @@ pub enum PythonType {
     /// the name x will have an Any as its value within the function
     /// because there is no information to determine what value it can contain
     Any,
+    /// The type without values, e.g. the type of a variable in a branch that the
+    /// narrowed type of the variable cannot reach.
+    /// https://typing.readthedocs.io/en/latest/spec/special-types.html#never
+    Never,
     /// representing a value with concrete type.
     /// For example, if we define some variable foo to have type Literal[3], we
     /// are declaring that foo must be exactly equal to 3 and no other value.
@@ -28,6 +33,11 @@ pub enum PythonType {
     Instance(InstanceType),
     Optional(Box<PythonType>),
     TypeVar(TypeVar),
+    /// Any number of elements of a type in the type arguments of a tuple, e.g. `int` in
+    /// `tuple[int, ...]` and in `tuple[str, *tuple[int, ...]]`. The other type arguments
+    /// of a tuple are the types of its elements in their positions.
+    /// https://typing.readthedocs.io/en/latest/spec/tuples.html
+    Unbounded(Box<PythonType>),
 }
 
 impl PythonType {
@@ -36,6 +46,7 @@ impl PythonType {
             (PythonType::None, PythonType::None) => true,
             (PythonType::Unknown, PythonType::Unknown) => true,
             (PythonType::Any, PythonType::Any) => true,
+            (PythonType::Never, PythonType::Never) => true,
             (PythonType::LiteralValue(v1), PythonType::LiteralValue(v2)) => v1 == v2,
             (PythonType::MultiValue(m1), PythonType::MultiValue(m2)) => {
                 if m1.len() != m2.len() {
@@ -55,6 +66,58 @@ impl PythonType {
             _ => false,
         }
     }
+
+    /// Creates the union of types. Nested unions are flattened, duplicate members are
+    /// removed and Never is dropped because it has no values. The class and the instance
+    /// forms of the same type are the same member.
+    pub fn union(types: Vec<PythonType>) -> PythonType {
+        // Keeps the way the type was written, e.g. `Optional[int]`
+        if types
+            .first()
+            .is_some_and(|first| types.iter().all(|t| t == first))
+        {
+            return types[0].clone();
+        }
+        let mut members: Vec<PythonType> = vec![];
+        for t in types {
+            for member in t.union_members() {
+                if member != PythonType::Never && !members.iter().any(|m| m.is_same_member(&member))
+                {
+                    members.push(member);
+                }
+            }
+        }
+        match members.len() {
+            0 => PythonType::Never,
+            1 => members.remove(0),
+            _ => PythonType::MultiValue(members),
+        }
+    }
+
+    fn is_same_member(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PythonType::Instance(i), PythonType::Class(c))
+            | (PythonType::Class(c), PythonType::Instance(i)) => {
+                i.class_type == *c && i.specialized_type_parameters == c.specialized
+            }
+            _ => self == other,
+        }
+    }
+
+    /// The members of a union type. Other types are unions of one member.
+    pub fn union_members(&self) -> Vec<PythonType> {
+        match self {
+            PythonType::MultiValue(members) => {
+                members.iter().flat_map(|m| m.union_members()).collect()
+            }
+            PythonType::Optional(inner) => {
+                let mut members = inner.union_members();
+                members.push(PythonType::None);
+                members
+            }
+            t => vec![t.clone()],
+        }
+    }
 }
 
 #[allow(unused)]
@@ -89,6 +152,28 @@ pub struct CallableType {
     pub signature: Vec<CallableArgs>,
     pub return_type: PythonType,
     pub is_async: bool,
+    /// The signatures of an overloaded function in the order of their declaration. The
+    /// callable itself is the first overload, calls are resolved against all of them.
+    pub overloads: Vec<CallableType>,
+    pub method_kind: MethodKind,
+}
+
+/// How a function is bound when it is accessed on a class or an instance. The decorators
+/// of a function that is defined in a class body decide its kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MethodKind {
+    /// Functions that are not defined in a class body are not bound
+    #[default]
+    Function,
+    /// Instance methods are bound to the object they are accessed on
+    Instance,
+    /// Class methods are bound to the class, `classmethod`
+    Class,
+    /// Static methods are not bound, `staticmethod`
+    Static,
+    /// Properties are accessed like attributes that have the type the getter returns,
+    /// `property` and `functools.cached_property`
+    Property,
 }
 
 impl Display for CallableType {
@@ -109,36 +194,70 @@ impl Display for CallableType {
 
 #[derive(Debug, Clone)]
 pub enum CallableArgs {
-    PositionalOnly(PythonType),
-    Positional(PythonType),
-    Keyword(PythonType),
-    Args(PythonType),
-    KwArgs(PythonType),
-    WithDefault(PythonType),
+    PositionalOnly(CallableParameter),
+    Positional(CallableParameter),
+    Keyword(CallableParameter),
+    Args(CallableParameter),
+    KwArgs(CallableParameter),
+}
+
+#[derive(Debug, Clone)]
+pub struct CallableParameter {
+    pub name: String,
+    pub python_type: PythonType,
+    pub has_default: bool,
+}
+
+impl CallableParameter {
+    pub fn new(name: String, python_type: PythonType, has_default: bool) -> Self {
+        Self {
+            name,
+            python_type,
+            has_default,
+        }
+    }
 }
 
 impl fmt::Display for CallableArgs {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            CallableArgs::PositionalOnly(ty) => write!(f, "pos_only: {}", ty),
-            CallableArgs::Positional(ty) => write!(f, "pos: {}", ty),
-            CallableArgs::Keyword(ty) => write!(f, "kw_only: {}", ty),
-            CallableArgs::Args(ty) => write!(f, "*args: {}", ty),
-            CallableArgs::KwArgs(ty) => write!(f, "**kwargs: {}", ty),
-            CallableArgs::WithDefault(ty) => write!(f, "kw_default: {} = ...", ty),
+            CallableArgs::PositionalOnly(p) => write!(f, "pos_only: {}", p.python_type),
+            CallableArgs::Positional(p) => write!(f, "pos: {}", p.python_type),
+            CallableArgs::Keyword(p) => write!(f, "kw_only: {}", p.python_type),
+            CallableArgs::Args(p) => write!(f, "*args: {}", p.python_type),
+            CallableArgs::KwArgs(p) => write!(f, "**kwargs: {}", p.python_type),
         }
     }
 }
 
 impl CallableArgs {
+    /// Two parameters are equal when they are the same kind of parameter with equal types.
+    pub fn type_equal(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+            && self.get_type().type_equal(other.get_type())
+    }
+
     pub fn get_type(&self) -> &PythonType {
+        &self.parameter().python_type
+    }
+
+    pub fn parameter(&self) -> &CallableParameter {
         match &self {
-            CallableArgs::Args(python_type) => python_type,
-            CallableArgs::PositionalOnly(python_type) => python_type,
-            CallableArgs::Positional(python_type) => python_type,
-            CallableArgs::Keyword(python_type) => python_type,
-            CallableArgs::KwArgs(python_type) => python_type,
-            CallableArgs::WithDefault(python_type) => python_type,
+            CallableArgs::Args(parameter) => parameter,
+            CallableArgs::PositionalOnly(parameter) => parameter,
+            CallableArgs::Positional(parameter) => parameter,
+            CallableArgs::Keyword(parameter) => parameter,
+            CallableArgs::KwArgs(parameter) => parameter,
+        }
+    }
+
+    pub fn parameter_mut(&mut self) -> &mut CallableParameter {
+        match self {
+            CallableArgs::Args(parameter) => parameter,
+            CallableArgs::PositionalOnly(parameter) => parameter,
+            CallableArgs::Positional(parameter) => parameter,
+            CallableArgs::Keyword(parameter) => parameter,
+            CallableArgs::KwArgs(parameter) => parameter,
         }
     }
 }
@@ -157,11 +276,23 @@ impl CallableType {
             signature,
             return_type,
             is_async,
+            overloads: vec![],
+            method_kind: MethodKind::Function,
         }
     }
+
+    pub fn is_overloaded(&self) -> bool {
+        !self.overloads.is_empty()
+    }
+
     pub fn type_equal(&self, other: &Self) -> bool {
-        // TODO: add check for args too. We need to check what should be the rule for
         self.return_type.type_equal(&other.return_type)
+            && self.signature.len() == other.signature.len()
+            && self
+                .signature
+                .iter()
+                .zip(other.signature.iter())
+                .all(|(a1, a2)| a1.type_equal(a2))
     }
 }
 
@@ -173,11 +304,37 @@ impl PartialEq for CallableType {
     }
 }
 
+/// The typed items of named tuples and typed dicts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassFields {
+    NamedTuple(Vec<NamedTupleField>),
+    TypedDict(Vec<TypedDictField>),
+}
+
+/// https://typing.readthedocs.io/en/latest/spec/namedtuples.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedTupleField {
+    pub name: String,
+    pub python_type: PythonType,
+    pub has_default: bool,
+}
+
+/// https://typing.readthedocs.io/en/latest/spec/typeddict.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedDictField {
+    pub name: String,
+    pub python_type: PythonType,
+    pub required: bool,
+    pub read_only: bool,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct CoroutineType {
+    /// The type of the value that awaiting the coroutine gives
     pub return_type: PythonType,
-    // TODO(coroutine_annotation): Any Any are send and yield type that are not implemented yet
-    // https://github.com/python/typing/issues/251
+    /// Coroutines of async functions are driven by the event loop, which sends and
+    /// receives values that the function cannot declare, so these are Any for them.
+    /// https://github.com/python/typing/issues/251
     pub send_type: PythonType,
     pub yield_type: PythonType,
 }
@@ -195,6 +352,11 @@ pub struct ClassType {
     pub specialized: Vec<PythonType>,
     // What types are allowed as base classes?
     pub base_classes: Vec<ClassType>,
+    /// The fields of classes that are created by calling `NamedTuple` or `TypedDict`.
+    /// These classes have no class body that declares the fields.
+    pub fields: Option<ClassFields>,
+    /// The metaclass that the class declares with the `metaclass` keyword
+    pub metaclass: Option<Box<ClassType>>,
 }
 
 impl ClassType {
@@ -209,6 +371,8 @@ impl ClassType {
             type_parameters,
             base_classes,
             specialized,
+            fields: None,
+            metaclass: None,
         }
     }
 
@@ -229,6 +393,18 @@ impl PartialEq for ClassType {
     }
 }
 
+/// Formats type arguments, a tuple of any length is written as `tuple[int, ...]`.
+fn format_type_arguments(arguments: &[PythonType]) -> String {
+    if let [PythonType::Unbounded(element)] = arguments {
+        return format!("{element}, ...");
+    }
+    arguments
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
 impl Display for ClassType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let args_str = self
@@ -237,12 +413,7 @@ impl Display for ClassType {
             .map(|arg| arg.to_string())
             .collect::<Vec<String>>()
             .join(", ");
-        let specialized = self
-            .specialized
-            .iter()
-            .map(|arg| arg.to_string())
-            .collect::<Vec<String>>()
-            .join(", ");
+        let specialized = format_type_arguments(&self.specialized);
         let fmt = if args_str.is_empty() && specialized.is_empty() {
             format!("(class) {}", self.details.name.clone())
         } else {
@@ -277,12 +448,7 @@ impl InstanceType {
 
 impl Display for InstanceType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let args_str = self
-            .specialized_type_parameters
-            .iter()
-            .map(|arg| arg.to_string())
-            .collect::<Vec<String>>()
-            .join(", ");
+        let args_str = format_type_arguments(&self.specialized_type_parameters);
         let fmt = if args_str.is_empty() {
             format!("(instance) {}", self.class_type.details.name.clone())
         } else {
@@ -298,7 +464,10 @@ impl Display for InstanceType {
 #[derive(Debug, Eq, Clone)]
 pub struct TypeVar {
     pub name: String,
+    /// The constraints of the type var, e.g. `TypeVar("T", int, str)`
     pub bounds: Vec<PythonType>,
+    /// The upper bound of the type var, e.g. `TypeVar("T", bound=int)`
+    pub upper_bound: Option<Box<PythonType>>,
     // TODO: We need to store the declaration path for types this is just to make it work for type
     // vars.
     pub decl_id: Id,
@@ -306,7 +475,9 @@ pub struct TypeVar {
 
 impl PartialEq for TypeVar {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.bounds == other.bounds
+        self.name == other.name
+            && self.bounds == other.bounds
+            && self.upper_bound == other.upper_bound
     }
 }
 
@@ -318,17 +489,40 @@ pub struct KnownValue {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum LiteralValue {
-    Bool,
-    Int,
-    Float,
-    Str,
+    Bool(bool),
+    Int(BigInt),
+    Str(String),
     None,
-    Bytes,
+    Bytes(Vec<u8>),
+    /// A member of an enum class, e.g. `Color.RED`
+    Enum(EnumMember),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EnumMember {
+    pub class: Box<ClassType>,
+    pub name: String,
 }
 
 impl Display for LiteralValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "")
+        match self {
+            LiteralValue::Bool(true) => write!(f, "True"),
+            LiteralValue::Bool(false) => write!(f, "False"),
+            LiteralValue::Int(i) => write!(f, "{i}"),
+            LiteralValue::Str(s) => write!(f, "{s:?}"),
+            LiteralValue::None => write!(f, "None"),
+            LiteralValue::Bytes(b) => {
+                write!(f, "b\"")?;
+                for byte in b {
+                    write!(f, "{}", std::ascii::escape_default(*byte))?;
+                }
+                write!(f, "\"")
+            }
+            LiteralValue::Enum(member) => {
+                write!(f, "{}.{}", member.class.details.name, member.name)
+            }
+        }
     }
 }
 
@@ -342,6 +536,7 @@ impl Display for PythonType {
         let type_str = match self {
             PythonType::None => "None",
             PythonType::Any => "Any",
+            PythonType::Never => "Never",
             PythonType::Module(_) => "Module",
             PythonType::Unknown => "Unknown",
             PythonType::Callable(callable_type) => {
@@ -350,7 +545,7 @@ impl Display for PythonType {
             PythonType::Coroutine(callable_type) => {
                 let fmt = format!(
                     "Coroutine[{}, {}, {}]",
-                    callable_type.send_type, callable_type.yield_type, callable_type.return_type
+                    callable_type.yield_type, callable_type.send_type, callable_type.return_type
                 );
                 return write!(f, "{}", fmt);
             }
@@ -382,6 +577,7 @@ impl Display for PythonType {
                 return write!(f, "TypeVar[{}, {}]", type_var.name, bounds);
             }
             PythonType::Optional(optional) => return write!(f, "Optional[{optional:}]"),
+            PythonType::Unbounded(element) => return write!(f, "*tuple[{element}, ...]"),
         };
 
         write!(f, "{}", type_str)
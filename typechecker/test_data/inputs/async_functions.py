@@ -0,0 +1,49 @@
+from typing import AsyncIterator, Awaitable, Generator
+
+
+async def fetch(url: str) -> bytes:
+    return b""
+
+
+class Ready:
+    def __await__(self) -> Generator[None, None, int]:
+        yield
+        return 1
+
+
+class Stream:
+    def __aiter__(self) -> "Stream":
+        return self
+
+    async def __anext__(self) -> str:
+        return ""
+
+
+class Session:
+    async def __aenter__(self) -> "Session":
+        return self
+
+    async def __aexit__(self, *args: object) -> None:
+        return None
+
+
+async def numbers():
+    yield 1
+
+
+async def main(pending: Awaitable[float]) -> None:
+    data = await fetch("a")
+    ready = await Ready()
+    value = await pending
+    async for line in Stream():
+        line
+    async for number in numbers():
+        number
+    async with Session() as session:
+        session
+    letters = [c async for c in Stream()]
+    lazy = (c async for c in Stream())
+
+
+async def gather(first: int, /, second: str, *rest: float, timeout: float, **options: bytes) -> None:
+    return None
@@ -0,0 +1,88 @@
+from typing import Any, Generic, TypeVar, overload
+
+T = TypeVar("T")
+
+
+class A:
+    def who(self) -> str:
+        return "A"
+
+
+class B(A):
+    pass
+
+
+class C(A):
+    def who(self) -> int:
+        return 1
+
+
+class D(B, C):
+    pass
+
+
+D().who()
+
+
+class Typed(Generic[T]):
+    def __init__(self, default: T) -> None:
+        self.default = default
+
+    @overload
+    def __get__(self, instance: None, owner: Any) -> "Typed[T]": ...
+    @overload
+    def __get__(self, instance: object, owner: Any) -> T: ...
+    def __get__(self, instance: Any, owner: Any) -> Any:
+        return self.default
+
+    def __set__(self, instance: object, value: T) -> None:
+        pass
+
+
+class Lazy:
+    def __get__(self, instance: object, owner: Any) -> float:
+        return 1.0
+
+
+class Model:
+    count = Typed(0)
+    ratio = Lazy()
+    lazy = Lazy()
+
+    def __init__(self) -> None:
+        self.count = "five"
+        self.ratio = "r"
+
+
+m = Model()
+m.count
+m.ratio
+m.lazy
+Model.count
+
+
+class Dynamic:
+    def __getattr__(self, name: str) -> bytes:
+        return b""
+
+
+Dynamic().anything
+
+
+class Meta(type):
+    registry: list[str]
+
+    def describe(cls) -> str:
+        return ""
+
+
+class Plugin(metaclass=Meta):
+    pass
+
+
+class SubPlugin(Plugin):
+    pass
+
+
+Plugin.registry
+SubPlugin.describe()
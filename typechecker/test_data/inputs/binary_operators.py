@@ -0,0 +1,30 @@
+a = 1 + 2
+b = 1 + 2.0
+c = 2.0 * 3
+d = "a" * 3
+e = 3 * "a"
+f = 1 / 2
+g = 7 // 2
+h = 1 + 2j
+i = True + 1
+j = "a" + "b"
+k = b"a" + b"b"
+l = 1 << 2
+m = 2**8
+n = (1 + 2) * 3.0
+
+
+class A:
+    def __add__(self, other: "A") -> int: ...
+
+
+class B:
+    def __radd__(self, other: A) -> str: ...
+
+
+o = A() + A()
+p = A() + B()
+q = [1] + [2]
+r = [1] + ["a"]
+s = (1, "a") + (2.0,)
+t = [1] * 2
@@ -0,0 +1,34 @@
+from dataclasses import dataclass
+
+
+@dataclass
+class Point:
+    x: int
+    y: int
+
+
+numbers = [1, 2, 3]
+names: list[str] = ["a", "b"]
+scores: dict[str, float] = {"a": 1.0}
+points = [Point(1, 2)]
+
+squares = [n * n for n in numbers]
+upper = {name.upper() for name in names}
+lengths = {name: len(name) for name in names}
+inverted = {score: name for name, score in scores.items()}
+evens = (n for n in numbers if n % 2 == 0)
+pairs = [(n, name) for n in numbers for name in names if n > 1]
+nested = [[x for x in range(n)] for n in numbers]
+coordinates = [p.x + p.y for p in points]
+enumerated = [index for index, name in enumerate(names)]
+
+total = sum(n for n in numbers)
+first = next(name for name in names)
+
+
+def scaled(factor: int) -> list[int]:
+    return [n * factor for n in numbers]
+
+
+def keys(mapping: dict[str, int]) -> list[str]:
+    return [key for key in mapping]
@@ -0,0 +1,79 @@
+import functools
+from contextlib import contextmanager
+from typing import Callable, Iterator, TypeVar, ParamSpec
+
+P = ParamSpec("P")
+R = TypeVar("R")
+
+
+def logged(func: Callable[P, R]) -> Callable[P, list[R]]:
+    ...
+
+
+def untyped(func):
+    return func
+
+
+class Temperature:
+    def __init__(self, celsius: float) -> None:
+        self._celsius = celsius
+
+    @property
+    def celsius(self) -> float:
+        return self._celsius
+
+    @celsius.setter
+    def celsius(self, value: float) -> None:
+        self._celsius = value
+
+    @property
+    def fahrenheit(self) -> float:
+        return self.celsius * 9 / 5 + 32
+
+    @staticmethod
+    def parse(text: str) -> float:
+        return float(text)
+
+    @classmethod
+    def freezing(cls) -> "Temperature":
+        return cls(0)
+
+    @functools.cache
+    def kelvin(self) -> float:
+        return self._celsius + 273.15
+
+    @logged
+    def scaled(self, factor: int) -> float:
+        return self._celsius * factor
+
+    @contextmanager
+    def session(self) -> Iterator[str]:
+        ...
+
+
+@logged
+def add(a: int, b: int) -> int:
+    return a + b
+
+
+@untyped
+def greet(name: str) -> str:
+    return name
+
+
+@functools.cache
+def fib(n: int) -> int:
+    return n
+
+
+t = Temperature(10)
+t.celsius
+t.fahrenheit
+Temperature.parse("1")
+t.parse("1")
+Temperature.freezing()
+t.kelvin()
+t.scaled(2)
+add(1, 2)
+greet("a")
+fib(3)
@@ -0,0 +1,51 @@
+from typing import Literal, Optional, Protocol, Union
+
+
+class Animal: ...
+
+
+class Dog(Animal): ...
+
+
+class Cat(Animal): ...
+
+
+a: int = 1
+b: int = "a"
+c: float = 1
+d: int = 1.0
+e: bool = 1
+f: Animal = Dog()
+g: Dog = Animal()
+h: Dog = Cat()
+i: Optional[int] = None
+j: int = None
+k: Union[int, str] = "a"
+l: int | str = b"a"
+m: Literal[1] = 1
+n: Literal[1] = 2
+o: Literal["a"] = "b"
+p: str = "a" + "b"
+q: list[int] = [1, 2]
+r: list[int] = ["a", "b"]
+s: list[float] = [1, 2]
+t: tuple[int, ...] = (1, 2)
+u: tuple[int, str] = (1, 2)
+v: object = Dog()
+
+
+class SupportsQuack(Protocol):
+    def quack(self, times: int) -> str: ...
+
+
+class Duck:
+    def quack(self, times: int) -> str: ...
+
+
+class Robot:
+    def quack(self, times: str) -> str: ...
+
+
+w: SupportsQuack = Duck()
+x: SupportsQuack = Robot()
+y: SupportsQuack = Dog()
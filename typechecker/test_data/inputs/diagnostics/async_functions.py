@@ -0,0 +1,42 @@
+from typing import Awaitable, Coroutine, overload
+
+
+async def fetch() -> int:
+    return 1
+
+
+def sync() -> None:
+    await fetch()
+    fetch()
+
+
+async def main() -> None:
+    fetch()
+    await fetch()
+    await 1
+    async for x in [1, 2]:
+        pass
+    async with open("a"):
+        pass
+    f = lambda: await fetch()
+
+
+def pending() -> Coroutine[object, object, int]:
+    return fetch()
+
+
+def awaitable() -> Awaitable[str]:
+    return fetch()
+
+
+await fetch()
+
+
+@overload
+async def load(key: int) -> int:
+    pass
+@overload
+async def load(key: str) -> str:
+    pass
+async def load(key):
+    return key
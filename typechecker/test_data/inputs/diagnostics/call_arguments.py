@@ -0,0 +1,81 @@
+from typing import Generic, TypeVar
+
+T = TypeVar("T")
+
+
+def f(a: int, b: str, c: float = 1.0) -> None: ...
+
+
+f(1, "a")
+f(1, "a", 2.0)
+f(1)
+f()
+f(1, "a", 2.0, 3)
+f(1, b="a")
+f(1, "a", b="b")
+f(1, "a", d=1)
+f("a", 1)
+f(1, "a", c="c")
+
+
+def g(a: int, /, b: int, *, c: int, d: int = 0) -> None: ...
+
+
+g(1, 2, c=3)
+g(1, 2, 3)
+g(a=1, b=2, c=3)
+g(1, b=2)
+
+
+def h(*args: int, **kwargs: str) -> None: ...
+
+
+h(1, 2, 3, x="a", y="b")
+h(1, "a", x=1)
+
+
+def k(a: int, b: int) -> None: ...
+
+
+args = [1, 2]
+kwargs = {"a": 1}
+k(*args)
+k(**kwargs)
+
+
+def identity(x: T) -> T: ...
+
+
+def first(x: list[T]) -> T: ...
+
+
+identity(1)
+first([1, 2])
+
+
+class A:
+    def __init__(self, x: int) -> None: ...
+
+    def method(self, y: str) -> None: ...
+
+
+a = A(1)
+A("a")
+A()
+a.method("a")
+a.method(1)
+a.method("a", "b")
+len([1, 2])
+len(1)
+print("a", 1, end="")
+A.method(a, "a")
+A.method(a, 1)
+A.method("a")
+
+
+class Box(Generic[T]):
+    def __init__(self, item: T) -> None: ...
+
+
+Box[str]("a")
+Box[str](1)
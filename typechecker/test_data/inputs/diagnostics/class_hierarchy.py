@@ -0,0 +1,42 @@
+from dataclasses import dataclass
+
+
+class Base:
+    pass
+
+
+class Derived(Base):
+    pass
+
+
+class Consistent(Derived, Base):
+    pass
+
+
+class Inconsistent(Base, Derived):
+    pass
+
+
+class Left(Base):
+    pass
+
+
+class Right(Base):
+    pass
+
+
+class Diamond(Left, Right):
+    pass
+
+
+class Crossed(Right, Left):
+    pass
+
+
+class Conflict(Diamond, Crossed):
+    pass
+
+
+@dataclass
+class Decorated(Base, Derived):
+    pass
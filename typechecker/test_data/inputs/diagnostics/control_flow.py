@@ -0,0 +1,157 @@
+import sys
+from typing import NoReturn
+
+
+def fail(message: str) -> NoReturn:
+    raise ValueError(message)
+
+
+def after_return() -> int:
+    return 1
+    print("never")
+
+
+def after_raise(x: int) -> int:
+    if x > 0:
+        return x
+    else:
+        raise ValueError("negative")
+    x = 2
+
+
+def after_no_return_call() -> int:
+    fail("no value")
+    return 1
+
+
+def after_exit() -> int:
+    sys.exit(1)
+
+
+def infinite_loop() -> int:
+    while True:
+        pass
+    return 1
+
+
+def loop_with_break(items: list[int]) -> int:
+    while True:
+        if items:
+            break
+    return 1
+
+
+def constant_condition():
+    if False:
+        print("never")
+    else:
+        print("always")
+
+
+def after_continue(items: list[int]):
+    for item in items:
+        continue
+        print(item)
+
+
+def possibly_unbound(flag: bool):
+    if flag:
+        value = 1
+    print(value)
+
+
+def bound_in_all_branches(flag: bool):
+    if flag:
+        value = 1
+    else:
+        value = 2
+    print(value)
+
+
+def unbound():
+    print(value)
+    value = 1
+
+
+def bound_in_loop(items: list[int]):
+    for item in items:
+        last = item
+    print(item, last)
+
+
+def bound_in_try():
+    try:
+        value = int("1")
+    except ValueError:
+        print(value)
+        return
+    print(value)
+
+
+def handler_name_deleted():
+    try:
+        pass
+    except ValueError as e:
+        pass
+    print(e)
+
+
+def deleted():
+    value = 1
+    del value
+    print(value)
+
+
+def match_exhaustive(command: str) -> int:
+    match command:
+        case "go":
+            result = 1
+        case _:
+            result = 2
+    return result
+
+
+def match_not_exhaustive(command: str):
+    match command:
+        case "go":
+            result = 1
+    print(result)
+
+
+def enclosing_and_global():
+    global counter
+    print(counter, len([x for x in range(3)]))
+    counter = 1
+
+
+def finally_runs(flag: bool) -> int:
+    try:
+        if flag:
+            return 1
+        value = 2
+    finally:
+        print(value)
+    return value
+
+
+def missing_return(flag: bool) -> int:
+    while flag:
+        return 1
+
+
+def reassigned_in_loop(flag: bool):
+    value = 1
+    for i in range(3):
+        if i:
+            value = None
+    print(value)
+    while flag:
+        if value:
+            value = 2
+    print(value)
+
+
+counter = 1
+for i in range(3):
+    if i:
+        counter = None
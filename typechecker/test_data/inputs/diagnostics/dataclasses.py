@@ -0,0 +1,70 @@
+from dataclasses import dataclass, field, replace
+from typing import ClassVar, dataclass_transform
+
+
+@dataclass
+class Point:
+    x: int
+    y: int = 0
+    label: str = field(default="origin", kw_only=True)
+    count: ClassVar[int] = 0
+
+
+Point(1)
+Point(1, 2, label="a")
+Point("1")
+Point()
+Point(1, 2, "a")
+
+
+@dataclass(order=True, frozen=True)
+class Version:
+    major: int
+    minor: int = field(default=0)
+    tags: list[str] = field(default_factory=list, init=False)
+
+
+Version(1, 2)
+Version(1, tags=[])
+v = Version(1)
+v.major = 2
+v < Version(2)
+Point(1) < Point(2)
+replace(v, minor=3)
+replace(v, patch=1)
+
+
+@dataclass
+class Point3D(Point):
+    z: int = 0
+
+
+Point3D(1, 2, 3, label="b")
+Point3D(1, 2, "3")
+
+
+@dataclass
+class Invalid:
+    a: int = 1
+    b: str
+
+
+@dataclass(slots=True)
+class Slotted:
+    __slots__ = ("a",)
+    a: int
+
+
+@dataclass_transform(kw_only_default=True)
+def model(model_class):
+    return model_class
+
+
+@model
+class User:
+    id: int
+    name: str = ""
+
+
+User(id=1)
+User(1)
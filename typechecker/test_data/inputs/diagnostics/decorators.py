@@ -0,0 +1,86 @@
+import functools
+from typing import Callable, TypeVar, ParamSpec
+
+P = ParamSpec("P")
+R = TypeVar("R")
+
+
+def retry(func: Callable[P, R]) -> Callable[P, R]:
+    ...
+
+
+class Account:
+    def __init__(self, balance: int) -> None:
+        self.balance = balance
+
+    @staticmethod
+    def parse(text: str) -> "Account":
+        return Account(int(text))
+
+    @classmethod
+    def empty(cls) -> "Account":
+        return cls(0)
+
+    @retry
+    def deposit(self, amount: int) -> None:
+        self.balance += amount
+
+
+@retry
+def transfer(source: Account, target: Account, amount: int) -> None:
+    ...
+
+
+@functools.cache
+def lookup(name: str) -> Account:
+    return Account(0)
+
+
+account = Account.parse("10")
+account.parse(10)
+Account.empty()
+Account.empty(1)
+account.deposit(5)
+account.deposit("5")
+transfer(account, account, "5")
+transfer(account, account)
+lookup("a")
+
+
+class Temperature:
+    def __init__(self) -> None:
+        self._celsius = 0.0
+
+    @property
+    def celsius(self) -> float:
+        return self._celsius
+
+    @celsius.setter
+    def celsius(self, value: float) -> None:
+        self._celsius = value
+
+    @property
+    def kelvin(self) -> float:
+        return self._celsius + 273.15
+
+    @property
+    def scale(self) -> str:
+        return "C"
+
+    @scale.setter
+    def scale(self, value: int) -> None:
+        pass
+
+
+class Reading(Temperature):
+    pass
+
+
+temperature = Temperature()
+temperature.celsius = 10.5
+temperature.celsius = 10
+temperature.celsius = "hot"
+Reading().celsius = "cold"
+temperature.kelvin = 1.0
+temperature.scale = 1
+temperature.scale = "F"
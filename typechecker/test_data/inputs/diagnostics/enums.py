@@ -0,0 +1,35 @@
+from enum import Enum
+from typing import Literal
+
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+
+
+def exhaustive(c: Color) -> str:
+    if c is Color.RED:
+        return "red"
+    elif c is Color.GREEN:
+        return "green"
+    elif c is Color.BLUE:
+        return "blue"
+
+
+def not_exhaustive(c: Color) -> str:
+    if c is Color.RED:
+        return "red"
+    elif c == Color.GREEN:
+        return "green"
+
+
+def exhaustive_with_or(c: Color) -> int:
+    if c == Color.RED or c == Color.GREEN:
+        return 1
+    if c is Color.BLUE:
+        return 2
+
+
+red: Literal[Color.RED] = Color.RED
+green: Literal[Color.RED] = Color.GREEN
@@ -0,0 +1,36 @@
+from typing import AsyncGenerator, Generator, Iterable, Iterator
+
+
+def numbers() -> Iterator[int]:
+    yield 1
+    yield "two"
+    yield
+
+
+def delegating() -> Generator[int, None, str]:
+    yield from numbers()
+    yield from ["a", "b"]
+    return 1
+
+
+def finished() -> Generator[int, None, str]:
+    yield 1
+    return "done"
+
+
+def iterable() -> Iterable[str]:
+    yield "a"
+
+
+def not_a_generator() -> int:
+    yield 1
+
+
+async def stream() -> AsyncGenerator[str, None]:
+    yield "a"
+    yield 2
+
+
+def untyped():
+    yield 1
+    yield "a"
@@ -0,0 +1,6 @@
+def add(a: int, b: int, scale: int = 1) -> int:
+    return (a + b) * scale + missing
+
+
+class Pair:
+    first = add(1, 2)
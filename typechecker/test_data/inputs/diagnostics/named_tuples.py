@@ -0,0 +1,41 @@
+from typing import NamedTuple
+
+
+class Point(NamedTuple):
+    x: int
+    y: int
+    label: str = ""
+
+
+Point(1, 2)
+Point(1, 2, "a")
+Point(1)
+Point(1, "2")
+
+p = Point(1, 2)
+a: int = p[0]
+b: str = p[-1]
+c: str = p[1]
+p[3]
+
+x, y, label = p
+s: str = x
+z, w = p
+
+p._replace(x=3)
+p._replace(z=3)
+q: Point = p._replace(label="b")
+
+Pair = NamedTuple("Pair", [("first", int), ("second", str)])
+
+pair = Pair(1, "a")
+Pair("a", "b")
+first: int = pair.first
+second: int = pair[1]
+
+
+class Point3D(Point):
+    pass
+
+
+Point3D(1, 2, "c", 4)
@@ -0,0 +1,14 @@
+import os
+
+a = None
+a()
+
+os()
+
+b = 1
+b()
+
+def func():
+    return 1
+
+func()
@@ -0,0 +1,28 @@
+from typing import overload
+
+
+@overload
+def convert(x: int) -> str: ...
+@overload
+def convert(x: str) -> int: ...
+def convert(x):
+    return x
+
+
+convert(1)
+convert("a")
+convert(1.5)
+convert(1, 2)
+
+
+class Box:
+    @overload
+    def __init__(self, value: int) -> None: ...
+    @overload
+    def __init__(self, value: str, label: str) -> None: ...
+    def __init__(self, value, label=""): ...
+
+
+Box(1)
+Box("a", "b")
+Box("a")
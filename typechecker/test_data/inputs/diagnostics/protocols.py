@@ -0,0 +1,56 @@
+from typing import Iterable, Protocol, SupportsInt, runtime_checkable
+
+
+class SupportsClose(Protocol):
+    def close(self) -> None: ...
+
+
+@runtime_checkable
+class Named(Protocol):
+    @property
+    def name(self) -> str: ...
+
+
+class Resource:
+    def close(self) -> None: ...
+
+
+class BadResource:
+    def close(self, force: bool) -> None: ...
+
+
+class Person:
+    name: str
+
+
+class Robot:
+    @property
+    def name(self) -> int: ...
+
+
+class Number:
+    def __int__(self) -> int: ...
+
+
+def use(resource: SupportsClose) -> None: ...
+
+
+def greet(named: Named) -> None: ...
+
+
+use(Resource())
+use(BadResource())
+use(Person())
+greet(Person())
+greet(Robot())
+a: SupportsInt = Number()
+b: SupportsInt = Person()
+c: Iterable[int] = [1, 2]
+d: Iterable[str] = [1, 2]
+
+
+def check(x: object, y: Person | Resource):
+    if isinstance(x, SupportsClose):
+        pass
+    if isinstance(y, Named):
+        y
@@ -0,0 +1,99 @@
+from typing import Literal, Optional
+
+
+def ok() -> int:
+    return 1
+
+
+def wrong_type() -> int:
+    return "a"
+
+
+def empty_return() -> int:
+    return
+
+
+def literal() -> Literal[1]:
+    return 1
+
+
+def optional(x: int) -> Optional[int]:
+    if x:
+        return x
+
+
+def returns_none() -> None:
+    return 1
+
+
+def missing(x: int) -> int:
+    if x:
+        return 1
+
+
+def both_branches(x: int) -> int:
+    if x:
+        return 1
+    else:
+        return 2
+
+
+def raises() -> int:
+    raise ValueError()
+
+
+def infinite_loop() -> int:
+    while True:
+        pass
+
+
+def loop_with_break() -> int:
+    while True:
+        break
+
+
+def try_except() -> int:
+    try:
+        return 1
+    except ValueError:
+        pass
+
+
+def try_finally() -> int:
+    try:
+        pass
+    finally:
+        return 1
+
+
+def match_all(x: int) -> int:
+    match x:
+        case 1:
+            return 1
+        case _:
+            return 2
+
+
+def match_partial(x: int) -> int:
+    match x:
+        case 1:
+            return 1
+
+
+def stub() -> int: ...
+
+
+def nested() -> str:
+    def inner() -> int:
+        return 1
+
+    return "a"
+
+
+async def coroutine() -> int:
+    return "a"
+
+
+class A:
+    def method(self) -> str:
+        return 1
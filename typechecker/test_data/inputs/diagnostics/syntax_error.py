@@ -0,0 +1,8 @@
+a = None
+b = a.
+
+def func(x=1, y):
+    return x
+
+c = 1 +
+a()
@@ -0,0 +1,29 @@
+pair = (1, "a")
+triple = (1, 2, 3)
+
+
+def homogeneous(values: tuple[int, ...]):
+    pass
+
+
+def fixed(values: tuple[int, str]):
+    pass
+
+
+def prefixed(values: tuple[int, *tuple[str, ...]]):
+    pass
+
+
+pair[2]
+pair[-3]
+a, b, c = pair
+(d, e), f = triple, 4
+(g, h, i), j = pair, 4
+homogeneous(triple)
+homogeneous(pair)
+fixed(pair)
+fixed(triple)
+prefixed((1, "a", "b"))
+prefixed(("a", "b"))
+x: tuple[int, int] = triple
+y: tuple[int, ...] = ()
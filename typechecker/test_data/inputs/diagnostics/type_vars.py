@@ -0,0 +1,13 @@
+from typing import TypeVar
+
+T = TypeVar("T")
+Number = TypeVar("Number", int, float)
+Bounded = TypeVar("Bounded", bound=str)
+Single = TypeVar("Single", str)
+
+
+def first(items: list[T]) -> T:
+    return items[0]
+
+
+first([1]) + "a"
@@ -0,0 +1,52 @@
+from typing import NotRequired, Required, TypedDict, Unpack
+from typing_extensions import ReadOnly
+
+
+class Movie(TypedDict):
+    name: str
+    year: int
+    rating: NotRequired[float]
+
+
+class PartialMovie(TypedDict, total=False):
+    name: Required[str]
+    year: int
+
+
+class Film(Movie):
+    director: str
+    id: ReadOnly[int]
+
+
+m: Movie = {"name": "Blade Runner", "year": 1982}
+m2: Movie = {"name": "Blade Runner"}
+m3: Movie = {"name": "Blade Runner", "year": "1982"}
+m4: Movie = {"name": "Blade Runner", "year": 1982, "genre": "sci-fi"}
+m5: PartialMovie = {"name": "Alien"}
+m6: PartialMovie = {"year": 1979}
+
+title: str = m["name"]
+year: str = m["year"]
+m["title"]
+m["year"] = 1983
+
+f: Film = {"name": "Alien", "year": 1979, "director": "Scott", "id": 1}
+f["id"] = 2
+f["director"] = "Ridley Scott"
+
+Movie(name="Alien", year=1979)
+Movie(name="Alien")
+
+Album = TypedDict("Album", {"title": str, "tracks": NotRequired[int]})
+
+album: Album = {"title": "Abbey Road"}
+album2: Album = {"tracks": 17}
+
+
+def play(**kwargs: Unpack[Movie]) -> None: ...
+
+
+play(name="Alien", year=1979)
+play(name="Alien", year="1979")
+play(name="Alien")
+play(name="Alien", year=1979, genre="sci-fi")
@@ -0,0 +1,82 @@
+import os
+from dataclasses import dataclass
+from enum import Enum
+
+
+class Account:
+    interest = 0.5
+
+    def __init__(self, owner: str) -> None:
+        self.owner = owner
+        self.balance = 0
+
+    def deposit(self, amount: int) -> None:
+        self.history = [amount]
+        self.balance += amount
+        self.balanse = amount
+        print(self.ballance)
+        print(self.history)
+
+    def close(self) -> None:
+        self.closed = True
+
+
+class Savings(Account):
+    def rate(self) -> float:
+        return self.interst + self.interest
+
+
+@dataclass
+class Point:
+    x: int
+    y: int
+
+
+class Color(Enum):
+    RED = 1
+
+
+account = Account("a")
+account.owner
+account.closed
+account.ownr
+account.deposit(1).missing
+Account.interest
+Account.__name__
+Account.balance_of
+Savings("b").deposit
+Point(1, 2).z
+Point.__dataclass_fields__
+Color.RED.value
+Color.RED.valeu
+Color.GREEN
+os.path.join("a", "b")
+os.getcwd()
+os.getcdw()
+os.__file__
+
+
+class Dynamic:
+    def __getattr__(self, name: str) -> int:
+        return 0
+
+
+Dynamic().anything
+
+
+def compute(values: list[int]) -> int:
+    total = 0
+    for value in values:
+        total += valeu
+    print(__name__, undefined_name)
+    squares = [v * v for v in values if v > limit]
+    return totl + len(squares)
+
+
+lenght = len([1])
+prnt(lenght)
+
+
+class Settings:
+    timeout = 10
+    retries = timout
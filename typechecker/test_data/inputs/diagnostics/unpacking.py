@@ -0,0 +1,17 @@
+from typing import Iterator
+
+
+def numbers() -> Iterator[int]:
+    yield 1
+
+
+a, b = 1
+c, *d = None
+e, f, *g = (1,)
+*h, i = ()
+j, *k = numbers()
+[l, m] = 1, 2, 3
+for n, o in [(1, 2, 3)]:
+    pass
+for p, q in [1, 2]:
+    pass
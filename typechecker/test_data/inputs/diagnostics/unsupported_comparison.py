@@ -0,0 +1,14 @@
+a = -"a"
+b = ~1.0
+c = 1 < "a"
+d = "a" >= 1
+e = 1 < 2 < "b"
+f = 1 in "abc"
+
+
+class A: ...
+
+
+g = -A()
+h = A() < A()
+i = A() == A()
@@ -0,0 +1,11 @@
+a = 1 + "a"
+b = "a" - "b"
+c = (1 + 2) * 3 - "b"
+d = [1, 2] @ 3
+
+
+class A: ...
+
+
+e = A() + 1
+f = 1 + A()
@@ -0,0 +1,59 @@
+from enum import Enum, Flag, IntEnum, StrEnum, auto
+from typing import Literal
+
+
+class Color(Enum):
+    RED = 1
+    GREEN = "green"
+    BLUE = auto()
+
+    _ignored = 0
+
+    def describe(self) -> str: ...
+
+
+class Priority(IntEnum):
+    LOW = auto()
+    HIGH = auto()
+
+
+class Mode(StrEnum):
+    READ = auto()
+    WRITE = auto()
+
+
+class Permission(Flag):
+    R = auto()
+    W = auto()
+
+
+red = Color.RED
+red.name
+red.value
+Color.BLUE.value
+Mode.READ.value
+red.describe()
+
+for color in Color:
+    color
+    color.value
+
+exact: Literal[Color.GREEN] = Color.GREEN
+
+
+def by_identity(c: Color):
+    if c is Color.RED:
+        c
+    else:
+        c
+    if c == Color.RED or c == Color.GREEN:
+        c
+    else:
+        c
+
+
+def flags(p: Permission):
+    if p is Permission.R:
+        p
+    else:
+        p
@@ -0,0 +1,56 @@
+from typing import AsyncGenerator, Generator, Iterator
+
+
+def count(limit: int):
+    i = 0
+    while i < limit:
+        yield i
+        i += 1
+    return "done"
+
+
+def delegate():
+    result = yield from count(3)
+    yield from ["a", "b"]
+    return result
+
+
+def bare():
+    yield
+
+
+def echo() -> Generator[int, str, bool]:
+    received = yield 1
+    received
+    return True
+
+
+def lines() -> Iterator[str]:
+    value = yield "a"
+    value
+
+
+async def ticks():
+    yield 1.0
+
+
+async def annotated_ticks() -> AsyncGenerator[float, None]:
+    yield 1.0
+
+
+def walk(depth: int):
+    yield depth
+    yield from walk(depth - 1)
+
+
+for number in count(2):
+    number
+
+counter = count(1)
+next(counter)
+delegate()
+bare()
+echo()
+ticks()
+annotated_ticks()
+walk(1)
@@ -0,0 +1,48 @@
+from typing import Generic, Optional, TypeVar
+
+T = TypeVar("T")
+U = TypeVar("U")
+N = TypeVar("N", bound=float)
+
+
+def first(xs: list[T]) -> T: ...
+
+
+def identity(x: T) -> T: ...
+
+
+def pair(x: T, y: U) -> tuple[T, U]: ...
+
+
+def either(x: T, y: T) -> T: ...
+
+
+def or_default(x: Optional[T], default: T) -> T: ...
+
+
+def largest(x: N) -> N: ...
+
+
+def to_str(x: int) -> str: ...
+
+
+class Box(Generic[T]):
+    def __init__(self, value: T) -> None:
+        self.value = value
+
+    def get(self) -> T: ...
+
+
+a = first([1, 2, 3])
+b = [1, 2].pop()
+c = identity("a")
+d = pair(1, "a")
+e = either(1, "a")
+f = or_default(None, 1)
+h = largest(1)
+i = Box(1)
+j = i.get()
+k = Box(to_str(1)).get()
+l = {"a": 1}.get("a")
+m = Box[str]("a")
+n = m.get()
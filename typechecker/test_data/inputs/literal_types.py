@@ -0,0 +1,16 @@
+from typing import Literal
+
+a: Literal[1] = 1
+b: Literal[2] = 2
+c: Literal[0x10] = 16
+d: Literal[100_000_000_000_000_000_000] = 100_000_000_000_000_000_000
+e: Literal["a"] = "a"
+f: Literal["a\tb"] = "a\tb"
+g: Literal[b"\x00b"]
+h: Literal[True] = True
+i: Literal[None] = None
+
+j = 1.5j
+k: "int" = 1
+l: Literal[1.5]
+m: Literal[...]
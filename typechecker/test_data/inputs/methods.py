@@ -0,0 +1,44 @@
+from typing import Self, TypeVar
+
+T = TypeVar("T", bound="Shape")
+
+
+class Shape:
+    def __init__(self, name: str) -> None:
+        self.name = name
+        self.scale = 1.0
+
+    def set_scale(this, scale: float) -> Self:
+        this.scale = scale
+        return this
+
+    def set_name(self, name: str) -> Self:
+        self.name = name
+        return self
+
+    @classmethod
+    def create(cls) -> Self:
+        return cls("shape")
+
+    def copy(self: T) -> T:
+        return self
+
+    @staticmethod
+    def area(self: float) -> float:
+        return self
+
+
+class Circle(Shape):
+    def radius(self) -> float:
+        return self.scale
+
+
+circle = Circle("c")
+circle.set_scale(2.0)
+circle.set_scale(2.0).set_name("d").radius()
+Circle.create()
+circle.copy()
+circle.area(1.0)
+Shape.area
+Shape.set_name(circle, "e")
+Shape.create
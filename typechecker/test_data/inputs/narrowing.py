@@ -0,0 +1,121 @@
+from typing import Callable, Literal, Optional, Union
+from typing_extensions import TypeGuard, TypeIs
+
+
+class Cat:
+    kind: Literal["cat"]
+
+    def meow(self) -> str: ...
+
+
+class Dog:
+    kind: Literal["dog"]
+
+    def bark(self) -> int: ...
+
+
+def none_check(x: Optional[int]):
+    if x is not None:
+        x
+    else:
+        x
+    x
+
+
+def early_return(x: Optional[str]) -> str:
+    if x is None:
+        return ""
+    return x
+
+
+def truthiness(x: Union[int, None]):
+    if x:
+        x
+    if not x:
+        x
+    y = x and x
+
+
+def instance_check(x: Union[int, str, Cat]):
+    if isinstance(x, (int, str)):
+        x
+    else:
+        x
+    if isinstance(x, Cat):
+        x.meow()
+
+
+def reassignment():
+    x = 1
+    x
+    x = "a"
+    x
+    if x:
+        x = 1.0
+    x
+
+
+def literals(x: Literal["a", "b", "c"]):
+    if x == "a":
+        x
+    elif x != "b":
+        x
+    else:
+        x
+    if x in ("a", "b"):
+        x
+    else:
+        x
+
+
+def callables(x: Union[int, Callable[[], int]]):
+    if callable(x):
+        x
+    else:
+        x
+
+
+def is_str(x: object) -> TypeGuard[str]: ...
+
+
+def is_int(x: Union[int, str]) -> TypeIs[int]: ...
+
+
+def guards(x: object, y: Union[int, str]):
+    if is_str(x):
+        x
+    if is_int(y):
+        y
+    else:
+        y
+
+
+def discriminated(pet: Union[Cat, Dog]):
+    if pet.kind == "cat":
+        pet
+    else:
+        pet
+
+
+def in_condition(x: Optional[int]):
+    z = x is not None and x
+    w = x if x is not None else 0
+    assert x
+    x
+
+
+def guard_join(y: Union[int, str]):
+    if is_str(y):
+        y
+    y
+
+
+def annotated_assignment():
+    n: Optional[int] = None
+    n
+    m: Union[int, str] = 1
+    m
+
+
+k: Optional[int] = None
+k
@@ -0,0 +1,55 @@
+import re
+from typing import Any, Literal, Union, overload
+
+
+@overload
+def convert(x: int) -> str: ...
+@overload
+def convert(x: str) -> int: ...
+@overload
+def convert(x: bytes, encoding: str) -> float: ...
+def convert(x, encoding=""):
+    return x
+
+
+convert(1)
+convert("a")
+convert(b"a", "utf-8")
+
+
+def expand(value: Union[int, str], unknown: Any):
+    convert(value)
+    convert(unknown)
+
+
+@overload
+def read(mode: Literal["r"]) -> str: ...
+@overload
+def read(mode: Literal["rb"]) -> bytes: ...
+def read(mode: str) -> Union[str, bytes]: ...
+
+
+read("r")
+read("rb")
+
+
+class Parser:
+    @overload
+    def parse(self, data: str) -> str: ...
+    @overload
+    def parse(self, data: int, *, strict: bool) -> int: ...
+    def parse(self, data, *, strict=False):
+        return data
+
+
+Parser().parse("a")
+Parser().parse(1, strict=True)
+
+d: dict[str, int] = {"a": 1}
+d.get("a")
+d.get("a", "default")
+
+open("file")
+open("file", "rb")
+
+re.match("a", "b")
@@ -0,0 +1,5 @@
+values = [1, 2, 3]
+
+squares = [v * v for v in values]
+pairs = {k: v for k, v in zip(values, values) if k}
+nested = [[w for w in range(v)] for v in values]
@@ -0,0 +1,34 @@
+from typing import Tuple
+
+pair = (1, "a")
+empty = ()
+nested = (pair, 2.0)
+numbers = tuple([1, 2])
+spread = (0, *pair, None)
+rest = (0, *[1, 2])
+
+
+def first(values: tuple[int, ...]) -> int:
+    return values[0]
+
+
+def head(values: tuple[int, *tuple[str, ...]]):
+    values[0]
+    values[1]
+    values[-1]
+
+
+def old_style(values: Tuple[int, str]):
+    values[1]
+
+
+pair[0]
+pair[-1]
+pair[0:1]
+pair[::-1]
+spread[1:]
+nested[0][1]
+
+a, b = pair
+(c, d), e = nested
+first((1, 2, 3))
@@ -0,0 +1,28 @@
+a = -1
+b = +2.0
+c = ~3
+d = -True
+e = not 1
+f = 1 < 2
+g = 1 == "a"
+h = 1 < 2.0 <= 3
+i = "a" in "abc"
+j = 1 in [1, 2]
+k = None is None
+l = 1 is not None
+m = "a" < "b"
+
+
+class A:
+    def __neg__(self) -> str: ...
+    def __lt__(self, other: int) -> int: ...
+
+
+class B:
+    def __gt__(self, other: A) -> str: ...
+
+
+n = -A()
+o = A() < 1
+p = A() < B()
+q = A() < 1 < 2
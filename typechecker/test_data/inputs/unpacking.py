@@ -0,0 +1,41 @@
+from typing import Iterator
+
+
+class Point:
+    def __init__(self) -> None:
+        self.x, self.y = 1, 2.0
+
+
+class Pair:
+    def __enter__(self) -> tuple[int, str]:
+        return (1, "a")
+
+    def __exit__(self, *args: object) -> None:
+        pass
+
+
+def names() -> Iterator[str]:
+    yield "a"
+
+
+values = (1, "a", 2.0, None)
+first, *middle, last = values
+*init, tail = [1, 2, 3]
+[head, rest] = 1, "b"
+(a, (b, c)), d = (1, ("x", 2.0)), None
+x = y = 0
+p, *q = names()
+numbers: list[int] = []
+numbers[0], z = 1, "z"
+
+for index, *items in [(1, "a", "b")]:
+    index
+    items
+
+with Pair() as (number, text):
+    number
+    text
+
+point = Point()
+point.x
+point.y